@@ -0,0 +1,257 @@
+//! ASCII-armor text transport encoding for CPACK frames.
+//!
+//! A `.cpack` frame (see [`crate::frame`]) is raw binary and breaks when
+//! pasted into issues, emails, or other text-only channels. [`armor`] wraps
+//! a frame in a base64 text envelope, line-wrapped at [`LINE_WIDTH`]
+//! characters and bracketed by BEGIN/END marker lines, with a trailing
+//! CRC-32 checksum line computed over the binary frame - so corruption
+//! introduced in transit (a dropped line, a mangled copy-paste) is caught
+//! immediately, before the inner SHA-256 integrity check on the decoded
+//! frame even runs. [`dearmor`] reverses it. The binary frame itself is
+//! never altered by either direction, so determinism is unaffected.
+
+use crate::frame::FrameError;
+
+/// Marker line opening an armored envelope.
+const ARMOR_BEGIN: &str = "-----BEGIN CPACK-----";
+
+/// Marker line closing an armored envelope.
+const ARMOR_END: &str = "-----END CPACK-----";
+
+/// Base64 body lines are wrapped to this many characters.
+const LINE_WIDTH: usize = 64;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Armor a raw CPACK frame into a text envelope.
+pub fn armor(frame: &[u8]) -> String {
+    let encoded = base64_encode(frame);
+
+    let mut out = String::with_capacity(encoded.len() + encoded.len() / LINE_WIDTH + 64);
+    out.push_str(ARMOR_BEGIN);
+    out.push('\n');
+    for line in encoded.as_bytes().chunks(LINE_WIDTH) {
+        out.push_str(std::str::from_utf8(line).expect("base64 alphabet is ASCII"));
+        out.push('\n');
+    }
+    out.push_str(&format!("CRC32:{:08x}\n", crc32(frame)));
+    out.push_str(ARMOR_END);
+    out.push('\n');
+    out
+}
+
+/// Reverse [`armor`]. Tolerant of blank lines or stray text outside the
+/// BEGIN/END markers (an email quoting the envelope, a leading comment),
+/// but rejects a missing/malformed envelope or a checksum that doesn't
+/// match the decoded bytes.
+pub fn dearmor(text: &str) -> Result<Vec<u8>, FrameError> {
+    let begin_at = text
+        .find(ARMOR_BEGIN)
+        .ok_or_else(|| FrameError::MalformedArmor("missing BEGIN CPACK marker".to_string()))?;
+    let end_at = text[begin_at..]
+        .find(ARMOR_END)
+        .map(|offset| begin_at + offset)
+        .ok_or_else(|| FrameError::MalformedArmor("missing END CPACK marker".to_string()))?;
+
+    let body = &text[begin_at + ARMOR_BEGIN.len()..end_at];
+
+    let mut base64_chars = String::new();
+    let mut checksum = None;
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(hex) = line.strip_prefix("CRC32:") {
+            let value = u32::from_str_radix(hex.trim(), 16)
+                .map_err(|_| FrameError::MalformedArmor(format!("bad CRC32 line: {line}")))?;
+            checksum = Some(value);
+            continue;
+        }
+        base64_chars.push_str(line);
+    }
+
+    let checksum = checksum
+        .ok_or_else(|| FrameError::MalformedArmor("missing CRC32 checksum line".to_string()))?;
+    let frame = base64_decode(&base64_chars)
+        .map_err(|e| FrameError::MalformedArmor(format!("bad base64 body: {e}")))?;
+
+    let actual = crc32(&frame);
+    if actual != checksum {
+        return Err(FrameError::ArmorChecksumMismatch {
+            expected: checksum,
+            actual,
+        });
+    }
+
+    Ok(frame)
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+        let triple = ((b0 as u32) << 16) | ((b1 as u32) << 8) | (b2 as u32);
+
+        out.push(BASE64_ALPHABET[((triple >> 18) & 0x3F) as usize] as char);
+        out.push(BASE64_ALPHABET[((triple >> 12) & 0x3F) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[((triple >> 6) & 0x3F) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(triple & 0x3F) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+fn base64_decode(text: &str) -> Result<Vec<u8>, String> {
+    let clean: Vec<u8> = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if clean.is_empty() {
+        return Ok(Vec::new());
+    }
+    if clean.len() % 4 != 0 {
+        return Err("base64 length is not a multiple of 4".to_string());
+    }
+
+    let mut out = Vec::with_capacity(clean.len() / 4 * 3);
+    for quad in clean.chunks(4) {
+        let mut sextets = [0u8; 4];
+        let mut pad = 0usize;
+        for (i, &byte) in quad.iter().enumerate() {
+            if byte == b'=' {
+                pad += 1;
+                sextets[i] = 0;
+            } else {
+                sextets[i] = base64_value(byte)?;
+            }
+        }
+        let triple = ((sextets[0] as u32) << 18)
+            | ((sextets[1] as u32) << 12)
+            | ((sextets[2] as u32) << 6)
+            | (sextets[3] as u32);
+
+        out.push((triple >> 16) as u8);
+        if pad < 2 {
+            out.push((triple >> 8) as u8);
+        }
+        if pad < 1 {
+            out.push(triple as u8);
+        }
+    }
+    Ok(out)
+}
+
+fn base64_value(byte: u8) -> Result<u8, String> {
+    BASE64_ALPHABET
+        .iter()
+        .position(|&c| c == byte)
+        .map(|pos| pos as u8)
+        .ok_or_else(|| format!("invalid base64 character: {:?}", byte as char))
+}
+
+/// Standard CRC-32 (IEEE 802.3 polynomial 0xEDB88320, reflected), computed
+/// bit-by-bit rather than via a precomputed table - armored frames are
+/// small text payloads, so the simpler implementation is plenty fast.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc: u32 = 0xFFFF_FFFF;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_armor_roundtrip() {
+        let frame = b"not a real cpack frame, just some bytes \x00\x01\xff".to_vec();
+        let armored = armor(&frame);
+        assert!(armored.starts_with(ARMOR_BEGIN));
+        assert!(armored.trim_end().ends_with(ARMOR_END));
+        assert_eq!(dearmor(&armored).unwrap(), frame);
+    }
+
+    #[test]
+    fn test_armor_empty_frame_roundtrips() {
+        let armored = armor(&[]);
+        assert_eq!(dearmor(&armored).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_armor_is_deterministic() {
+        let frame: Vec<u8> = (0..300u32).map(|i| (i % 256) as u8).collect();
+        assert_eq!(armor(&frame), armor(&frame));
+    }
+
+    #[test]
+    fn test_armor_wraps_body_lines_at_line_width() {
+        let frame: Vec<u8> = (0..300u32).map(|i| (i % 256) as u8).collect();
+        let armored = armor(&frame);
+        for line in armored.lines() {
+            if line == ARMOR_BEGIN || line == ARMOR_END || line.starts_with("CRC32:") {
+                continue;
+            }
+            assert!(line.len() <= LINE_WIDTH, "line too long: {}", line.len());
+        }
+    }
+
+    #[test]
+    fn test_dearmor_tolerates_surrounding_noise() {
+        let frame = b"hello cpack".to_vec();
+        let armored = armor(&frame);
+        let noisy = format!("---------- Forwarded message ----------\n\n{armored}\n-- \nSent from my phone\n");
+        assert_eq!(dearmor(&noisy).unwrap(), frame);
+    }
+
+    #[test]
+    fn test_dearmor_rejects_missing_markers() {
+        let err = dearmor("just some plain text, no armor here").unwrap_err();
+        assert!(matches!(err, FrameError::MalformedArmor(_)));
+    }
+
+    #[test]
+    fn test_dearmor_rejects_missing_checksum_line() {
+        let frame = b"hello".to_vec();
+        let armored = armor(&frame);
+        let stripped: String = armored
+            .lines()
+            .filter(|l| !l.starts_with("CRC32:"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let err = dearmor(&stripped).unwrap_err();
+        assert!(matches!(err, FrameError::MalformedArmor(_)));
+    }
+
+    #[test]
+    fn test_dearmor_rejects_checksum_mismatch() {
+        let frame = b"original bytes that need more than one base64 character".to_vec();
+        let armored = armor(&frame);
+        let body_start = armored.find('\n').unwrap() + 1;
+        let mut bytes = armored.into_bytes();
+        bytes[body_start] = if bytes[body_start] == b'A' { b'B' } else { b'A' };
+        let corrupted = String::from_utf8(bytes).unwrap();
+        let err = dearmor(&corrupted).unwrap_err();
+        assert!(matches!(err, FrameError::ArmorChecksumMismatch { .. }));
+    }
+
+    #[test]
+    fn test_base64_roundtrip_all_byte_values() {
+        let frame: Vec<u8> = (0..=255u16).map(|b| b as u8).collect();
+        let armored = armor(&frame);
+        assert_eq!(dearmor(&armored).unwrap(), frame);
+    }
+}
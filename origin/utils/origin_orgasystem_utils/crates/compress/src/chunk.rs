@@ -0,0 +1,253 @@
+//! FastCDC content-defined chunking.
+//!
+//! Splits a file's bytes into variable-length chunks at data-dependent
+//! boundaries rather than fixed offsets, so a small edit only perturbs
+//! the chunk(s) around the edit — everything before and after still cuts
+//! at the same boundaries and dedupes against chunks already stored.
+//!
+//! The cut point is driven by a Gear-hash rolling checksum: for each byte
+//! `b` at the current position, `fp = (fp << 1).wrapping_add(GEAR[b])`. A
+//! chunk boundary falls wherever `fp & mask == 0`. Chunking is
+//! "normalized" (FastCDC's Algorithm 2): below the target size we apply
+//! `MASK_S`, which has more one-bits and so is less likely to match,
+//! discouraging small chunks; once past the target we switch to the
+//! looser `MASK_L` so the chunk converges back toward the target size.
+//! A hard `MAX_SIZE` bounds worst-case chunk size, and a `MIN_SIZE` is
+//! skipped unconditionally so no chunk (other than a final remainder)
+//! falls below it.
+
+use sha2::{Digest, Sha256};
+
+/// No chunk (other than a trailing remainder) is smaller than this.
+const MIN_SIZE: usize = 4 * 1024;
+
+/// Target chunk size: cut points become likely once a chunk reaches this.
+const NORMAL_SIZE: usize = 8 * 1024;
+
+/// No chunk exceeds this size; a cut is forced if no boundary is found.
+const MAX_SIZE: usize = 64 * 1024;
+
+/// Bits in the cut-point mask for a `NORMAL_SIZE` average (`log2(8192)`).
+const NORMAL_BITS: u32 = 13;
+
+/// Stricter mask (more one-bits, lower match probability) used while a
+/// chunk is still smaller than `NORMAL_SIZE`.
+const MASK_S: u64 = (1u64 << (NORMAL_BITS + 1)) - 1;
+
+/// Looser mask (fewer one-bits, higher match probability) used once a
+/// chunk has grown past `NORMAL_SIZE`, to pull it back toward the target.
+const MASK_L: u64 = (1u64 << (NORMAL_BITS - 2)) - 1;
+
+/// Fixed 256-entry Gear table for the rolling hash in `next_cut`. Generated
+/// once from a deterministic PRNG seed; must never change, since it
+/// defines where existing chunk boundaries fall.
+#[rustfmt::skip]
+const GEAR: [u64; 256] = [
+    0xAD01C31EF3B4E131, 0xA0C83B956F079D76, 0xB79EC3703564A849, 0x85BC4E60C58AE2F3,
+    0x06E3ACD627E03F12, 0x155B35EF59C8FA88, 0x5AA196843B810724, 0x6F896DC964DA5B58,
+    0xA6A17BA16C79C1AB, 0x2B6659F4332562F1, 0x46888A8DF04B6BD4, 0xDC704C147BD0D160,
+    0xF76B1A48D2935909, 0x5713670FBF841C7C, 0xCD15388614B4D036, 0x30BD7ABF1AE5520E,
+    0x1DB5FA4C1CB224B8, 0x188456E620132BFB, 0x2AD4C4F956F5A808, 0xBC0514035A46BCE4,
+    0x5D8BBAE9BE05FB44, 0x17AE8D2E802B7C77, 0x671E0A0896BC8D10, 0x9772197A67DB4D58,
+    0xD8FF5775584CD588, 0x57917F4852870BE9, 0x24898B1B26DDBED0, 0x26E389F4477A3674,
+    0x423385C5A87C91E5, 0x63568B3B2F6BD05B, 0x1593A2AB325573AC, 0x2078FA9AF05269BF,
+    0xE644AD486BB3D88F, 0xB3F9EEF35B8613F5, 0x09C806755881D818, 0x1677597C785BE982,
+    0xE0BF0B8EEBBE5C6D, 0xDC7998D74E4FBC27, 0x682BED456A8E0B19, 0x7D5214971257D709,
+    0x17C181243D74CF1F, 0x74B0CA58A5205D98, 0x27E7768C0DF71AE6, 0xC7EEDE72B786EE56,
+    0xBDA9BDA2DB56496C, 0x3A7BB30D2A97E164, 0x6324C6B2C2EDC100, 0xE30955D22554E170,
+    0x98DD3A39AEE34580, 0xBC63890800B0450B, 0xA56BAEDD6F355BE3, 0xBBE89F9B62F7C963,
+    0x5EA6F96EACC7BBBF, 0xC7230DB0FE36E7B8, 0x8A86B208501D636B, 0xE1F7BB24F8733962,
+    0x67C18CC702BA1E77, 0x6BDE4135EC2C4A47, 0xE9519AF372C3E5D5, 0xE2DFBDCF7CF54690,
+    0xF576CC8252743825, 0xA950ABCDC78D8DC1, 0x9EE74EB2820EA7B6, 0x583A9F95722A22C9,
+    0xB096D36FD45E7831, 0xF41AC2C90B5AF644, 0x415B08C40FC4F888, 0x108B9F2CCD17C597,
+    0x4552825FAC02D1D4, 0xAE9E290559ACB39B, 0x36881B9A6CBC993E, 0xE797204BF488679F,
+    0xBB7A425FB6BE9851, 0xF21488207C687E74, 0xB717F0006AE49BEB, 0xBAADE3DD292ED014,
+    0xF0BFC37C3123DA9A, 0xBBA6381229818528, 0xBEA9EB8C88D43966, 0x9FE6619C8970CF72,
+    0x7CC3CFB3528650D9, 0x6DE1AB4037975265, 0x8EA31DD3C9AC57ED, 0x64C142ECFF335AC4,
+    0xF3A7A279C6036D06, 0xC108DB0D913B346A, 0xD869C83861831DB0, 0x854EC8BE6CC79F7C,
+    0x78A141A3DD2E8737, 0xF56AF0F9DA15DED9, 0x3B95D43DE235CAF0, 0x82BF5D5D7DE770D7,
+    0xA50759D69C1342DE, 0xF3243BC5152B5858, 0x3757E8853F5EFC1E, 0x8803F50574F6DCF6,
+    0x25BF2A3C2154BC42, 0xF8A45DA5712C9E3E, 0xF63B386515F02428, 0xC286CE86B905BE79,
+    0x423D7A5B37A5248A, 0x028D00D76C37ABDA, 0x8510A314822C770F, 0x587EC99AEA30FD7D,
+    0xA14326ED5DCE1955, 0xCAAA2E18238D74F7, 0x9B2C040C19F34A74, 0x7FCF12851486C44A,
+    0xD6F00A1CD1B8CDDD, 0xE2C35A86E8086ABB, 0x57ADB58B392EA9D7, 0xE19AA65A466F451B,
+    0x3CB616F08E6EFE9A, 0x044BE2DF3D422FF4, 0x4BA5128219E8EADB, 0x81429578CDDB9AA4,
+    0x5C0EC66217E73FD9, 0x3E0631B291CF6D74, 0xD5136D4C93DCDA3E, 0x218393BACFCFC388,
+    0xCC1F9B1DB2D88B28, 0x8F9F47368765DE1F, 0x636403AF84821CD3, 0xF4999D10C1F7B329,
+    0x4E87D289F44DCE4B, 0xD5A4707054FC0303, 0x90A270B03DEE1985, 0x2F52F46B6E21CA08,
+    0x83F067CA371D9287, 0x7A156B14F001E55D, 0x331EAB0CB66C8F4E, 0xA183371630165DA5,
+    0x731B1F2AE08A8B9E, 0x56A7443C93CFBD93, 0x72C9383BC62AA1B2, 0x03773DD5B7AC6D33,
+    0x8C349DCB93DC59B7, 0x9CBB568BFD52A40D, 0x8FE7667258217861, 0xDFD251021C3BD177,
+    0xC7546644000FA7B1, 0xCC2ECC99053A8723, 0xA79E47564138F587, 0x8820D246F3A7CACC,
+    0x6F19B4D84BE52EC7, 0x3601F66B4725FE4C, 0xEE4BEC5525403AE5, 0xD50586B14BE8853C,
+    0xE9CB9EDC2D0B4FCD, 0x1C5B5CBF503D15B0, 0xEEC9E4AA5E683047, 0x4679E0911A15023E,
+    0x043D83A8F0F7A0AC, 0x341A43B3C14F84AA, 0x3D001DB083226869, 0x8C5004860D42DB8C,
+    0xF02F10C05A5E9A04, 0xC148E08819E33CEB, 0xBFA8923C227DF5FE, 0x4D229A6E9FFFDE7F,
+    0x977B1C36CA7C6416, 0xE9F6E56C94A250C0, 0x6DD6E01E85A85FCC, 0xA9C504FEC21E252B,
+    0x29C6F303F822F8E9, 0x070D4300CBF6413B, 0x76EFA1488BF56604, 0x09718E6F1074A12A,
+    0xB74A465515B70D10, 0x22DAF6D1FCF5BE1F, 0xAA974F5EF40DD69D, 0x337EC941114E9FFC,
+    0xEE416D6F9E2E67AC, 0xDDBE42DAAB6E1655, 0x0CD93F649C18547A, 0x014AAD4AF65DB41F,
+    0xE15CA3A8E65A206E, 0x447E5BE0C73089CC, 0xE45126E1EDF72D1A, 0x061FCB460294E4BC,
+    0xA092DB2E3535B9D7, 0xADAB7CB6FD90CE68, 0x85D3DE0D148EAEF1, 0x16B13E36A699D72B,
+    0x9B9FC396E07D4F82, 0xA9E46E4E6755BFF1, 0xA02A4575A96B9D29, 0x909967C489BB4C58,
+    0xE445E4C87F8820B8, 0x65CB40F80B128C5F, 0x74B305DE08815977, 0x7D12715284EBEAED,
+    0x206284BBE63483A3, 0x87E866532C92AA8C, 0xD077006CC29F215E, 0xA50370FDECD65876,
+    0xAAA8A1128E57BACF, 0xEE6C7C9A65C611B8, 0xFEDFA7D6DBCC8450, 0xEAC012D5BF791F8B,
+    0x7D09E743DA6110FD, 0xD4EB2FB42AA60293, 0xD40E3F4176E66E30, 0x97321FAB8E82709D,
+    0xDFE424A68E6524C7, 0x0D4BC9578CAF9BFA, 0x3AB3B10A33C17722, 0xD565B8160DDFE614,
+    0xE9F71609497532AC, 0xF99862B22548CA89, 0xCCD90FF05A66E2FA, 0xDBCE6BD0FF5505DC,
+    0xD0CD71CCE360AE5D, 0x2ABD4361E23191F6, 0x0FECEE477619162D, 0x38867C91E005A4BF,
+    0x9E6E415CD92DBE44, 0x595DFB8C433C82D1, 0x23B40741D71115B8, 0x8F32E9497E348436,
+    0x5C8C3CFC9C8A15DB, 0xBFCDF61EA6CE1D2E, 0x4418BB974560F40A, 0x4179CC7C823DA0E7,
+    0xAB9152B40FCDCF85, 0x977044166B2CADEB, 0x939C2C932A9DEC45, 0x09807600E770CDDE,
+    0xF1E6C5DDA8704E91, 0x819C84385E2CF4C8, 0xD3341DF382B34FDA, 0x857767D5AD306537,
+    0xB175BC5AD4F5CBB7, 0x30234070E2D955F1, 0x047231E1F6234B42, 0x4D7EF9A00563FDD1,
+    0x3570A8470D99BA0C, 0x1E3A770E0F0AF253, 0x2C7529CB2CC78287, 0x8D2D28FE4FBDA051,
+    0x7BFC9FBA93662236, 0x500A50FFA8EC18A8, 0x3E6F9B85EECAD93B, 0x1976878416851B2A,
+    0x8D5AACCEE74697C0, 0xA7F2E2089FEE6BB6, 0x2490C1431F1F932D, 0x073A57D6DC2B5A22,
+    0x989DB878B269CFDB, 0x7C1BBD3A655848C7, 0x22694D55771A1E61, 0x6B4AFBDC46FE3611,
+    0x384AA3D2A380A70F, 0x3A76DF803C235DD6, 0x57628DC790F7066B, 0x718EE8C58DC1FD75,
+];
+
+/// Find the length of the next chunk at the front of `data`.
+///
+/// Returns `data.len()` if that's already `<= MIN_SIZE` (nothing left to
+/// normalize), otherwise scans for a Gear-hash cut point between
+/// `MIN_SIZE` and `MAX_SIZE`, falling back to `MAX_SIZE` if none is found.
+fn next_cut(data: &[u8]) -> usize {
+    let len = data.len();
+    if len <= MIN_SIZE {
+        return len;
+    }
+
+    let mut fp: u64 = 0;
+    let normal_size = NORMAL_SIZE.min(len);
+    let max_size = MAX_SIZE.min(len);
+
+    let mut i = MIN_SIZE;
+    while i < normal_size {
+        fp = (fp << 1).wrapping_add(GEAR[data[i] as usize]);
+        if fp & MASK_S == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+    while i < max_size {
+        fp = (fp << 1).wrapping_add(GEAR[data[i] as usize]);
+        if fp & MASK_L == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+    max_size
+}
+
+/// Split `content` into content-defined chunks, each paired with its
+/// hex SHA-256 hash, in file order.
+///
+/// An empty input produces an empty chunk list (it reassembles to an
+/// empty file without needing a zero-length chunk).
+pub fn chunk_content(content: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut chunks = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let cut = next_cut(rest);
+        let (chunk, remainder) = rest.split_at(cut);
+        chunks.push((hex_sha256(chunk), chunk.to_vec()));
+        rest = remainder;
+    }
+    chunks
+}
+
+/// Hex-encoded SHA-256 of a byte slice.
+fn hex_sha256(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_content_has_no_chunks() {
+        assert!(chunk_content(b"").is_empty());
+    }
+
+    #[test]
+    fn test_small_content_is_one_chunk() {
+        let chunks = chunk_content(b"hello world");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].1, b"hello world");
+    }
+
+    #[test]
+    fn test_chunks_reassemble_to_original() {
+        let content: Vec<u8> = (0..500_000u32).map(|i| (i % 251) as u8).collect();
+        let chunks = chunk_content(&content);
+        assert!(
+            chunks.len() > 1,
+            "large input should split into several chunks"
+        );
+        let reassembled: Vec<u8> = chunks.iter().flat_map(|(_, c)| c.clone()).collect();
+        assert_eq!(reassembled, content);
+    }
+
+    #[test]
+    fn test_chunking_is_deterministic() {
+        let content: Vec<u8> = (0..200_000u32).map(|i| (i * 7 % 256) as u8).collect();
+        let a = chunk_content(&content);
+        let b = chunk_content(&content);
+        let a_hashes: Vec<&String> = a.iter().map(|(h, _)| h).collect();
+        let b_hashes: Vec<&String> = b.iter().map(|(h, _)| h).collect();
+        assert_eq!(a_hashes, b_hashes);
+    }
+
+    #[test]
+    fn test_no_chunk_exceeds_max_size() {
+        let content: Vec<u8> = (0..1_000_000u32).map(|i| (i % 13) as u8).collect();
+        let chunks = chunk_content(&content);
+        assert!(chunks.iter().all(|(_, c)| c.len() <= MAX_SIZE));
+    }
+
+    #[test]
+    fn test_edit_far_from_start_leaves_early_chunks_unchanged() {
+        let mut content: Vec<u8> = (0..300_000u32).map(|i| (i * 31 % 256) as u8).collect();
+        let original = chunk_content(&content);
+
+        // Flip a byte well past the first chunk or two.
+        let flip_at = 250_000;
+        content[flip_at] ^= 0xFF;
+        let edited = chunk_content(&content);
+
+        let shared_prefix = original
+            .iter()
+            .zip(edited.iter())
+            .take_while(|(a, b)| a.0 == b.0)
+            .count();
+        assert!(
+            shared_prefix >= 1,
+            "content-defined chunking should keep at least the first chunk \
+             identical when editing bytes far past it"
+        );
+    }
+
+    #[test]
+    fn test_appending_data_does_not_perturb_earlier_chunks() {
+        // A cut decision only looks at bytes within the chunk being
+        // scanned, so appending new data at the end must not change any
+        // chunk before the last one — the property that makes dedup
+        // across pack revisions work.
+        let base: Vec<u8> = (0..500_000u32).map(|i| (i * 17 % 256) as u8).collect();
+        let mut extended = base.clone();
+        extended.extend_from_slice(b"trailing bytes appended later");
+
+        let base_chunks = chunk_content(&base);
+        let extended_chunks = chunk_content(&extended);
+        assert!(base_chunks.len() > 1);
+
+        let stable = base_chunks.len() - 1;
+        assert_eq!(base_chunks[..stable], extended_chunks[..stable]);
+    }
+}
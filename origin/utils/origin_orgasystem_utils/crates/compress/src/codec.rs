@@ -0,0 +1,221 @@
+//! Pluggable compression codecs for the CPACK payload.
+//!
+//! A codec is identified by the on-wire byte stored in
+//! `CpackHeader.compression_method` (see [`crate::frame`]); decode only ever
+//! dispatches on that byte, so old packs keep decompressing regardless of
+//! what codec the current default is. `store` is a zero-copy identity path
+//! for constrained readers that would rather skip decompression entirely.
+//! `xz` reserves a method byte for a future LZMA codec and is not yet wired
+//! up on either side.
+
+use crate::frame::{FrameError, COMPRESS_STORE, COMPRESS_XZ, COMPRESS_ZSTD};
+
+/// Lowest zstd compression level accepted by `--level`.
+pub const MIN_ZSTD_LEVEL: i32 = 1;
+/// Highest zstd compression level accepted by `--level`.
+pub const MAX_ZSTD_LEVEL: i32 = 19;
+/// Default zstd level used when the caller doesn't pick one.
+pub const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+/// Max size in bytes for a trained zstd dictionary - the same budget
+/// zstd's own `zstd --train` CLI defaults to.
+pub const DEFAULT_DICT_SIZE: usize = 112_640;
+
+/// Train a zstd dictionary from `samples` (typically one entry per file, in
+/// the same sorted order the caller will later compress them in), capped at
+/// [`DEFAULT_DICT_SIZE`] bytes. Training is deterministic for a given
+/// sample set, so dictionary-mode cpacks stay byte-for-byte reproducible.
+pub fn train_dictionary(samples: &[Vec<u8>]) -> Result<Vec<u8>, FrameError> {
+    Ok(zstd::dict::from_samples(samples, DEFAULT_DICT_SIZE)?)
+}
+
+/// Compress `data` against a trained `dictionary` at `level` (clamped as in
+/// [`compress_payload`]).
+pub fn compress_with_dictionary(
+    data: &[u8],
+    dictionary: &[u8],
+    level: i32,
+) -> Result<Vec<u8>, FrameError> {
+    let level = level.clamp(MIN_ZSTD_LEVEL, MAX_ZSTD_LEVEL);
+    let mut compressor = zstd::bulk::Compressor::with_dictionary(level, dictionary)?;
+    Ok(compressor.compress(data)?)
+}
+
+/// Decompress `data` that was sealed with [`compress_with_dictionary`]
+/// against the same `dictionary`. `capacity` is the known original content
+/// length (stored alongside the compressed bytes in the dictionary-mode
+/// payload), so decompression doesn't have to guess a buffer size.
+pub fn decompress_with_dictionary(
+    data: &[u8],
+    dictionary: &[u8],
+    capacity: usize,
+) -> Result<Vec<u8>, FrameError> {
+    let mut decompressor = zstd::bulk::Decompressor::with_dictionary(dictionary)?;
+    Ok(decompressor.decompress(data, capacity)?)
+}
+
+/// Compress `payload` with the codec named by the `CpackHeader.compression_method`
+/// byte `codec`. `level` is only consulted for codecs that have one (zstd);
+/// it is clamped to `[MIN_ZSTD_LEVEL, MAX_ZSTD_LEVEL]` rather than rejected,
+/// so a stray out-of-range level doesn't fail a pack outright.
+pub fn compress_payload(payload: &[u8], codec: u8, level: i32) -> Result<Vec<u8>, FrameError> {
+    match codec {
+        COMPRESS_STORE => Ok(payload.to_vec()),
+        COMPRESS_ZSTD => {
+            let level = level.clamp(MIN_ZSTD_LEVEL, MAX_ZSTD_LEVEL);
+            Ok(zstd::encode_all(payload, level)?)
+        }
+        COMPRESS_XZ => Err(FrameError::CodecNotImplemented(codec)),
+        other => Err(FrameError::UnsupportedCompression(other)),
+    }
+}
+
+/// Decompress `data` that was sealed with `compress_payload` under `codec`.
+pub fn decompress_payload(data: &[u8], codec: u8) -> Result<Vec<u8>, FrameError> {
+    match codec {
+        COMPRESS_STORE => Ok(data.to_vec()),
+        COMPRESS_ZSTD => Ok(zstd::decode_all(data)?),
+        COMPRESS_XZ => Err(FrameError::CodecNotImplemented(codec)),
+        other => Err(FrameError::UnsupportedCompression(other)),
+    }
+}
+
+/// Deterministically pick the smallest-output codec for `payload` among the
+/// implemented candidates (`COMPRESS_STORE`, `COMPRESS_ZSTD` at
+/// [`DEFAULT_ZSTD_LEVEL`]) - `COMPRESS_XZ` is reserved but not wired up (see
+/// module docs), so it is never a candidate. Candidates are tried in a fixed
+/// order and ties favor `COMPRESS_STORE`, so the choice only ever depends on
+/// `payload` itself and is reproducible across runs and platforms.
+pub fn choose_method(payload: &[u8]) -> u8 {
+    let mut best = COMPRESS_STORE;
+    let mut best_len = payload.len();
+
+    for &codec in &[COMPRESS_ZSTD] {
+        if let Ok(compressed) = compress_payload(payload, codec, DEFAULT_ZSTD_LEVEL) {
+            if compressed.len() < best_len {
+                best = codec;
+                best_len = compressed.len();
+            }
+        }
+    }
+
+    best
+}
+
+/// Parse a `--codec` CLI argument (`store`, `zstd`, or `xz`) into the
+/// on-wire `CpackHeader.compression_method` byte.
+pub fn parse_codec_name(name: &str) -> Result<u8, FrameError> {
+    match name {
+        "store" => Ok(COMPRESS_STORE),
+        "zstd" => Ok(COMPRESS_ZSTD),
+        "xz" => Ok(COMPRESS_XZ),
+        other => Err(FrameError::UnknownCodecName(other.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_roundtrip_is_identity() {
+        let payload = b"hello, origin".to_vec();
+        let compressed = compress_payload(&payload, COMPRESS_STORE, 0).unwrap();
+        assert_eq!(compressed, payload);
+        let restored = decompress_payload(&compressed, COMPRESS_STORE).unwrap();
+        assert_eq!(restored, payload);
+    }
+
+    #[test]
+    fn test_zstd_roundtrip() {
+        let payload = b"hello, origin".repeat(100);
+        let compressed = compress_payload(&payload, COMPRESS_ZSTD, DEFAULT_ZSTD_LEVEL).unwrap();
+        let restored = decompress_payload(&compressed, COMPRESS_ZSTD).unwrap();
+        assert_eq!(restored, payload);
+    }
+
+    #[test]
+    fn test_zstd_level_is_clamped_not_rejected() {
+        let payload = b"clamped level".to_vec();
+        assert!(compress_payload(&payload, COMPRESS_ZSTD, 0).is_ok());
+        assert!(compress_payload(&payload, COMPRESS_ZSTD, 99).is_ok());
+    }
+
+    #[test]
+    fn test_xz_not_yet_implemented() {
+        let payload = b"reserved".to_vec();
+        assert!(matches!(
+            compress_payload(&payload, COMPRESS_XZ, 0),
+            Err(FrameError::CodecNotImplemented(_))
+        ));
+    }
+
+    #[test]
+    fn test_dictionary_compress_roundtrip() {
+        let samples = vec![
+            b"fn main() { println!(\"one\"); }".to_vec(),
+            b"fn main() { println!(\"two\"); }".to_vec(),
+            b"fn main() { println!(\"three\"); }".to_vec(),
+        ];
+        let dictionary = train_dictionary(&samples).unwrap();
+        assert!(!dictionary.is_empty());
+
+        for sample in &samples {
+            let compressed = compress_with_dictionary(sample, &dictionary, DEFAULT_ZSTD_LEVEL).unwrap();
+            let restored = decompress_with_dictionary(&compressed, &dictionary, sample.len()).unwrap();
+            assert_eq!(&restored, sample);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_beats_no_dictionary_for_many_similar_small_files() {
+        let samples: Vec<Vec<u8>> = (0..50)
+            .map(|i| format!("fn handler_{i}() {{ log::info!(\"handling request\"); }}").into_bytes())
+            .collect();
+        let dictionary = train_dictionary(&samples).unwrap();
+
+        let with_dict_total: usize = samples
+            .iter()
+            .map(|s| compress_with_dictionary(s, &dictionary, DEFAULT_ZSTD_LEVEL).unwrap().len())
+            .sum();
+        let without_dict_total: usize = samples
+            .iter()
+            .map(|s| compress_payload(s, COMPRESS_ZSTD, DEFAULT_ZSTD_LEVEL).unwrap().len())
+            .sum();
+
+        assert!(
+            with_dict_total < without_dict_total,
+            "dictionary mode ({with_dict_total} bytes) should beat per-file zstd with no shared dictionary ({without_dict_total} bytes)"
+        );
+    }
+
+    #[test]
+    fn test_choose_method_picks_zstd_for_compressible_payload() {
+        let payload = b"origin origin origin origin origin origin origin".repeat(20);
+        assert_eq!(choose_method(&payload), COMPRESS_ZSTD);
+    }
+
+    #[test]
+    fn test_choose_method_picks_store_for_tiny_incompressible_payload() {
+        // Too short for zstd's framing overhead to pay for itself.
+        let payload = b"\x01\x02\x03".to_vec();
+        assert_eq!(choose_method(&payload), COMPRESS_STORE);
+    }
+
+    #[test]
+    fn test_choose_method_is_deterministic() {
+        let payload: Vec<u8> = (0..4096u32).map(|i| (i % 251) as u8).collect();
+        assert_eq!(choose_method(&payload), choose_method(&payload));
+    }
+
+    #[test]
+    fn test_parse_codec_name() {
+        assert_eq!(parse_codec_name("store").unwrap(), COMPRESS_STORE);
+        assert_eq!(parse_codec_name("zstd").unwrap(), COMPRESS_ZSTD);
+        assert_eq!(parse_codec_name("xz").unwrap(), COMPRESS_XZ);
+        assert!(matches!(
+            parse_codec_name("bogus"),
+            Err(FrameError::UnknownCodecName(_))
+        ));
+    }
+}
@@ -1,31 +1,549 @@
 //! Compress a DPACK directory into a single .cpack file.
 
+use crate::chunk::chunk_content;
+use crate::codec::{
+    choose_method, compress_payload, compress_with_dictionary, train_dictionary, DEFAULT_ZSTD_LEVEL,
+};
+use crate::crypto::{encrypt_payload, ENCRYPT_NONE, ENCRYPT_XCHACHA20POLY1305};
+use crate::cstore;
 use crate::frame::{
-    encode_payload, sha256_bytes, CpackHeader, FrameError, COMPRESS_ZSTD, CPACK_VERSION,
+    encode_chunk_store_payload, encode_chunked_payload, encode_dictionary_payload, encode_payload,
+    sha256_bytes, CpackHeader, FrameError, COMPRESS_ZSTD, CPACK_VERSION, DICT_MODE_NONE,
+    DICT_MODE_TRAINED, PAYLOAD_CHUNK_STORE, PAYLOAD_CHUNKED, PAYLOAD_FLAT, PAYLOAD_SEEKABLE,
 };
+use crate::merkle::merkle_root;
+use crate::seekable::encode_seekable_payload;
 use dpack_core::manifest::DpackManifest;
 use std::collections::BTreeMap;
 use std::path::Path;
 use walkdir::WalkDir;
 
-/// Compress a DPACK directory into a .cpack file.
-///
-/// The dpack_dir must contain manifest.json and a data/ subdirectory.
-/// Output is written to `output_path`.
+/// Compress a DPACK directory into a .cpack file using the default codec
+/// (zstd at [`DEFAULT_ZSTD_LEVEL`]).
+///
+/// The dpack_dir must contain manifest.json and a data/ subdirectory.
+/// Output is written to `output_path`.
+///
+/// Returns the SHA-256 hex string of the uncompressed payload.
+pub fn compress_dpack(dpack_dir: &Path, output_path: &Path) -> Result<String, FrameError> {
+    compress_dpack_with_codec(dpack_dir, output_path, COMPRESS_ZSTD, DEFAULT_ZSTD_LEVEL)
+}
+
+/// Compress a DPACK directory into a .cpack file with an explicit codec and
+/// level, letting callers trade compression ratio against speed (or skip
+/// compression with `COMPRESS_STORE` for constrained readers). `level` is
+/// only meaningful for codecs that have one (zstd) and is ignored otherwise.
+/// `codec` must be one of `COMPRESS_STORE`, `COMPRESS_ZSTD` or `COMPRESS_XZ`
+/// (see [`crate::frame`], [`crate::codec`]).
+///
+/// Returns the SHA-256 hex string of the uncompressed payload.
+pub fn compress_dpack_with_codec(
+    dpack_dir: &Path,
+    output_path: &Path,
+    codec: u8,
+    level: i32,
+) -> Result<String, FrameError> {
+    // Read manifest
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+
+    // Validate manifest parses
+    let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+
+    // Re-serialize manifest canonically (sorted keys via BTreeMap in struct)
+    let canonical_manifest = serde_json::to_vec(&manifest)?;
+
+    // Collect files from data/ directory, sorted by relative path
+    let data_dir = dpack_dir.join("data");
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    if data_dir.exists() {
+        for entry in WalkDir::new(&data_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.map_err(|e| {
+                let msg = e.to_string();
+                FrameError::Io(
+                    e.into_io_error()
+                        .unwrap_or_else(|| std::io::Error::other(msg)),
+                )
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&data_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = std::fs::read(entry.path())?;
+            files.insert(rel, content);
+        }
+    }
+
+    // Build sorted file list
+    let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+
+    // Encode payload
+    let payload = encode_payload(&canonical_manifest, &sorted_files);
+
+    // Hash payload
+    let payload_hash = sha256_bytes(&payload);
+
+    // Compress with the requested codec (fixed parameters - no timestamps,
+    // no adaptive window/dictionary settings - so output stays deterministic
+    // across runs for a given codec+level).
+    let compressed = compress_payload(&payload, codec, level)?;
+
+    // Build header
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: codec,
+        payload_format: PAYLOAD_FLAT,
+        encryption_method: ENCRYPT_NONE,
+        payload_sha256: payload_hash,
+        compressed_size: compressed.len() as u64,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
+    };
+
+    // Write output file
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&compressed);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Compress a DPACK directory into a .cpack file, picking the codec
+/// automatically via [`crate::codec::choose_method`] instead of requiring
+/// the caller to name one up front. The choice only depends on the encoded
+/// payload bytes, so a given dpack_dir always ends up compressed the same
+/// way.
+///
+/// Returns the SHA-256 hex string of the uncompressed payload.
+pub fn compress_dpack_auto(dpack_dir: &Path, output_path: &Path) -> Result<String, FrameError> {
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+    let canonical_manifest = serde_json::to_vec(&manifest)?;
+
+    let data_dir = dpack_dir.join("data");
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    if data_dir.exists() {
+        for entry in WalkDir::new(&data_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.map_err(|e| {
+                let msg = e.to_string();
+                FrameError::Io(
+                    e.into_io_error()
+                        .unwrap_or_else(|| std::io::Error::other(msg)),
+                )
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&data_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = std::fs::read(entry.path())?;
+            files.insert(rel, content);
+        }
+    }
+
+    let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+    let payload = encode_payload(&canonical_manifest, &sorted_files);
+    let payload_hash = sha256_bytes(&payload);
+
+    let codec = choose_method(&payload);
+    let compressed = compress_payload(&payload, codec, DEFAULT_ZSTD_LEVEL)?;
+
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: codec,
+        payload_format: PAYLOAD_FLAT,
+        encryption_method: ENCRYPT_NONE,
+        payload_sha256: payload_hash,
+        compressed_size: compressed.len() as u64,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
+    };
+
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&compressed);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Compress a DPACK directory into a .cpack file, then seal the compressed
+/// payload at rest with XChaCha20-Poly1305 under a key derived from
+/// `passphrase` via Argon2id (see [`crate::crypto`]).
+///
+/// The serialized fixed header is used as AEAD associated data, so the
+/// header cannot be tampered with independently of the ciphertext.
+pub fn compress_dpack_encrypted(
+    dpack_dir: &Path,
+    output_path: &Path,
+    passphrase: &[u8],
+) -> Result<String, FrameError> {
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+    let canonical_manifest = serde_json::to_vec(&manifest)?;
+
+    let data_dir = dpack_dir.join("data");
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    if data_dir.exists() {
+        for entry in WalkDir::new(&data_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.map_err(|e| {
+                let msg = e.to_string();
+                FrameError::Io(
+                    e.into_io_error()
+                        .unwrap_or_else(|| std::io::Error::other(msg)),
+                )
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&data_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = std::fs::read(entry.path())?;
+            files.insert(rel, content);
+        }
+    }
+
+    let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+    let payload = encode_payload(&canonical_manifest, &sorted_files);
+    let payload_hash = sha256_bytes(&payload);
+    let compressed = zstd::encode_all(payload.as_slice(), 3)?;
+
+    // Poly1305 appends a fixed 16-byte tag, so the ciphertext length is
+    // known before encrypting and the header can be finalized (and used as
+    // AEAD associated data) in one shot.
+    const POLY1305_TAG_SIZE: u64 = 16;
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: COMPRESS_ZSTD,
+        payload_format: PAYLOAD_FLAT,
+        encryption_method: ENCRYPT_XCHACHA20POLY1305,
+        payload_sha256: payload_hash,
+        compressed_size: compressed.len() as u64 + POLY1305_TAG_SIZE,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
+    };
+    let aad = header.to_bytes();
+    let (crypto_header, ciphertext) = encrypt_payload(&compressed, passphrase, &aad)?;
+
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&crypto_header.to_bytes());
+    out.extend_from_slice(&ciphertext);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Compress a DPACK directory into a .cpack file using content-defined
+/// chunking instead of inlining whole files.
+///
+/// Each file's bytes are split with FastCDC (see [`crate::chunk`]) and
+/// deduplicated by chunk SHA-256 across the whole pack, so a one-byte
+/// change to a large file only adds the one or two chunks around the
+/// edit rather than re-storing the entire file, and identical content
+/// repeated across files is stored once. The embedded manifest copy
+/// records each file's ordered chunk hashes in `FileEntry.chunks`;
+/// `manifest.json` on disk and its `sha256`/`size` fields are untouched.
+pub fn compress_dpack_chunked(dpack_dir: &Path, output_path: &Path) -> Result<String, FrameError> {
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+    let mut manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+
+    let data_dir = dpack_dir.join("data");
+    let mut chunk_store: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    let mut sorted_files: Vec<(String, Vec<u8>)> = Vec::new();
+    for (rel_path, entry) in manifest.files.iter_mut() {
+        let content = std::fs::read(data_dir.join(rel_path))?;
+        let mut hashes = Vec::new();
+        for (hash, bytes) in chunk_content(&content) {
+            chunk_store.entry(hash.clone()).or_insert(bytes);
+            hashes.push(hash);
+        }
+        entry.chunks = hashes;
+        sorted_files.push((rel_path.clone(), content));
+    }
+
+    let canonical_manifest = serde_json::to_vec(&manifest)?;
+    let chunks: Vec<(String, Vec<u8>)> = chunk_store.into_iter().collect();
+
+    let payload = encode_chunked_payload(&canonical_manifest, &chunks);
+    let payload_hash = sha256_bytes(&payload);
+    let compressed = zstd::encode_all(payload.as_slice(), 3)?;
+
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: COMPRESS_ZSTD,
+        payload_format: PAYLOAD_CHUNKED,
+        encryption_method: ENCRYPT_NONE,
+        payload_sha256: payload_hash,
+        compressed_size: compressed.len() as u64,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
+    };
+
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&compressed);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Chunk-level dedup stats for a DPACK directory: how many bytes its files
+/// add up to uncompressed, against how many bytes [`compress_dpack_chunked`]
+/// actually ends up storing once chunks shared across files are deduplicated
+/// by content hash.
+pub struct DedupStats {
+    pub total_bytes: u64,
+    pub unique_bytes: u64,
+}
+
+impl DedupStats {
+    /// Fraction of `total_bytes` actually stored, in `[0.0, 1.0]` (`0.0` when
+    /// there were no bytes to begin with). `1.0 - ratio()` is the fraction
+    /// dedup saved.
+    pub fn ratio(&self) -> f64 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.unique_bytes as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+/// Compute [`DedupStats`] for `dpack_dir` without writing a .cpack - the same
+/// chunking [`compress_dpack_chunked`] performs, just tallied instead of
+/// stored.
+pub fn chunk_dedup_stats(dpack_dir: &Path) -> Result<DedupStats, FrameError> {
+    let data_dir = dpack_dir.join("data");
+    let mut chunk_store: BTreeMap<String, usize> = BTreeMap::new();
+    let mut total_bytes: u64 = 0;
+
+    if data_dir.exists() {
+        for entry in WalkDir::new(&data_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.map_err(|e| {
+                let msg = e.to_string();
+                FrameError::Io(
+                    e.into_io_error()
+                        .unwrap_or_else(|| std::io::Error::other(msg)),
+                )
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let content = std::fs::read(entry.path())?;
+            total_bytes += content.len() as u64;
+            for (hash, bytes) in chunk_content(&content) {
+                chunk_store.entry(hash).or_insert(bytes.len());
+            }
+        }
+    }
+
+    let unique_bytes = chunk_store.values().map(|&len| len as u64).sum();
+    Ok(DedupStats {
+        total_bytes,
+        unique_bytes,
+    })
+}
+
+/// Compress a DPACK directory into a .cpack file backed by a cross-pack
+/// chunk store instead of this cpack's own bytes.
+///
+/// Like [`compress_dpack_chunked`], each file is split with FastCDC (see
+/// [`crate::chunk`]) and `FileEntry.chunks` records the ordered chunk
+/// hashes needed to reassemble it. Unlike `compress_dpack_chunked`, no
+/// chunk content is embedded in the output file at all - every chunk is
+/// written to `cstore_dir` (see [`crate::cstore`]) only if it isn't
+/// already there, so packing a new revision of a similar tree only ever
+/// grows the store by the chunks that actually changed, and the `.cpack`
+/// itself shrinks to little more than the manifest.
+///
+/// The payload hash is computed over the same uncompressed, flat-encoded
+/// payload [`compress_dpack`] would produce, so the hash-equality invariant
+/// other modes rely on holds here too.
+pub fn compress_dpack_chunk_store(
+    dpack_dir: &Path,
+    cstore_dir: &Path,
+    output_path: &Path,
+) -> Result<String, FrameError> {
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+    let mut manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+
+    let data_dir = dpack_dir.join("data");
+    let mut sorted_files: Vec<(String, Vec<u8>)> = Vec::new();
+    for (rel_path, entry) in manifest.files.iter_mut() {
+        let content = std::fs::read(data_dir.join(rel_path))?;
+        let (hashes, _written) = cstore::store_chunks(cstore_dir, &content)?;
+        entry.chunks = hashes;
+        sorted_files.push((rel_path.clone(), content));
+    }
+
+    let canonical_manifest = serde_json::to_vec(&manifest)?;
+
+    // Hash over the same flat encoding the non-chunk-store modes use, so the
+    // payload hash is independent of which on-disk representation we chose.
+    let payload = encode_payload(&canonical_manifest, &sorted_files);
+    let payload_hash = sha256_bytes(&payload);
+
+    let body = encode_chunk_store_payload(&canonical_manifest);
+    let compressed = zstd::encode_all(body.as_slice(), 3)?;
+
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: COMPRESS_ZSTD,
+        payload_format: PAYLOAD_CHUNK_STORE,
+        encryption_method: ENCRYPT_NONE,
+        payload_sha256: payload_hash,
+        compressed_size: compressed.len() as u64,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
+    };
+
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&compressed);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Compress a DPACK directory into a .cpack file using a zstd dictionary
+/// trained on its own sorted file contents, then compress each file
+/// individually against that dictionary and embed the dictionary in the
+/// frame (see [`crate::codec::train_dictionary`], [`DICT_MODE_TRAINED`]).
+///
+/// A single whole-payload zstd pass (the default [`compress_dpack`]) only
+/// gets to exploit redundancy within one shared window; for a DPACK made of
+/// many small, similar source files, seeding each file's compression with a
+/// dictionary trained on the whole set typically shrinks the result further.
+///
+/// The payload hash is computed over the same uncompressed, flat-encoded
+/// payload [`compress_dpack`] would produce, so the hash-equality invariant
+/// `roundtrip_compress_decompress` relies on is unaffected by which mode
+/// produced the bytes on disk.
+pub fn compress_dpack_with_dictionary(
+    dpack_dir: &Path,
+    output_path: &Path,
+    level: i32,
+) -> Result<String, FrameError> {
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+    let canonical_manifest = serde_json::to_vec(&manifest)?;
+
+    let data_dir = dpack_dir.join("data");
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    if data_dir.exists() {
+        for entry in WalkDir::new(&data_dir)
+            .follow_links(false)
+            .sort_by_file_name()
+        {
+            let entry = entry.map_err(|e| {
+                let msg = e.to_string();
+                FrameError::Io(
+                    e.into_io_error()
+                        .unwrap_or_else(|| std::io::Error::other(msg)),
+                )
+            })?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&data_dir)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = std::fs::read(entry.path())?;
+            files.insert(rel, content);
+        }
+    }
+
+    let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+
+    // Hash over the same flat encoding the non-dictionary modes use, so the
+    // payload hash is independent of which on-disk representation we chose.
+    let payload = encode_payload(&canonical_manifest, &sorted_files);
+    let payload_hash = sha256_bytes(&payload);
+
+    let samples: Vec<Vec<u8>> = sorted_files.iter().map(|(_, content)| content.clone()).collect();
+    let dictionary = train_dictionary(&samples)?;
+
+    let compressed_files: Vec<(String, u64, Vec<u8>)> = sorted_files
+        .iter()
+        .map(|(path, content)| {
+            let compressed = compress_with_dictionary(content, &dictionary, level)?;
+            Ok::<_, FrameError>((path.clone(), content.len() as u64, compressed))
+        })
+        .collect::<Result<_, FrameError>>()?;
+
+    let body = encode_dictionary_payload(&canonical_manifest, &dictionary, &compressed_files);
+
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: COMPRESS_ZSTD,
+        payload_format: PAYLOAD_FLAT,
+        encryption_method: ENCRYPT_NONE,
+        payload_sha256: payload_hash,
+        compressed_size: body.len() as u64,
+        dictionary_mode: DICT_MODE_TRAINED,
+        merkle_root: merkle_root(&sorted_files),
+    };
+
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&body);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Compress a DPACK directory into a .cpack file whose payload is seekable:
+/// each file is compressed as its own independent zstd frame with a trailer
+/// index (see [`crate::seekable`]), so [`crate::seekable::extract_file`] can
+/// pull one file out of a large pack without decompressing the rest.
 ///
-/// Returns the SHA-256 hex string of the uncompressed payload.
-pub fn compress_dpack(dpack_dir: &Path, output_path: &Path) -> Result<String, FrameError> {
-    // Read manifest
+/// The payload hash is computed over the same uncompressed, flat-encoded
+/// payload [`compress_dpack`] would produce, so the hash-equality invariant
+/// other modes rely on holds here too.
+pub fn compress_dpack_seekable(
+    dpack_dir: &Path,
+    output_path: &Path,
+    level: i32,
+) -> Result<String, FrameError> {
     let manifest_path = dpack_dir.join("manifest.json");
     let manifest_bytes = std::fs::read(&manifest_path)?;
-
-    // Validate manifest parses
     let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
-
-    // Re-serialize manifest canonically (sorted keys via BTreeMap in struct)
     let canonical_manifest = serde_json::to_vec(&manifest)?;
 
-    // Collect files from data/ directory, sorted by relative path
     let data_dir = dpack_dir.join("data");
     let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
 
@@ -55,27 +573,78 @@ pub fn compress_dpack(dpack_dir: &Path, output_path: &Path) -> Result<String, Fr
         }
     }
 
-    // Build sorted file list
     let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
 
-    // Encode payload
     let payload = encode_payload(&canonical_manifest, &sorted_files);
-
-    // Hash payload
     let payload_hash = sha256_bytes(&payload);
 
-    // Compress with zstd (level 3 for good ratio/speed balance)
-    let compressed = zstd::encode_all(payload.as_slice(), 3)?;
+    let body = encode_seekable_payload(&canonical_manifest, &sorted_files, level)?;
 
-    // Build header
     let header = CpackHeader {
         version: CPACK_VERSION,
         compression_method: COMPRESS_ZSTD,
+        payload_format: PAYLOAD_SEEKABLE,
+        encryption_method: ENCRYPT_NONE,
+        payload_sha256: payload_hash,
+        compressed_size: body.len() as u64,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
+    };
+
+    let mut out = header.to_bytes();
+    out.extend_from_slice(&body);
+    std::fs::write(output_path, &out)?;
+
+    Ok(hex::encode(payload_hash))
+}
+
+/// Compress a full DPACK directory into a delta .cpack against `base_manifest`:
+/// the embedded manifest is a [`dpack_core::delta::DeltaManifest`] rather than
+/// a [`DpackManifest`], and only files whose file entry (hash, size or
+/// recorded metadata) differs from the base are read from `data/` and
+/// included in the payload (see
+/// [`dpack_core::pack::pack_repo_delta`] for the repo-root-to-delta-dir
+/// counterpart of this function). `dpack_dir` is unchanged; only the output
+/// `.cpack` is smaller when most of the tree matches `base_manifest`.
+///
+/// Returns the SHA-256 hex string of the uncompressed delta payload.
+pub fn compress_dpack_with_base(
+    dpack_dir: &Path,
+    output_path: &Path,
+    codec: u8,
+    level: i32,
+    base_manifest: &DpackManifest,
+) -> Result<String, FrameError> {
+    let manifest_path = dpack_dir.join("manifest.json");
+    let manifest_bytes = std::fs::read(&manifest_path)?;
+    let full_manifest: DpackManifest = serde_json::from_slice(&manifest_bytes)?;
+
+    let delta = dpack_core::delta::diff_manifests(base_manifest, &full_manifest);
+    let canonical_delta = serde_json::to_vec(&delta)?;
+
+    let data_dir = dpack_dir.join("data");
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for rel_path in delta.added_or_changed.keys() {
+        let content = std::fs::read(data_dir.join(rel_path))?;
+        files.insert(rel_path.clone(), content);
+    }
+    let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+
+    let payload = encode_payload(&canonical_delta, &sorted_files);
+    let payload_hash = sha256_bytes(&payload);
+    let compressed = compress_payload(&payload, codec, level)?;
+
+    let header = CpackHeader {
+        version: CPACK_VERSION,
+        compression_method: codec,
+        payload_format: PAYLOAD_FLAT,
+        encryption_method: ENCRYPT_NONE,
         payload_sha256: payload_hash,
         compressed_size: compressed.len() as u64,
+        dictionary_mode: DICT_MODE_NONE,
+        merkle_root: merkle_root(&sorted_files),
     };
 
-    // Write output file
     let mut out = header.to_bytes();
     out.extend_from_slice(&compressed);
     std::fs::write(output_path, &out)?;
@@ -97,19 +666,14 @@ mod tests {
         let mut files = std::collections::BTreeMap::new();
         files.insert(
             "README.md".to_string(),
-            dpack_core::manifest::FileEntry {
-                sha256: seed_core::compute_sha256(b"# Test"),
-                size: 6,
-            },
+            dpack_core::manifest::FileEntry::new(seed_core::compute_sha256(b"# Test"), 6),
         );
         files.insert(
             "src/main.rs".to_string(),
-            dpack_core::manifest::FileEntry {
-                sha256: seed_core::compute_sha256(b"fn main() {}"),
-                size: 12,
-            },
+            dpack_core::manifest::FileEntry::new(seed_core::compute_sha256(b"fn main() {}"), 12),
         );
-        let pack_hash = dpack_core::manifest::DpackManifest::compute_pack_hash(&files);
+        let hash_scheme = seed_core::hash::HashScheme::default();
+        let pack_hash = dpack_core::manifest::DpackManifest::compute_pack_hash(&files, &hash_scheme);
         let manifest = dpack_core::manifest::DpackManifest {
             schema_version: "1.0".to_string(),
             root_2i_seed_fingerprint: "test_fp".to_string(),
@@ -117,6 +681,8 @@ mod tests {
             source_root: "/tmp/test".to_string(),
             files,
             pack_hash,
+            vcs: None,
+            hash_scheme,
         };
         let json = serde_json::to_string_pretty(&manifest).unwrap();
         std::fs::write(dir.join("manifest.json"), json).unwrap();
@@ -155,4 +721,453 @@ mod tests {
         let d2 = std::fs::read(&p2).unwrap();
         assert_eq!(d1, d2, "compress must be deterministic");
     }
+
+    #[test]
+    fn test_compress_with_codec_deterministic_per_codec_and_level() {
+        use crate::frame::{COMPRESS_STORE, COMPRESS_ZSTD};
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        for (codec, level) in [(COMPRESS_STORE, 0), (COMPRESS_ZSTD, 1), (COMPRESS_ZSTD, 19)] {
+            let out1 = TempDir::new().unwrap();
+            let out2 = TempDir::new().unwrap();
+            let p1 = out1.path().join("a.cpack");
+            let p2 = out2.path().join("b.cpack");
+
+            compress_dpack_with_codec(dpack.path(), &p1, codec, level).unwrap();
+            compress_dpack_with_codec(dpack.path(), &p2, codec, level).unwrap();
+
+            let d1 = std::fs::read(&p1).unwrap();
+            let d2 = std::fs::read(&p2).unwrap();
+            assert_eq!(
+                d1, d2,
+                "compress must be deterministic for codec={codec} level={level}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compress_dpack_auto_picks_smallest_codec_and_roundtrips() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+        let hash = compress_dpack_auto(dpack.path(), &cpack_path).unwrap();
+
+        let data = std::fs::read(&cpack_path).unwrap();
+        let header = CpackHeader::from_bytes(&data).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let restored_hash =
+            crate::decompress::decompress_cpack(&cpack_path, restored.path()).unwrap();
+        assert_eq!(hash, restored_hash);
+        assert_eq!(
+            std::fs::read(restored.path().join("data/README.md")).unwrap(),
+            std::fs::read(dpack.path().join("data/README.md")).unwrap(),
+        );
+
+        // compress_dpack_auto must have actually used choose_method's pick,
+        // not some other fixed default.
+        let manifest_bytes = std::fs::read(dpack.path().join("manifest.json")).unwrap();
+        let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes).unwrap();
+        let canonical_manifest = serde_json::to_vec(&manifest).unwrap();
+        let data_dir = dpack.path().join("data");
+        let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+        for entry in WalkDir::new(&data_dir).follow_links(false).sort_by_file_name() {
+            let entry = entry.unwrap();
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(&data_dir)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/");
+            files.insert(rel, std::fs::read(entry.path()).unwrap());
+        }
+        let sorted_files: Vec<(String, Vec<u8>)> = files.into_iter().collect();
+        let payload = encode_payload(&canonical_manifest, &sorted_files);
+        assert_eq!(header.compression_method, crate::codec::choose_method(&payload));
+    }
+
+    #[test]
+    fn test_compress_dpack_auto_is_deterministic() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let out1 = TempDir::new().unwrap();
+        let out2 = TempDir::new().unwrap();
+        let p1 = out1.path().join("a.cpack");
+        let p2 = out2.path().join("b.cpack");
+
+        compress_dpack_auto(dpack.path(), &p1).unwrap();
+        compress_dpack_auto(dpack.path(), &p2).unwrap();
+
+        assert_eq!(std::fs::read(&p1).unwrap(), std::fs::read(&p2).unwrap());
+    }
+
+    #[test]
+    fn test_compress_with_codec_store_is_uncompressed_passthrough() {
+        use crate::frame::{COMPRESS_STORE, HEADER_SIZE};
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        compress_dpack_with_codec(dpack.path(), &cpack_path, COMPRESS_STORE, 0).unwrap();
+        let data = std::fs::read(&cpack_path).unwrap();
+        assert_eq!(data[5], COMPRESS_STORE);
+
+        let header = CpackHeader::from_bytes(&data).unwrap();
+        assert_eq!(header.compressed_size as usize, data.len() - HEADER_SIZE);
+    }
+
+    #[test]
+    fn test_compress_decompress_roundtrips_per_codec() {
+        use crate::decompress::decompress_cpack;
+        use crate::frame::{COMPRESS_STORE, COMPRESS_ZSTD};
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        for (codec, level) in [(COMPRESS_STORE, 0), (COMPRESS_ZSTD, 1), (COMPRESS_ZSTD, 19)] {
+            let out = TempDir::new().unwrap();
+            let cpack_path = out.path().join("test.cpack");
+            let payload_hash =
+                compress_dpack_with_codec(dpack.path(), &cpack_path, codec, level).unwrap();
+
+            let restored = TempDir::new().unwrap();
+            let restored_hash = decompress_cpack(&cpack_path, restored.path()).unwrap();
+            assert_eq!(
+                payload_hash, restored_hash,
+                "payload hash must match after decompress for codec={codec} level={level}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_compress_dictionary_creates_file_with_trained_dictionary_mode() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        compress_dpack_with_dictionary(dpack.path(), &cpack_path, DEFAULT_ZSTD_LEVEL).unwrap();
+
+        let bytes = std::fs::read(&cpack_path).unwrap();
+        let header = CpackHeader::from_bytes(&bytes).unwrap();
+        assert_eq!(header.dictionary_mode, crate::frame::DICT_MODE_TRAINED);
+    }
+
+    #[test]
+    fn test_compress_dictionary_roundtrips_via_decompress() {
+        use crate::decompress::decompress_cpack_with_dictionary;
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        let payload_hash =
+            compress_dpack_with_dictionary(dpack.path(), &cpack_path, DEFAULT_ZSTD_LEVEL).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let restored_hash = decompress_cpack_with_dictionary(&cpack_path, restored.path()).unwrap();
+        assert_eq!(payload_hash, restored_hash);
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/README.md")).unwrap(),
+            "# Test"
+        );
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+    }
+
+    #[test]
+    fn test_compress_chunked_creates_file_with_chunked_format() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        let hash = compress_dpack_chunked(dpack.path(), &cpack_path).unwrap();
+        assert!(cpack_path.exists());
+        assert_eq!(hash.len(), 64);
+
+        let data = std::fs::read(&cpack_path).unwrap();
+        assert_eq!(&data[0..4], b"CPCK");
+        assert_eq!(data[6], crate::frame::PAYLOAD_CHUNKED);
+    }
+
+    #[test]
+    fn test_compress_chunked_dedupes_identical_file_content() {
+        let dpack = TempDir::new().unwrap();
+        let data_dir = dpack.path().join("data");
+        std::fs::create_dir_all(data_dir.join("a")).unwrap();
+        std::fs::create_dir_all(data_dir.join("b")).unwrap();
+        let body = "same content in two files\n".repeat(500);
+        std::fs::write(data_dir.join("a/one.txt"), &body).unwrap();
+        std::fs::write(data_dir.join("b/two.txt"), &body).unwrap();
+
+        let mut files = BTreeMap::new();
+        files.insert(
+            "a/one.txt".to_string(),
+            dpack_core::manifest::FileEntry::new(
+                seed_core::compute_sha256(body.as_bytes()),
+                body.len() as u64,
+            ),
+        );
+        files.insert(
+            "b/two.txt".to_string(),
+            dpack_core::manifest::FileEntry::new(
+                seed_core::compute_sha256(body.as_bytes()),
+                body.len() as u64,
+            ),
+        );
+        let hash_scheme = seed_core::hash::HashScheme::default();
+        let pack_hash = DpackManifest::compute_pack_hash(&files, &hash_scheme);
+        let manifest = DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: "test_fp".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/test".to_string(),
+            files,
+            pack_hash,
+            vcs: None,
+            hash_scheme,
+        };
+        std::fs::write(
+            dpack.path().join("manifest.json"),
+            serde_json::to_string_pretty(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let chunked_out = TempDir::new().unwrap();
+        let chunked_path = chunked_out.path().join("chunked.cpack");
+        compress_dpack_chunked(dpack.path(), &chunked_path).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        crate::decompress::decompress_cpack_chunked(&chunked_path, restored.path()).unwrap();
+        let restored_manifest: DpackManifest =
+            serde_json::from_slice(&std::fs::read(restored.path().join("manifest.json")).unwrap())
+                .unwrap();
+
+        let one = &restored_manifest.files["a/one.txt"];
+        let two = &restored_manifest.files["b/two.txt"];
+        assert!(!one.chunks.is_empty());
+        assert_eq!(
+            one.chunks, two.chunks,
+            "two byte-identical files must dedupe to the same chunk hash list"
+        );
+    }
+
+    #[test]
+    fn test_compress_chunk_store_creates_file_with_chunk_store_format_and_roundtrips() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+        let cstore_dir = TempDir::new().unwrap();
+
+        let hash = compress_dpack_chunk_store(dpack.path(), cstore_dir.path(), &cpack_path).unwrap();
+        assert!(cpack_path.exists());
+        assert_eq!(hash.len(), 64);
+
+        let data = std::fs::read(&cpack_path).unwrap();
+        assert_eq!(&data[0..4], b"CPCK");
+        assert_eq!(data[6], crate::frame::PAYLOAD_CHUNK_STORE);
+
+        let restored = TempDir::new().unwrap();
+        let restored_hash = crate::decompress::decompress_cpack_chunk_store(
+            &cpack_path,
+            cstore_dir.path(),
+            restored.path(),
+        )
+        .unwrap();
+        assert_eq!(hash, restored_hash);
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/README.md")).unwrap(),
+            "# Test"
+        );
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+    }
+
+    #[test]
+    fn test_compress_chunk_store_second_pack_of_similar_tree_writes_no_new_chunks_for_unchanged_file() {
+        let cstore_dir = TempDir::new().unwrap();
+
+        let dpack_v1 = TempDir::new().unwrap();
+        make_dpack(dpack_v1.path());
+        let out_v1 = TempDir::new().unwrap();
+        compress_dpack_chunk_store(
+            dpack_v1.path(),
+            cstore_dir.path(),
+            &out_v1.path().join("v1.cpack"),
+        )
+        .unwrap();
+
+        let unchanged_chunk_count = walkdir::WalkDir::new(cstore_dir.path())
+            .into_iter()
+            .filter(|e| e.as_ref().is_ok_and(|e| e.file_type().is_file()))
+            .count();
+
+        // Repacking the exact same tree into the same store must not add any
+        // new chunks - everything it needs is already on disk.
+        let dpack_v2 = TempDir::new().unwrap();
+        make_dpack(dpack_v2.path());
+        let out_v2 = TempDir::new().unwrap();
+        compress_dpack_chunk_store(
+            dpack_v2.path(),
+            cstore_dir.path(),
+            &out_v2.path().join("v2.cpack"),
+        )
+        .unwrap();
+
+        let total_chunk_count = walkdir::WalkDir::new(cstore_dir.path())
+            .into_iter()
+            .filter(|e| e.as_ref().is_ok_and(|e| e.file_type().is_file()))
+            .count();
+        assert_eq!(total_chunk_count, unchanged_chunk_count);
+    }
+
+    #[test]
+    fn test_chunk_dedup_stats_reports_savings_for_duplicated_content() {
+        let dpack = TempDir::new().unwrap();
+        let data_dir = dpack.path().join("data");
+        std::fs::create_dir_all(data_dir.join("a")).unwrap();
+        std::fs::create_dir_all(data_dir.join("b")).unwrap();
+        let body = "same content in two files\n".repeat(500);
+        std::fs::write(data_dir.join("a/one.txt"), &body).unwrap();
+        std::fs::write(data_dir.join("b/two.txt"), &body).unwrap();
+
+        let stats = chunk_dedup_stats(dpack.path()).unwrap();
+        assert_eq!(stats.total_bytes, 2 * body.len() as u64);
+        assert_eq!(stats.unique_bytes, body.len() as u64);
+        assert!((stats.ratio() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_chunk_dedup_stats_empty_dpack_has_zero_ratio() {
+        let dpack = TempDir::new().unwrap();
+        std::fs::create_dir_all(dpack.path().join("data")).unwrap();
+        let stats = chunk_dedup_stats(dpack.path()).unwrap();
+        assert_eq!(stats.total_bytes, 0);
+        assert_eq!(stats.unique_bytes, 0);
+        assert_eq!(stats.ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_compress_with_base_includes_only_changed_files() {
+        use crate::decompress::decompress_cpack_delta;
+        use dpack_core::manifest::FileEntry;
+
+        let base_dir = TempDir::new().unwrap();
+        make_dpack(base_dir.path());
+        let base_manifest: DpackManifest =
+            serde_json::from_slice(&std::fs::read(base_dir.path().join("manifest.json")).unwrap())
+                .unwrap();
+
+        let full_dir = TempDir::new().unwrap();
+        make_dpack(full_dir.path());
+        std::fs::write(full_dir.path().join("data/README.md"), "# Test v2").unwrap();
+        let mut full_manifest: DpackManifest = serde_json::from_slice(
+            &std::fs::read(full_dir.path().join("manifest.json")).unwrap(),
+        )
+        .unwrap();
+        full_manifest.files.insert(
+            "README.md".to_string(),
+            FileEntry::new(seed_core::compute_sha256(b"# Test v2"), 9),
+        );
+        full_manifest.pack_hash =
+            DpackManifest::compute_pack_hash(&full_manifest.files, &full_manifest.hash_scheme);
+        std::fs::write(
+            full_dir.path().join("manifest.json"),
+            serde_json::to_string_pretty(&full_manifest).unwrap(),
+        )
+        .unwrap();
+
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("delta.cpack");
+        compress_dpack_with_base(
+            full_dir.path(),
+            &cpack_path,
+            COMPRESS_ZSTD,
+            DEFAULT_ZSTD_LEVEL,
+            &base_manifest,
+        )
+        .unwrap();
+
+        let restored = TempDir::new().unwrap();
+        decompress_cpack_delta(&cpack_path, restored.path()).unwrap();
+
+        let delta: dpack_core::delta::DeltaManifest = serde_json::from_slice(
+            &std::fs::read(restored.path().join("delta_manifest.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(delta.added_or_changed.len(), 1);
+        assert!(delta.added_or_changed.contains_key("README.md"));
+        assert!(!restored.path().join("data/src/main.rs").exists());
+        assert!(restored.path().join("data/README.md").exists());
+    }
+
+    #[test]
+    fn test_compress_dpack_encrypted_creates_sealed_file() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        let hash = compress_dpack_encrypted(dpack.path(), &cpack_path, b"hunter2").unwrap();
+        assert!(cpack_path.exists());
+        assert_eq!(hash.len(), 64);
+
+        let data = std::fs::read(&cpack_path).unwrap();
+        assert_eq!(&data[0..4], b"CPCK");
+        assert_eq!(data[7], ENCRYPT_XCHACHA20POLY1305);
+    }
+
+    #[test]
+    fn test_compress_dpack_encrypted_roundtrips() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        compress_dpack_encrypted(dpack.path(), &cpack_path, b"hunter2").unwrap();
+
+        let restored = TempDir::new().unwrap();
+        crate::decompress::decompress_cpack_encrypted(&cpack_path, restored.path(), b"hunter2")
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/README.md")).unwrap(),
+            "# Test"
+        );
+    }
+
+    #[test]
+    fn test_compress_dpack_encrypted_wrong_passphrase_fails_closed() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        let out = TempDir::new().unwrap();
+        let cpack_path = out.path().join("test.cpack");
+
+        compress_dpack_encrypted(dpack.path(), &cpack_path, b"hunter2").unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let err = crate::decompress::decompress_cpack_encrypted(
+            &cpack_path,
+            restored.path(),
+            b"not the passphrase",
+        )
+        .unwrap_err();
+        assert!(matches!(err, FrameError::DecryptionFailed));
+    }
 }
@@ -0,0 +1,121 @@
+//! Cross-pack chunk store: persists FastCDC chunks (see [`crate::chunk`])
+//! to a `.cstore` directory shared across many `.cpack` files, so packing a
+//! new version of a similar tree only writes the chunks not already on
+//! disk, giving dedup across pack revisions rather than just within one.
+//!
+//! Layout mirrors [`dpack_core::pack::pack_repo_objects`]'s git-odb-style
+//! object store: `<cstore_dir>/<first two hex chars>/<remaining hex
+//! chars>`, keyed by each chunk's hex SHA-256.
+
+use crate::chunk::chunk_content;
+use crate::frame::{sha256_bytes, FrameError};
+use dpack_core::validate_hex_hash;
+use std::path::{Path, PathBuf};
+
+/// The on-disk path of the chunk store entry holding `hash`'s bytes,
+/// git-odb-style: `<cstore_dir>/<first two hex chars>/<remaining hex chars>`.
+/// Fails closed on a malformed `hash` rather than panicking on the slice -
+/// `reassemble`'s `chunk_hashes` come straight out of an untrusted
+/// deserialized manifest.
+pub fn chunk_path(cstore_dir: &Path, hash: &str) -> Result<PathBuf, FrameError> {
+    if !validate_hex_hash(hash) {
+        return Err(FrameError::MissingChunk(hash.to_string()));
+    }
+    Ok(cstore_dir.join(&hash[..2]).join(&hash[2..]))
+}
+
+/// Split `content` into FastCDC chunks and write each one to `cstore_dir`
+/// that isn't already present on disk. Returns the ordered list of chunk
+/// hashes making up `content` (for `FileEntry.chunks`) and how many of
+/// those chunks were newly written (for dedup stats).
+pub fn store_chunks(cstore_dir: &Path, content: &[u8]) -> Result<(Vec<String>, usize), FrameError> {
+    let mut hashes = Vec::new();
+    let mut written = 0;
+    for (hash, bytes) in chunk_content(content) {
+        let dest = chunk_path(cstore_dir, &hash)?;
+        if !dest.exists() {
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, &bytes)?;
+            written += 1;
+        }
+        hashes.push(hash);
+    }
+    Ok((hashes, written))
+}
+
+/// Reassemble a file's content by concatenating its chunks from
+/// `cstore_dir` in order, verifying each chunk's own SHA-256 against the
+/// hash the manifest expects before trusting it.
+pub fn reassemble(cstore_dir: &Path, chunk_hashes: &[String]) -> Result<Vec<u8>, FrameError> {
+    let mut out = Vec::new();
+    for hash in chunk_hashes {
+        let path = chunk_path(cstore_dir, hash)?;
+        let bytes = std::fs::read(&path).map_err(|_| FrameError::MissingChunk(hash.clone()))?;
+        if hex::encode(sha256_bytes(&bytes)) != *hash {
+            return Err(FrameError::MissingChunk(hash.clone()));
+        }
+        out.extend_from_slice(&bytes);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_store_then_reassemble_roundtrips() {
+        let cstore = TempDir::new().unwrap();
+        let content: Vec<u8> = (0..200_000u32).map(|i| (i * 7 % 256) as u8).collect();
+
+        let (hashes, written) = store_chunks(cstore.path(), &content).unwrap();
+        assert!(written > 0);
+
+        let reassembled = reassemble(cstore.path(), &hashes).unwrap();
+        assert_eq!(reassembled, content);
+    }
+
+    #[test]
+    fn test_second_store_of_same_content_writes_nothing_new() {
+        let cstore = TempDir::new().unwrap();
+        let content = b"some repeated content, stored across two pack versions".to_vec();
+
+        let (_, first_written) = store_chunks(cstore.path(), &content).unwrap();
+        let (_, second_written) = store_chunks(cstore.path(), &content).unwrap();
+
+        assert!(first_written > 0);
+        assert_eq!(second_written, 0);
+    }
+
+    #[test]
+    fn test_reassemble_detects_corrupted_chunk() {
+        let cstore = TempDir::new().unwrap();
+        let content = b"chunk store integrity check".to_vec();
+        let (hashes, _) = store_chunks(cstore.path(), &content).unwrap();
+
+        let path = chunk_path(cstore.path(), &hashes[0]).unwrap();
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] ^= 0xFF;
+        std::fs::write(&path, bytes).unwrap();
+
+        let err = reassemble(cstore.path(), &hashes).unwrap_err();
+        assert!(matches!(err, FrameError::MissingChunk(_)));
+    }
+
+    #[test]
+    fn test_reassemble_rejects_missing_chunk() {
+        let cstore = TempDir::new().unwrap();
+        let err = reassemble(cstore.path(), &["deadbeef".repeat(8)]).unwrap_err();
+        assert!(matches!(err, FrameError::MissingChunk(_)));
+    }
+
+    #[test]
+    fn test_reassemble_rejects_malformed_hash_instead_of_panicking() {
+        let cstore = TempDir::new().unwrap();
+        let err = reassemble(cstore.path(), &["x".to_string()]).unwrap_err();
+        assert!(matches!(err, FrameError::MissingChunk(_)));
+    }
+}
@@ -1,6 +1,17 @@
 //! Decompress a .cpack file back into a DPACK directory.
 
-use crate::frame::{decode_payload, sha256_bytes, CpackHeader, FrameError, HEADER_SIZE};
+use crate::codec::decompress_payload;
+use crate::crypto::{decrypt_payload, CryptoHeader, CRYPTO_HEADER_SIZE, ENCRYPT_XCHACHA20POLY1305};
+use crate::cstore;
+use crate::frame::{
+    decode_chunk_store_payload, decode_chunked_payload, decode_dictionary_payload, decode_payload,
+    sha256_bytes, validate_rel_path, CpackHeader, FrameError, DICT_MODE_NONE, DICT_MODE_TRAINED,
+    HEADER_SIZE, PAYLOAD_CHUNK_STORE, PAYLOAD_CHUNKED, PAYLOAD_SEEKABLE,
+};
+use crate::seekable::decode_seekable_payload;
+use dpack_core::manifest::DpackManifest;
+use dpack_core::receipt::{AuditReceipt, GateResult, GateStatus};
+use ed25519_dalek::VerifyingKey;
 use std::path::Path;
 
 /// Decompress a .cpack file into a DPACK directory.
@@ -19,6 +30,12 @@ pub fn decompress_cpack(cpack_path: &Path, output_dir: &Path) -> Result<String,
 
     // Parse header
     let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.encryption_method != crate::crypto::ENCRYPT_NONE {
+        return Err(FrameError::MissingPassphrase);
+    }
+    if header.dictionary_mode != DICT_MODE_NONE {
+        return Err(FrameError::UnsupportedDictionaryMode(header.dictionary_mode));
+    }
 
     // Extract compressed data
     let compressed = &cpack_data[HEADER_SIZE..];
@@ -26,8 +43,8 @@ pub fn decompress_cpack(cpack_path: &Path, output_dir: &Path) -> Result<String,
         return Err(FrameError::PayloadTruncated);
     }
 
-    // Decompress
-    let payload = zstd::decode_all(compressed)?;
+    // Decompress (dispatches on header.compression_method)
+    let payload = decompress_payload(compressed, header.compression_method)?;
 
     // Verify integrity
     let actual_hash = sha256_bytes(&payload);
@@ -55,6 +72,551 @@ pub fn decompress_cpack(cpack_path: &Path, output_dir: &Path) -> Result<String,
     Ok(hex::encode(actual_hash))
 }
 
+/// Decompress a .cpack file produced by [`crate::compress::compress_dpack_chunked`]
+/// into a DPACK directory.
+///
+/// Each file is reassembled by concatenating its `FileEntry.chunks` in
+/// order against the payload's chunk store. Verifies SHA-256 integrity
+/// of the whole payload before writing.
+pub fn decompress_cpack_chunked(
+    cpack_path: &Path,
+    output_dir: &Path,
+) -> Result<String, FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.payload_format != PAYLOAD_CHUNKED {
+        return Err(FrameError::UnsupportedPayloadFormat(header.payload_format));
+    }
+
+    let compressed = &cpack_data[HEADER_SIZE..];
+    if compressed.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+
+    let payload = decompress_payload(compressed, header.compression_method)?;
+
+    let actual_hash = sha256_bytes(&payload);
+    if actual_hash != header.payload_sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+
+    let (manifest_json, chunk_store) = decode_chunked_payload(&payload)?;
+    let manifest: DpackManifest = serde_json::from_slice(&manifest_json)?;
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    let data_dir = output_dir.join("data");
+    for (rel_path, entry) in &manifest.files {
+        validate_rel_path(rel_path)?;
+        let mut content = Vec::new();
+        for hash in &entry.chunks {
+            let chunk = chunk_store
+                .get(hash)
+                .ok_or_else(|| FrameError::MissingChunk(hash.clone()))?;
+            content.extend_from_slice(chunk);
+        }
+
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, &content)?;
+    }
+
+    Ok(hex::encode(actual_hash))
+}
+
+/// Decompress a .cpack file produced by
+/// [`crate::compress::compress_dpack_chunk_store`] into a DPACK directory.
+///
+/// The cpack itself carries only the manifest; each file is reassembled by
+/// resolving its `FileEntry.chunks` against `cstore_dir` (see
+/// [`crate::cstore::reassemble`]), which must be the same chunk store the
+/// pack was produced against. Verifies the flat-encoded payload's SHA-256
+/// against the header before writing, same as
+/// [`decompress_cpack_with_dictionary`].
+pub fn decompress_cpack_chunk_store(
+    cpack_path: &Path,
+    cstore_dir: &Path,
+    output_dir: &Path,
+) -> Result<String, FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.payload_format != PAYLOAD_CHUNK_STORE {
+        return Err(FrameError::UnsupportedPayloadFormat(header.payload_format));
+    }
+
+    let compressed = &cpack_data[HEADER_SIZE..];
+    if compressed.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let body = decompress_payload(compressed, header.compression_method)?;
+    let manifest_json = decode_chunk_store_payload(&body)?;
+    let manifest: DpackManifest = serde_json::from_slice(&manifest_json)?;
+
+    let mut sorted_files: Vec<(String, Vec<u8>)> = Vec::new();
+    for (rel_path, entry) in &manifest.files {
+        validate_rel_path(rel_path)?;
+        let content = cstore::reassemble(cstore_dir, &entry.chunks)?;
+        sorted_files.push((rel_path.clone(), content));
+    }
+    sorted_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let payload = crate::frame::encode_payload(&manifest_json, &sorted_files);
+    let actual_hash = sha256_bytes(&payload);
+    if actual_hash != header.payload_sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    let data_dir = output_dir.join("data");
+    for (rel_path, content) in &sorted_files {
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+    }
+
+    Ok(hex::encode(actual_hash))
+}
+
+/// Decompress a .cpack file produced by
+/// [`crate::compress::compress_dpack_with_dictionary`] into a DPACK
+/// directory.
+///
+/// Each file is decompressed individually against the dictionary embedded
+/// in the frame. Verifies SHA-256 integrity of the flat-encoded payload
+/// (the same encoding a non-dictionary cpack would hash) before writing.
+pub fn decompress_cpack_with_dictionary(
+    cpack_path: &Path,
+    output_dir: &Path,
+) -> Result<String, FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.dictionary_mode != DICT_MODE_TRAINED {
+        return Err(FrameError::UnsupportedDictionaryMode(header.dictionary_mode));
+    }
+
+    let body = &cpack_data[HEADER_SIZE..];
+    if body.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+
+    let (manifest_json, files) = decode_dictionary_payload(body)?;
+
+    let payload = crate::frame::encode_payload(&manifest_json, &files);
+    let actual_hash = sha256_bytes(&payload);
+    if actual_hash != header.payload_sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    let data_dir = output_dir.join("data");
+    for (rel_path, content) in &files {
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+    }
+
+    Ok(hex::encode(actual_hash))
+}
+
+/// Decompress a .cpack file produced by [`crate::compress::compress_dpack_seekable`]
+/// into a DPACK directory, iterating its frames in order and decompressing
+/// each individually.
+///
+/// For a full extract this does no less work than the other modes; it's
+/// [`crate::seekable::extract_file`] that benefits from the seekable layout,
+/// by decompressing only one frame instead of going through here at all.
+pub fn decompress_cpack_seekable(cpack_path: &Path, output_dir: &Path) -> Result<String, FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.payload_format != PAYLOAD_SEEKABLE {
+        return Err(FrameError::UnsupportedPayloadFormat(header.payload_format));
+    }
+
+    let body = &cpack_data[HEADER_SIZE..];
+    if body.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+
+    let (manifest_json, files) = decode_seekable_payload(body)?;
+
+    let payload = crate::frame::encode_payload(&manifest_json, &files);
+    let actual_hash = sha256_bytes(&payload);
+    if actual_hash != header.payload_sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    let data_dir = output_dir.join("data");
+    for (rel_path, content) in &files {
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+    }
+
+    Ok(hex::encode(actual_hash))
+}
+
+/// Decompress a .cpack file produced by
+/// [`crate::compress::compress_dpack_encrypted`] into a DPACK directory.
+///
+/// Re-derives the key from `passphrase` and the stored Argon2id parameters,
+/// verifies the Poly1305 tag against the serialized fixed header (the AEAD
+/// associated data) before decompressing, and still cross-checks
+/// `payload_sha256` afterwards. Fails closed on any mismatch.
+pub fn decompress_cpack_encrypted(
+    cpack_path: &Path,
+    output_dir: &Path,
+    passphrase: &[u8],
+) -> Result<String, FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.encryption_method != ENCRYPT_XCHACHA20POLY1305 {
+        return Err(FrameError::UnsupportedEncryptionMethod(
+            header.encryption_method,
+        ));
+    }
+
+    let rest = &cpack_data[HEADER_SIZE..];
+    if rest.len() < CRYPTO_HEADER_SIZE {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let crypto_header = CryptoHeader::from_bytes(&rest[..CRYPTO_HEADER_SIZE])?;
+    let ciphertext = &rest[CRYPTO_HEADER_SIZE..];
+    if ciphertext.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+
+    let aad = &cpack_data[..HEADER_SIZE];
+    let compressed = decrypt_payload(&crypto_header, ciphertext, passphrase, aad)?;
+
+    let payload = zstd::decode_all(compressed.as_slice())?;
+
+    let actual_hash = sha256_bytes(&payload);
+    if actual_hash != header.payload_sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+
+    let (manifest_json, files) = decode_payload(&payload)?;
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    let data_dir = output_dir.join("data");
+    for (rel_path, content) in &files {
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+    }
+
+    Ok(hex::encode(actual_hash))
+}
+
+/// Like [`decompress_cpack`], but first verifies the CPACK's detached
+/// signature (see [`crate::sign::verify_cpack_signature`]) against
+/// `verifying_key`, rejecting with [`FrameError::SignatureInvalid`] before
+/// any decompression happens. Returns the decompressed payload hash
+/// alongside a `G5_SIGNATURE` [`GateResult`] so a caller building a full
+/// [`dpack_core::receipt::AuditReceipt`] can fold the signature's pass/fail
+/// status in next to the integrity gates.
+pub fn decompress_cpack_verified(
+    cpack_path: &Path,
+    output_dir: &Path,
+    verifying_key: &VerifyingKey,
+) -> Result<(String, GateResult), FrameError> {
+    crate::sign::verify_cpack_signature(cpack_path, verifying_key)?;
+    let gate = GateResult {
+        gate: "G5_SIGNATURE".to_string(),
+        status: GateStatus::Pass,
+        detail: "detached ed25519 signature verified against supplied key".to_string(),
+    };
+    let hash = decompress_cpack(cpack_path, output_dir)?;
+    Ok((hash, gate))
+}
+
+/// Decompress a .cpack file while recording a full `dpack_core`-style audit
+/// receipt: `G0_SCHEMA` (manifest.json parses and its schema_version is
+/// "1.0"), `G1_INTEGRITY` (payload SHA-256 matches the header),
+/// `G2_PER_FILE_HASH` (each extracted file's SHA-256 matches its
+/// `FileEntry.sha256`), and `G3_PACK_HASH` (recomputed
+/// `DpackManifest::compute_pack_hash` equals `manifest.pack_hash`). Files
+/// are only written to `output_dir` when every gate passes. Once the payload
+/// decompresses and decodes, the receipt is returned even when a gate fails,
+/// so a caller can see exactly what failed; a bad header, truncated payload,
+/// decompression failure or undecodable payload returns an error instead.
+/// Mirrors
+/// [`dpack_core::pack::verify_pack`]'s gate style, scoped to a single
+/// compressed `.cpack` rather than a repo pack directory.
+pub fn decompress_cpack_audited(
+    cpack_path: &Path,
+    output_dir: &Path,
+) -> Result<(String, AuditReceipt), FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.encryption_method != crate::crypto::ENCRYPT_NONE {
+        return Err(FrameError::MissingPassphrase);
+    }
+    if header.dictionary_mode != DICT_MODE_NONE {
+        return Err(FrameError::UnsupportedDictionaryMode(header.dictionary_mode));
+    }
+
+    let compressed = &cpack_data[HEADER_SIZE..];
+    if compressed.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let payload = decompress_payload(compressed, header.compression_method)?;
+    let actual_hash = sha256_bytes(&payload);
+    let (manifest_json, files) = decode_payload(&payload)?;
+
+    let manifest: Option<DpackManifest> = serde_json::from_slice(&manifest_json).ok();
+    let mut gates = Vec::new();
+
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: match &manifest {
+            Some(m) if m.schema_version == "1.0" => GateStatus::Pass,
+            _ => GateStatus::Fail,
+        },
+        detail: match &manifest {
+            Some(m) => format!("schema_version={}", m.schema_version),
+            None => "manifest.json failed to parse".to_string(),
+        },
+    });
+
+    let integrity_ok = actual_hash == header.payload_sha256;
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: if integrity_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if integrity_ok {
+            "payload SHA-256 matches header".to_string()
+        } else {
+            "payload SHA-256 mismatch".to_string()
+        },
+    });
+
+    let mut per_file_ok = manifest.is_some();
+    let mut per_file_detail = if manifest.is_some() {
+        format!("{} files verified", files.len())
+    } else {
+        "manifest unavailable".to_string()
+    };
+    if let Some(manifest) = &manifest {
+        for (rel_path, content) in &files {
+            match manifest.files.get(rel_path) {
+                Some(entry) => {
+                    let actual = manifest.hash_scheme.digest(content);
+                    if actual != entry.sha256 {
+                        per_file_ok = false;
+                        per_file_detail = format!("hash mismatch: {rel_path}");
+                        break;
+                    }
+                }
+                None => {
+                    per_file_ok = false;
+                    per_file_detail = format!("no manifest entry for {rel_path}");
+                    break;
+                }
+            }
+        }
+    }
+    gates.push(GateResult {
+        gate: "G2_PER_FILE_HASH".to_string(),
+        status: if per_file_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: per_file_detail,
+    });
+
+    let pack_hash_ok = manifest.as_ref().is_some_and(|m| {
+        DpackManifest::compute_pack_hash(&m.files, &m.hash_scheme) == m.pack_hash
+    });
+    gates.push(GateResult {
+        gate: "G3_PACK_HASH".to_string(),
+        status: if pack_hash_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if pack_hash_ok {
+            "pack_hash matches".to_string()
+        } else {
+            "pack_hash mismatch".to_string()
+        },
+    });
+
+    let all_passed = gates.iter().all(|g| g.status != GateStatus::Fail);
+    if all_passed {
+        std::fs::create_dir_all(output_dir)?;
+        std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+        let data_dir = output_dir.join("data");
+        for (rel_path, content) in &files {
+            let dest = data_dir.join(rel_path);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, content)?;
+        }
+    }
+
+    let seed_fingerprint = manifest
+        .as_ref()
+        .map(|m| m.root_2i_seed_fingerprint.clone())
+        .unwrap_or_default();
+    let receipt = AuditReceipt::new(
+        "decompress",
+        &seed_fingerprint,
+        manifest.as_ref().map(|m| m.pack_hash.as_str()),
+        gates,
+    );
+
+    Ok((hex::encode(actual_hash), receipt))
+}
+
+/// Decompress a .cpack file produced by
+/// [`crate::compress::compress_dpack_with_base`] into a delta pack directory:
+/// `delta_manifest.json` plus a `data/` holding only the changed files.
+/// The result has the same shape [`dpack_core::pack::pack_repo_delta`]
+/// produces, so it can be passed straight to
+/// [`dpack_core::pack::unfurl_pack_delta`] or
+/// [`dpack_core::pack::verify_pack_delta`] against the appropriate base.
+/// Verifies SHA-256 integrity before writing.
+pub fn decompress_cpack_delta(cpack_path: &Path, output_dir: &Path) -> Result<String, FrameError> {
+    let cpack_data = std::fs::read(cpack_path)?;
+
+    if cpack_data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: cpack_data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+
+    let header = CpackHeader::from_bytes(&cpack_data)?;
+    if header.encryption_method != crate::crypto::ENCRYPT_NONE {
+        return Err(FrameError::MissingPassphrase);
+    }
+
+    let compressed = &cpack_data[HEADER_SIZE..];
+    if compressed.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+
+    let payload = decompress_payload(compressed, header.compression_method)?;
+
+    let actual_hash = sha256_bytes(&payload);
+    if actual_hash != header.payload_sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+
+    let (delta_manifest_json, files) = decode_payload(&payload)?;
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(output_dir.join("delta_manifest.json"), &delta_manifest_json)?;
+
+    let data_dir = output_dir.join("data");
+    for (rel_path, content) in &files {
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+    }
+
+    Ok(hex::encode(actual_hash))
+}
+
+/// Decompress a delta `.cpack` (see [`crate::compress::compress_dpack_with_base`])
+/// straight into a reconstructed full DPACK directory at `output_dir`, by
+/// decoding it into `delta_dir` (same shape [`decompress_cpack_delta`]
+/// produces) and merging that onto `base_pack_dir` via
+/// [`dpack_core::pack::materialize_delta_pack`]. Fails closed if
+/// `base_pack_dir` isn't the exact base the delta was diffed against, or if
+/// the merged tree's pack_hash doesn't match the delta's advertised
+/// `full_pack_hash` - the correct-base check [`dpack_core::delta::apply_delta`]
+/// already does, which makes a separate base-hash field on [`CpackHeader`]
+/// unnecessary here. Returns the reconstructed manifest's pack_hash.
+pub fn reconstruct_cpack_delta(
+    delta_cpack_path: &Path,
+    delta_dir: &Path,
+    base_pack_dir: &Path,
+    output_dir: &Path,
+) -> Result<String, FrameError> {
+    decompress_cpack_delta(delta_cpack_path, delta_dir)?;
+
+    let full_manifest = dpack_core::pack::materialize_delta_pack(delta_dir, base_pack_dir, output_dir)
+        .map_err(|e| FrameError::DeltaReconstructionFailed(e.to_string()))?;
+
+    Ok(full_manifest.pack_hash)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,19 +632,14 @@ mod tests {
         let mut files = std::collections::BTreeMap::new();
         files.insert(
             "README.md".to_string(),
-            dpack_core::manifest::FileEntry {
-                sha256: seed_core::compute_sha256(b"# Test"),
-                size: 6,
-            },
+            dpack_core::manifest::FileEntry::new(seed_core::compute_sha256(b"# Test"), 6),
         );
         files.insert(
             "src/main.rs".to_string(),
-            dpack_core::manifest::FileEntry {
-                sha256: seed_core::compute_sha256(b"fn main() {}"),
-                size: 12,
-            },
+            dpack_core::manifest::FileEntry::new(seed_core::compute_sha256(b"fn main() {}"), 12),
         );
-        let pack_hash = dpack_core::manifest::DpackManifest::compute_pack_hash(&files);
+        let hash_scheme = seed_core::hash::HashScheme::default();
+        let pack_hash = dpack_core::manifest::DpackManifest::compute_pack_hash(&files, &hash_scheme);
         let manifest = dpack_core::manifest::DpackManifest {
             schema_version: "1.0".to_string(),
             root_2i_seed_fingerprint: "test_fp".to_string(),
@@ -90,6 +647,8 @@ mod tests {
             source_root: "/tmp/test".to_string(),
             files,
             pack_hash,
+            vcs: None,
+            hash_scheme,
         };
         let json = serde_json::to_string_pretty(&manifest).unwrap();
         std::fs::write(dir.join("manifest.json"), json).unwrap();
@@ -149,4 +708,534 @@ mod tests {
         let result = decompress_cpack(&cpack_path, restored.path());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_roundtrip_compress_decompress_chunked() {
+        use crate::compress::compress_dpack_chunked;
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack_chunked(dpack.path(), &cpack_path).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        decompress_cpack_chunked(&cpack_path, restored.path()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/README.md")).unwrap(),
+            "# Test"
+        );
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+    }
+
+    #[test]
+    fn test_decompress_chunked_rejects_flat_cpack() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack(dpack.path(), &cpack_path).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_chunked(&cpack_path, restored.path()).unwrap_err();
+        assert!(matches!(err, FrameError::UnsupportedPayloadFormat(_)));
+    }
+
+    #[test]
+    fn test_decompress_chunk_store_rejects_flat_cpack() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack(dpack.path(), &cpack_path).unwrap();
+
+        let cstore_dir = TempDir::new().unwrap();
+        let restored = TempDir::new().unwrap();
+        let err =
+            decompress_cpack_chunk_store(&cpack_path, cstore_dir.path(), restored.path())
+                .unwrap_err();
+        assert!(matches!(err, FrameError::UnsupportedPayloadFormat(_)));
+    }
+
+    #[test]
+    fn test_decompress_chunk_store_fails_closed_on_missing_chunk() {
+        use crate::compress::compress_dpack_chunk_store;
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        let cstore_dir = TempDir::new().unwrap();
+        compress_dpack_chunk_store(dpack.path(), cstore_dir.path(), &cpack_path).unwrap();
+
+        // Decompressing against an empty store (none of the chunks present)
+        // must fail rather than silently reassembling wrong/empty content.
+        let other_cstore_dir = TempDir::new().unwrap();
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_chunk_store(
+            &cpack_path,
+            other_cstore_dir.path(),
+            restored.path(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, FrameError::MissingChunk(_)));
+    }
+
+    #[test]
+    fn test_roundtrip_compress_decompress_dictionary() {
+        use crate::compress::compress_dpack_with_dictionary;
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack_with_dictionary(dpack.path(), &cpack_path, 3).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        decompress_cpack_with_dictionary(&cpack_path, restored.path()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/README.md")).unwrap(),
+            "# Test"
+        );
+        assert_eq!(
+            std::fs::read_to_string(restored.path().join("data/src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+    }
+
+    #[test]
+    fn test_decompress_dictionary_rejects_flat_cpack() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack(dpack.path(), &cpack_path).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_with_dictionary(&cpack_path, restored.path()).unwrap_err();
+        assert!(matches!(err, FrameError::UnsupportedDictionaryMode(_)));
+    }
+
+    #[test]
+    fn test_decompress_cpack_rejects_dictionary_mode() {
+        use crate::compress::compress_dpack_with_dictionary;
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack_with_dictionary(dpack.path(), &cpack_path, 3).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack(&cpack_path, restored.path()).unwrap_err();
+        assert!(matches!(err, FrameError::UnsupportedDictionaryMode(_)));
+    }
+
+    #[test]
+    fn test_decompress_cpack_rejects_encrypted_without_passphrase() {
+        use crate::compress::compress_dpack_encrypted;
+
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack_encrypted(dpack.path(), &cpack_path, b"hunter2").unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack(&cpack_path, restored.path()).unwrap_err();
+        assert!(matches!(err, FrameError::MissingPassphrase));
+    }
+
+    #[test]
+    fn test_reconstruct_cpack_delta_matches_full_pack_of_mutated_tree() {
+        use crate::compress::compress_dpack_with_base;
+        use dpack_core::manifest::FileEntry;
+
+        let base_dir = TempDir::new().unwrap();
+        make_dpack(base_dir.path());
+        let base_manifest: DpackManifest =
+            serde_json::from_slice(&std::fs::read(base_dir.path().join("manifest.json")).unwrap())
+                .unwrap();
+
+        // Mutate one file in a copy of the base tree.
+        let mutated_dir = TempDir::new().unwrap();
+        make_dpack(mutated_dir.path());
+        std::fs::write(mutated_dir.path().join("data/README.md"), "# Test v2").unwrap();
+        let mut mutated_manifest: DpackManifest = serde_json::from_slice(
+            &std::fs::read(mutated_dir.path().join("manifest.json")).unwrap(),
+        )
+        .unwrap();
+        mutated_manifest.files.insert(
+            "README.md".to_string(),
+            FileEntry::new(seed_core::compute_sha256(b"# Test v2"), 9),
+        );
+        mutated_manifest.pack_hash = DpackManifest::compute_pack_hash(
+            &mutated_manifest.files,
+            &mutated_manifest.hash_scheme,
+        );
+        std::fs::write(
+            mutated_dir.path().join("manifest.json"),
+            serde_json::to_string_pretty(&mutated_manifest).unwrap(),
+        )
+        .unwrap();
+
+        // Produce a delta cpack of the mutated tree against the base.
+        let out = TempDir::new().unwrap();
+        let delta_cpack_path = out.path().join("delta.cpack");
+        compress_dpack_with_base(
+            mutated_dir.path(),
+            &delta_cpack_path,
+            crate::frame::COMPRESS_ZSTD,
+            crate::codec::DEFAULT_ZSTD_LEVEL,
+            &base_manifest,
+        )
+        .unwrap();
+
+        // Reconstruct straight from the delta cpack + base dir.
+        let delta_dir = TempDir::new().unwrap();
+        let reconstructed_dir = TempDir::new().unwrap();
+        let pack_hash = reconstruct_cpack_delta(
+            &delta_cpack_path,
+            delta_dir.path(),
+            base_dir.path(),
+            reconstructed_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(pack_hash, mutated_manifest.pack_hash);
+
+        // A full pack of the mutated tree must be byte-identical to the
+        // delta-reconstructed one.
+        let reconstructed_manifest: DpackManifest = serde_json::from_slice(
+            &std::fs::read(reconstructed_dir.path().join("manifest.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(reconstructed_manifest.files, mutated_manifest.files);
+        assert_eq!(reconstructed_manifest.pack_hash, mutated_manifest.pack_hash);
+        assert_eq!(
+            std::fs::read(reconstructed_dir.path().join("data/README.md")).unwrap(),
+            std::fs::read(mutated_dir.path().join("data/README.md")).unwrap(),
+        );
+        assert_eq!(
+            std::fs::read(reconstructed_dir.path().join("data/src/main.rs")).unwrap(),
+            std::fs::read(mutated_dir.path().join("data/src/main.rs")).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_reconstruct_cpack_delta_rejects_wrong_base() {
+        use crate::compress::compress_dpack_with_base;
+
+        let base_dir = TempDir::new().unwrap();
+        make_dpack(base_dir.path());
+        let base_manifest: DpackManifest =
+            serde_json::from_slice(&std::fs::read(base_dir.path().join("manifest.json")).unwrap())
+                .unwrap();
+
+        let mutated_dir = TempDir::new().unwrap();
+        make_dpack(mutated_dir.path());
+        std::fs::write(mutated_dir.path().join("data/README.md"), "# Test v2").unwrap();
+        let mut mutated_manifest: DpackManifest = serde_json::from_slice(
+            &std::fs::read(mutated_dir.path().join("manifest.json")).unwrap(),
+        )
+        .unwrap();
+        mutated_manifest.files.insert(
+            "README.md".to_string(),
+            dpack_core::manifest::FileEntry::new(seed_core::compute_sha256(b"# Test v2"), 9),
+        );
+        mutated_manifest.pack_hash = DpackManifest::compute_pack_hash(
+            &mutated_manifest.files,
+            &mutated_manifest.hash_scheme,
+        );
+        std::fs::write(
+            mutated_dir.path().join("manifest.json"),
+            serde_json::to_string_pretty(&mutated_manifest).unwrap(),
+        )
+        .unwrap();
+
+        let out = TempDir::new().unwrap();
+        let delta_cpack_path = out.path().join("delta.cpack");
+        compress_dpack_with_base(
+            mutated_dir.path(),
+            &delta_cpack_path,
+            crate::frame::COMPRESS_ZSTD,
+            crate::codec::DEFAULT_ZSTD_LEVEL,
+            &base_manifest,
+        )
+        .unwrap();
+
+        // A different (wrong) base tree - one whose unchanged file also
+        // differs, so its pack_hash genuinely diverges from the real base -
+        // must be rejected rather than silently reconstructing a corrupted
+        // result.
+        let wrong_base_dir = TempDir::new().unwrap();
+        make_dpack(wrong_base_dir.path());
+        std::fs::write(wrong_base_dir.path().join("data/src/main.rs"), "fn main() { /* not the base */ }").unwrap();
+        let mut wrong_base_manifest: DpackManifest = serde_json::from_slice(
+            &std::fs::read(wrong_base_dir.path().join("manifest.json")).unwrap(),
+        )
+        .unwrap();
+        wrong_base_manifest.files.insert(
+            "src/main.rs".to_string(),
+            dpack_core::manifest::FileEntry::new(
+                seed_core::compute_sha256(b"fn main() { /* not the base */ }"),
+                32,
+            ),
+        );
+        wrong_base_manifest.pack_hash = DpackManifest::compute_pack_hash(
+            &wrong_base_manifest.files,
+            &wrong_base_manifest.hash_scheme,
+        );
+        std::fs::write(
+            wrong_base_dir.path().join("manifest.json"),
+            serde_json::to_string_pretty(&wrong_base_manifest).unwrap(),
+        )
+        .unwrap();
+
+        let delta_dir = TempDir::new().unwrap();
+        let reconstructed_dir = TempDir::new().unwrap();
+        let err = reconstruct_cpack_delta(
+            &delta_cpack_path,
+            delta_dir.path(),
+            wrong_base_dir.path(),
+            reconstructed_dir.path(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, FrameError::DeltaReconstructionFailed(_)));
+    }
+
+
+    #[test]
+    fn test_decompress_cpack_audited_all_gates_pass() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack(dpack.path(), &cpack_path).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let (hash, receipt) = decompress_cpack_audited(&cpack_path, restored.path()).unwrap();
+
+        assert!(receipt.passed);
+        assert_eq!(receipt.gates.len(), 4);
+        assert_eq!(hash.len(), 64);
+        assert_eq!(receipt.root_2i_seed_fingerprint, "test_fp");
+        assert!(restored.path().join("data/README.md").exists());
+    }
+
+    /// Wrap an already-encoded payload in a minimal valid `CpackHeader` and
+    /// write it to a fresh temp file, for tests that need to hand-craft a
+    /// malicious `.cpack` rather than go through the normal `compress_dpack_*`
+    /// path (which would never itself produce an unsafe `rel_path`).
+    fn write_raw_cpack(payload: &[u8], payload_format: u8, dictionary_mode: u8) -> TempDir {
+        let header = CpackHeader {
+            version: crate::frame::CPACK_VERSION,
+            compression_method: crate::frame::COMPRESS_STORE,
+            payload_format,
+            encryption_method: crate::crypto::ENCRYPT_NONE,
+            payload_sha256: sha256_bytes(payload),
+            compressed_size: payload.len() as u64,
+            dictionary_mode,
+            merkle_root: [0u8; 32],
+        };
+        let mut out = header.to_bytes();
+        out.extend_from_slice(payload);
+
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("test.cpack"), &out).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_decompress_cpack_rejects_path_traversal() {
+        let payload = crate::frame::encode_payload(
+            b"{}",
+            &[("../../etc/passwd".to_string(), b"evil".to_vec())],
+        );
+        let cpack_dir = write_raw_cpack(&payload, crate::frame::PAYLOAD_FLAT, DICT_MODE_NONE);
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack(&cpack_dir.path().join("test.cpack"), restored.path())
+            .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_delta_rejects_path_traversal() {
+        let payload = crate::frame::encode_payload(
+            b"{}",
+            &[("../../etc/passwd".to_string(), b"evil".to_vec())],
+        );
+        let cpack_dir = write_raw_cpack(&payload, crate::frame::PAYLOAD_FLAT, DICT_MODE_NONE);
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_delta(&cpack_dir.path().join("test.cpack"), restored.path())
+            .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_audited_rejects_path_traversal() {
+        let payload = crate::frame::encode_payload(
+            b"{}",
+            &[("../../etc/passwd".to_string(), b"evil".to_vec())],
+        );
+        let cpack_dir = write_raw_cpack(&payload, crate::frame::PAYLOAD_FLAT, DICT_MODE_NONE);
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_audited(&cpack_dir.path().join("test.cpack"), restored.path())
+            .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_with_dictionary_rejects_path_traversal() {
+        use crate::codec::{compress_with_dictionary, train_dictionary};
+
+        let samples = vec![b"sample content".to_vec()];
+        let dictionary = train_dictionary(&samples).unwrap();
+        let compressed = compress_with_dictionary(&samples[0], &dictionary, 3).unwrap();
+        let payload = crate::frame::encode_dictionary_payload(
+            b"{}",
+            &dictionary,
+            &[(
+                "../../etc/passwd".to_string(),
+                samples[0].len() as u64,
+                compressed,
+            )],
+        );
+        let cpack_dir = write_raw_cpack(&payload, crate::frame::PAYLOAD_FLAT, DICT_MODE_TRAINED);
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_with_dictionary(
+            &cpack_dir.path().join("test.cpack"),
+            restored.path(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_seekable_rejects_path_traversal() {
+        let payload = crate::seekable::encode_seekable_payload(
+            b"{}",
+            &[("../../etc/passwd".to_string(), b"evil".to_vec())],
+            3,
+        )
+        .unwrap();
+        let cpack_dir = write_raw_cpack(&payload, PAYLOAD_SEEKABLE, DICT_MODE_NONE);
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_seekable(&cpack_dir.path().join("test.cpack"), restored.path())
+            .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_chunked_rejects_path_traversal_in_manifest() {
+        use dpack_core::manifest::FileEntry;
+
+        let mut entry = FileEntry::new(seed_core::compute_sha256(b"evil"), 4);
+        entry.chunks = vec![hex::encode(sha256_bytes(b"evil"))];
+        let mut files = std::collections::BTreeMap::new();
+        files.insert("../../etc/passwd".to_string(), entry);
+        let manifest = dpack_core::manifest::DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: "test_fp".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/test".to_string(),
+            files,
+            pack_hash: "deadbeef".to_string(),
+            vcs: None,
+            hash_scheme: seed_core::hash::HashScheme::default(),
+        };
+        let manifest_json = serde_json::to_vec(&manifest).unwrap();
+        let chunks = vec![(hex::encode(sha256_bytes(b"evil")), b"evil".to_vec())];
+        let payload = crate::frame::encode_chunked_payload(&manifest_json, &chunks);
+        let cpack_dir = write_raw_cpack(&payload, PAYLOAD_CHUNKED, DICT_MODE_NONE);
+
+        let restored = TempDir::new().unwrap();
+        let err =
+            decompress_cpack_chunked(&cpack_dir.path().join("test.cpack"), restored.path())
+                .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_chunk_store_rejects_path_traversal_in_manifest() {
+        use dpack_core::manifest::FileEntry;
+
+        let cstore_dir = TempDir::new().unwrap();
+        let (hashes, _) = cstore::store_chunks(cstore_dir.path(), b"evil").unwrap();
+
+        let mut entry = FileEntry::new(seed_core::compute_sha256(b"evil"), 4);
+        entry.chunks = hashes;
+        let mut files = std::collections::BTreeMap::new();
+        files.insert("../../etc/passwd".to_string(), entry);
+        let manifest = dpack_core::manifest::DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: "test_fp".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/test".to_string(),
+            files,
+            pack_hash: "deadbeef".to_string(),
+            vcs: None,
+            hash_scheme: seed_core::hash::HashScheme::default(),
+        };
+        let manifest_json = serde_json::to_vec(&manifest).unwrap();
+        let payload = crate::frame::encode_chunk_store_payload(&manifest_json);
+        let cpack_dir = write_raw_cpack(&payload, PAYLOAD_CHUNK_STORE, DICT_MODE_NONE);
+
+        let restored = TempDir::new().unwrap();
+        let err = decompress_cpack_chunk_store(
+            &cpack_dir.path().join("test.cpack"),
+            cstore_dir.path(),
+            restored.path(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, FrameError::UnsafeRelPath(_)));
+        assert!(!restored.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_decompress_cpack_audited_fails_gate_and_skips_write_on_tampered_file_hash() {
+        let dpack = TempDir::new().unwrap();
+        make_dpack(dpack.path());
+        std::fs::write(dpack.path().join("data/README.md"), "# Tampered").unwrap();
+
+        let cpack_file = TempDir::new().unwrap();
+        let cpack_path = cpack_file.path().join("test.cpack");
+        compress_dpack(dpack.path(), &cpack_path).unwrap();
+
+        let restored = TempDir::new().unwrap();
+        let (_, receipt) = decompress_cpack_audited(&cpack_path, restored.path()).unwrap();
+
+        assert!(!receipt.passed);
+        let per_file_gate = receipt
+            .gates
+            .iter()
+            .find(|g| g.gate == "G2_PER_FILE_HASH")
+            .unwrap();
+        assert_eq!(per_file_gate.status, GateStatus::Fail);
+        assert!(!restored.path().join("data/README.md").exists());
+    }
 }
@@ -3,13 +3,31 @@
 //! Binary format (v1):
 //!   Bytes 0-3:    Magic "CPCK"
 //!   Byte  4:      Version (1)
-//!   Byte  5:      Compression method (1 = zstd)
-//!   Bytes 6-7:    Reserved (0x00, 0x00)
+//!   Byte  5:      Compression method (0 = store, 1 = zstd, 2 = xz reserved;
+//!                 see [`crate::codec`])
+//!   Byte  6:      Payload format (0 = flat files, 1 = chunked, 2 = seekable,
+//!                 3 = chunk store; see below)
+//!   Byte  7:      Encryption method (0 = none, 1 = XChaCha20-Poly1305; see [`crate::crypto`])
 //!   Bytes 8-39:   SHA-256 of uncompressed payload (32 bytes)
 //!   Bytes 40-47:  Compressed data length (u64 LE)
-//!   Bytes 48..:   Zstd compressed payload
+//!   Byte  48:     Dictionary mode (0 = none, 1 = trained zstd dictionary;
+//!                 see [`crate::codec::train_dictionary`]). Only meaningful
+//!                 when compression method is zstd.
+//!   Bytes 49-80:  Merkle root over the payload's `(path, content)` entries
+//!                 (see [`crate::merkle`]), letting a consumer check a single
+//!                 extracted file's inclusion proof against a pinned pack
+//!                 root without decompressing the rest of the payload.
+//!   Bytes 81..:   If dictionary mode is trained: a dictionary-mode payload
+//!                 (see [`encode_dictionary_payload`]) - the trained
+//!                 dictionary followed by the manifest and each file
+//!                 individually compressed against it. Otherwise, if
+//!                 encryption method is none: the compressed payload.
+//!                 Otherwise: a [`crate::crypto::CryptoHeader`], followed by
+//!                 the zstd compressed payload sealed under it (ciphertext
+//!                 plus Poly1305 tag). `compressed_size` covers only the
+//!                 bytes after the crypto header in that case.
 //!
-//! Payload format (before compression):
+//! Flat payload format (`PAYLOAD_FLAT`, before compression):
 //!   u32 LE: manifest JSON length
 //!   bytes:  manifest JSON (canonical, sorted keys)
 //!   u32 LE: file count
@@ -18,9 +36,34 @@
 //!     bytes:  path
 //!     u64 LE: content length
 //!     bytes:  content
+//!
+//! Chunked payload format (`PAYLOAD_CHUNKED`, see [`crate::chunk`]):
+//!   u32 LE: manifest JSON length
+//!   bytes:  manifest JSON (each `FileEntry.chunks` lists the ordered
+//!           chunk hashes that reassemble that file; content itself is
+//!           not inlined in the manifest)
+//!   u32 LE: chunk count
+//!   For each chunk (sorted by hex hash):
+//!     u32 LE: hash length (UTF-8 hex)
+//!     bytes:  hash
+//!     u64 LE: chunk content length
+//!     bytes:  chunk content
+//!
+//! Seekable payload (`PAYLOAD_SEEKABLE`, see [`crate::seekable`]): each file
+//! is its own independent zstd frame rather than one stream over the whole
+//! payload, plus a trailer index, so a single file can be extracted by
+//! seeking straight to its frame without decompressing the rest of the pack.
+//!
+//! Chunk store payload (`PAYLOAD_CHUNK_STORE`, see [`crate::cstore`]):
+//!   u32 LE: manifest JSON length
+//!   bytes:  manifest JSON (each `FileEntry.chunks` lists the ordered
+//!           chunk hashes that reassemble that file; unlike
+//!           `PAYLOAD_CHUNKED`, no chunk content travels with this cpack
+//!           at all - every chunk is read from an external, cross-pack
+//!           `.cstore` directory instead, so packing a new version of a
+//!           similar tree only ever writes chunks not already on disk)
 
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 
 /// Magic bytes identifying a CPACK file.
 pub const CPACK_MAGIC: &[u8; 4] = b"CPCK";
@@ -28,19 +71,63 @@ pub const CPACK_MAGIC: &[u8; 4] = b"CPCK";
 /// Current CPACK format version.
 pub const CPACK_VERSION: u8 = 1;
 
+/// Compression method: zero-copy passthrough, no compression.
+pub const COMPRESS_STORE: u8 = 0;
+
 /// Compression method: zstd.
 pub const COMPRESS_ZSTD: u8 = 1;
 
+/// Compression method: xz/LZMA. Reserved - not yet implemented by
+/// [`crate::codec`]; encoding or decoding this method currently fails with
+/// [`FrameError::CodecNotImplemented`].
+pub const COMPRESS_XZ: u8 = 2;
+
+/// Payload format: file contents inlined directly (the original format).
+pub const PAYLOAD_FLAT: u8 = 0;
+
+/// Payload format: file contents stored once per distinct FastCDC chunk,
+/// referenced by hash from the manifest (see [`crate::chunk`]).
+pub const PAYLOAD_CHUNKED: u8 = 1;
+
+/// Payload format: each file compressed as its own independent zstd frame
+/// plus a trailer index, enabling single-file extraction without a full
+/// decompress (see [`crate::seekable`]).
+pub const PAYLOAD_SEEKABLE: u8 = 2;
+
+/// Payload format: no file content inlined at all - just the manifest JSON,
+/// whose `FileEntry.chunks` resolve against an external, cross-pack chunk
+/// store (see [`crate::cstore`]) instead of this cpack's own bytes.
+pub const PAYLOAD_CHUNK_STORE: u8 = 3;
+
+/// Dictionary mode: no trained dictionary; the payload is compressed as one
+/// whole blob (see [`crate::codec::compress_payload`]).
+pub const DICT_MODE_NONE: u8 = 0;
+
+/// Dictionary mode: a zstd dictionary trained on the pack's own file
+/// contents, with each file compressed individually against it (see
+/// [`crate::codec::train_dictionary`], [`encode_dictionary_payload`]).
+pub const DICT_MODE_TRAINED: u8 = 1;
+
+/// Re-exported so callers matching on header fields don't need to depend on
+/// the `crypto` module directly for the common case.
+pub use crate::crypto::{ENCRYPT_NONE, ENCRYPT_XCHACHA20POLY1305};
+
 /// Fixed header size in bytes.
-pub const HEADER_SIZE: usize = 48;
+pub const HEADER_SIZE: usize = 81;
 
 /// CPACK file header.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpackHeader {
     pub version: u8,
     pub compression_method: u8,
+    pub payload_format: u8,
+    pub encryption_method: u8,
     pub payload_sha256: [u8; 32],
     pub compressed_size: u64,
+    pub dictionary_mode: u8,
+    /// Merkle root over the payload's `(path, content)` entries (see
+    /// [`crate::merkle`]).
+    pub merkle_root: [u8; 32],
 }
 
 impl CpackHeader {
@@ -50,9 +137,12 @@ impl CpackHeader {
         buf.extend_from_slice(CPACK_MAGIC);
         buf.push(self.version);
         buf.push(self.compression_method);
-        buf.extend_from_slice(&[0u8; 2]); // reserved
+        buf.push(self.payload_format);
+        buf.push(self.encryption_method);
         buf.extend_from_slice(&self.payload_sha256);
         buf.extend_from_slice(&self.compressed_size.to_le_bytes());
+        buf.push(self.dictionary_mode);
+        buf.extend_from_slice(&self.merkle_root);
         buf
     }
 
@@ -72,17 +162,42 @@ impl CpackHeader {
             return Err(FrameError::UnsupportedVersion(version));
         }
         let compression_method = data[5];
-        if compression_method != COMPRESS_ZSTD {
+        if compression_method != COMPRESS_STORE
+            && compression_method != COMPRESS_ZSTD
+            && compression_method != COMPRESS_XZ
+        {
             return Err(FrameError::UnsupportedCompression(compression_method));
         }
+        let payload_format = data[6];
+        if payload_format != PAYLOAD_FLAT
+            && payload_format != PAYLOAD_CHUNKED
+            && payload_format != PAYLOAD_SEEKABLE
+            && payload_format != PAYLOAD_CHUNK_STORE
+        {
+            return Err(FrameError::UnsupportedPayloadFormat(payload_format));
+        }
+        let encryption_method = data[7];
+        if encryption_method != ENCRYPT_NONE && encryption_method != ENCRYPT_XCHACHA20POLY1305 {
+            return Err(FrameError::UnsupportedEncryptionMethod(encryption_method));
+        }
         let mut sha = [0u8; 32];
         sha.copy_from_slice(&data[8..40]);
         let compressed_size = u64::from_le_bytes(data[40..48].try_into().unwrap());
+        let dictionary_mode = data[48];
+        if dictionary_mode != DICT_MODE_NONE && dictionary_mode != DICT_MODE_TRAINED {
+            return Err(FrameError::UnsupportedDictionaryMode(dictionary_mode));
+        }
+        let mut merkle_root = [0u8; 32];
+        merkle_root.copy_from_slice(&data[49..81]);
         Ok(Self {
             version,
             compression_method,
+            payload_format,
+            encryption_method,
             payload_sha256: sha,
             compressed_size,
+            dictionary_mode,
+            merkle_root,
         })
     }
 }
@@ -116,6 +231,26 @@ pub fn encode_payload(
     buf
 }
 
+/// Reject a `rel_path` read out of an untrusted payload that could escape
+/// the `data/` directory it's about to be joined onto: an absolute path
+/// makes `Path::join` discard the base entirely, and a `..` component
+/// walks back out of it. Every payload decoder that hands a `rel_path`
+/// back to a caller for writing must run it through this first - mirrors
+/// `dpack_core::pack`'s `validate_rel_path`, for the same class of bug one
+/// layer further downstream (a compressed `.cpack` rather than a DPACK
+/// directory).
+pub(crate) fn validate_rel_path(rel_path: &str) -> Result<(), FrameError> {
+    let path = std::path::Path::new(rel_path);
+    let unsafe_path = path.is_absolute()
+        || path
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir));
+    if unsafe_path {
+        return Err(FrameError::UnsafeRelPath(rel_path.to_string()));
+    }
+    Ok(())
+}
+
 /// Decode a payload back into manifest JSON and file entries.
 pub fn decode_payload(data: &[u8]) -> Result<(Vec<u8>, Vec<(String, Vec<u8>)>), FrameError> {
     let mut pos = 0;
@@ -152,6 +287,7 @@ pub fn decode_payload(data: &[u8]) -> Result<(Vec<u8>, Vec<(String, Vec<u8>)>),
         }
         let path = String::from_utf8(data[pos..pos + plen].to_vec())
             .map_err(|_| FrameError::InvalidUtf8Path)?;
+        validate_rel_path(&path)?;
         pos += plen;
 
         // Content
@@ -172,13 +308,243 @@ pub fn decode_payload(data: &[u8]) -> Result<(Vec<u8>, Vec<(String, Vec<u8>)>),
     Ok((manifest_json, files))
 }
 
-/// Compute SHA-256 of a byte slice.
+/// Encode a chunked payload: manifest JSON (whose `FileEntry.chunks`
+/// already reference the chunk hashes below) + the deduplicated chunk
+/// store, sorted by hash -> deterministic bytes.
+pub fn encode_chunked_payload(
+    manifest_json: &[u8],
+    chunks: &[(String, Vec<u8>)], // sorted by hash
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    let mlen = manifest_json.len() as u32;
+    buf.extend_from_slice(&mlen.to_le_bytes());
+    buf.extend_from_slice(manifest_json);
+
+    let ccount = chunks.len() as u32;
+    buf.extend_from_slice(&ccount.to_le_bytes());
+
+    for (hash, content) in chunks {
+        let hlen = hash.len() as u32;
+        buf.extend_from_slice(&hlen.to_le_bytes());
+        buf.extend_from_slice(hash.as_bytes());
+        let clen = content.len() as u64;
+        buf.extend_from_slice(&clen.to_le_bytes());
+        buf.extend_from_slice(content);
+    }
+
+    buf
+}
+
+/// Decode a chunked payload back into manifest JSON and the chunk store
+/// (hash -> content).
+pub fn decode_chunked_payload(
+    data: &[u8],
+) -> Result<(Vec<u8>, std::collections::BTreeMap<String, Vec<u8>>), FrameError> {
+    let mut pos = 0;
+
+    if data.len() < pos + 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let mlen = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+    pos += 4;
+    if data.len() < pos + mlen {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let manifest_json = data[pos..pos + mlen].to_vec();
+    pos += mlen;
+
+    if data.len() < pos + 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let ccount = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+    pos += 4;
+
+    let mut chunks = std::collections::BTreeMap::new();
+    for _ in 0..ccount {
+        if data.len() < pos + 4 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let hlen = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        pos += 4;
+        if data.len() < pos + hlen {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let hash = String::from_utf8(data[pos..pos + hlen].to_vec())
+            .map_err(|_| FrameError::InvalidUtf8Path)?;
+        pos += hlen;
+
+        if data.len() < pos + 8 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let clen = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap()) as usize;
+        pos += 8;
+        if data.len() < pos + clen {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let content = data[pos..pos + clen].to_vec();
+        pos += clen;
+
+        chunks.insert(hash, content);
+    }
+
+    Ok((manifest_json, chunks))
+}
+
+/// Encode a chunk-store-mode payload: just the manifest JSON, length
+/// prefixed like every other payload format. Unlike [`encode_chunked_payload`],
+/// no chunk content is stored here - it lives in an external
+/// [`crate::cstore`] directory shared across packs (see [`PAYLOAD_CHUNK_STORE`]).
+pub fn encode_chunk_store_payload(manifest_json: &[u8]) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(4 + manifest_json.len());
+    let mlen = manifest_json.len() as u32;
+    buf.extend_from_slice(&mlen.to_le_bytes());
+    buf.extend_from_slice(manifest_json);
+    buf
+}
+
+/// Decode a chunk-store-mode payload back into its manifest JSON.
+pub fn decode_chunk_store_payload(data: &[u8]) -> Result<Vec<u8>, FrameError> {
+    if data.len() < 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let mlen = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
+    if data.len() < 4 + mlen {
+        return Err(FrameError::PayloadTruncated);
+    }
+    Ok(data[4..4 + mlen].to_vec())
+}
+
+/// Encode a dictionary-mode payload: the trained dictionary, the manifest
+/// JSON (stored uncompressed, as it's typically small), and each file's
+/// content individually compressed against the dictionary (see
+/// [`crate::codec::train_dictionary`], [`crate::codec::compress_with_dictionary`]).
+/// `files` must already be sorted by path, each entry holding the original
+/// (uncompressed) content length alongside the dictionary-compressed bytes
+/// so [`decode_dictionary_payload`] can size its decompression buffer.
+pub fn encode_dictionary_payload(
+    manifest_json: &[u8],
+    dictionary: &[u8],
+    files: &[(String, u64, Vec<u8>)], // (path, original_len, compressed content), sorted by path
+) -> Vec<u8> {
+    let mut buf = Vec::new();
+
+    let dlen = dictionary.len() as u32;
+    buf.extend_from_slice(&dlen.to_le_bytes());
+    buf.extend_from_slice(dictionary);
+
+    let mlen = manifest_json.len() as u32;
+    buf.extend_from_slice(&mlen.to_le_bytes());
+    buf.extend_from_slice(manifest_json);
+
+    let fcount = files.len() as u32;
+    buf.extend_from_slice(&fcount.to_le_bytes());
+
+    for (path, original_len, compressed) in files {
+        let plen = path.len() as u32;
+        buf.extend_from_slice(&plen.to_le_bytes());
+        buf.extend_from_slice(path.as_bytes());
+        buf.extend_from_slice(&original_len.to_le_bytes());
+        let clen = compressed.len() as u64;
+        buf.extend_from_slice(&clen.to_le_bytes());
+        buf.extend_from_slice(compressed);
+    }
+
+    buf
+}
+
+/// Decode a dictionary-mode payload back into the manifest JSON and the
+/// fully decompressed file entries (path -> original content). Each entry
+/// is decompressed against the embedded dictionary individually.
+pub fn decode_dictionary_payload(
+    data: &[u8],
+) -> Result<(Vec<u8>, Vec<(String, Vec<u8>)>), FrameError> {
+    let mut pos = 0;
+
+    if data.len() < pos + 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let dlen = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+    pos += 4;
+    if data.len() < pos + dlen {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let dictionary = &data[pos..pos + dlen];
+    pos += dlen;
+
+    if data.len() < pos + 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let mlen = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+    pos += 4;
+    if data.len() < pos + mlen {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let manifest_json = data[pos..pos + mlen].to_vec();
+    pos += mlen;
+
+    if data.len() < pos + 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let fcount = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+    pos += 4;
+
+    let mut files = Vec::with_capacity(fcount);
+    for _ in 0..fcount {
+        if data.len() < pos + 4 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let plen = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        pos += 4;
+        if data.len() < pos + plen {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let path = String::from_utf8(data[pos..pos + plen].to_vec())
+            .map_err(|_| FrameError::InvalidUtf8Path)?;
+        validate_rel_path(&path)?;
+        pos += plen;
+
+        if data.len() < pos + 8 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let original_len = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap()) as usize;
+        pos += 8;
+
+        if data.len() < pos + 8 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let clen = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap()) as usize;
+        pos += 8;
+        if data.len() < pos + clen {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let compressed = &data[pos..pos + clen];
+        pos += clen;
+
+        let content = crate::codec::decompress_with_dictionary(compressed, dictionary, original_len)?;
+        files.push((path, content));
+    }
+
+    Ok((manifest_json, files))
+}
+
+/// Bytes fed to the hasher per call in [`sha256_bytes`], so hashing a large
+/// payload doesn't require a single huge `update` call.
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Compute SHA-256 of a byte slice, feeding it to the hasher in fixed-size
+/// chunks via [`seed_core::StreamingHasher`] rather than one `update` over
+/// the whole buffer - the same incremental hasher a future streaming zstd
+/// encoder/decoder could feed directly as compressed bytes flow, instead of
+/// buffering the full payload purely to hash it.
 pub fn sha256_bytes(data: &[u8]) -> [u8; 32] {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    let result = hasher.finalize();
+    let mut hasher = seed_core::StreamingHasher::new();
+    for chunk in data.chunks(HASH_CHUNK_SIZE) {
+        hasher.update(chunk);
+    }
+    let digest = hex::decode(hasher.finalize_hex()).expect("hex digest is always valid hex");
     let mut out = [0u8; 32];
-    out.copy_from_slice(&result);
+    out.copy_from_slice(&digest);
     out
 }
 
@@ -192,12 +558,44 @@ pub enum FrameError {
     UnsupportedVersion(u8),
     #[error("unsupported compression method: {0}")]
     UnsupportedCompression(u8),
+    #[error("codec not yet implemented: {0}")]
+    CodecNotImplemented(u8),
+    #[error("unknown codec name: {0} (expected store, zstd, or xz)")]
+    UnknownCodecName(String),
+    #[error("unsupported payload format: {0}")]
+    UnsupportedPayloadFormat(u8),
+    #[error("unsupported dictionary mode: {0}")]
+    UnsupportedDictionaryMode(u8),
+    #[error("unsupported encryption method: {0}")]
+    UnsupportedEncryptionMethod(u8),
     #[error("payload truncated")]
     PayloadTruncated,
     #[error("invalid UTF-8 in file path")]
     InvalidUtf8Path,
+    #[error("unsafe path in payload: {0}")]
+    UnsafeRelPath(String),
     #[error("payload SHA-256 mismatch")]
     IntegrityMismatch,
+    #[error("missing chunk {0} referenced by manifest")]
+    MissingChunk(String),
+    #[error("no entry for {rel_path} in the seekable trailer index")]
+    EntryNotFound { rel_path: String },
+    #[error("malformed CPACK armor: {0}")]
+    MalformedArmor(String),
+    #[error("CPACK armor checksum mismatch: expected CRC32 {expected:08x}, got {actual:08x}")]
+    ArmorChecksumMismatch { expected: u32, actual: u32 },
+    #[error("delta reconstruction failed: {0}")]
+    DeltaReconstructionFailed(String),
+    #[error("a passphrase or key file is required to decrypt this CPACK")]
+    MissingPassphrase,
+    #[error("key derivation failed: {0}")]
+    KeyDerivation(String),
+    #[error("encryption failed")]
+    EncryptionFailed,
+    #[error("decryption failed (wrong passphrase, or payload was tampered with)")]
+    DecryptionFailed,
+    #[error("CPACK signature is missing, malformed, or does not verify against the supplied key")]
+    SignatureInvalid,
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
     #[error("JSON error: {0}")]
@@ -213,8 +611,12 @@ mod tests {
         let header = CpackHeader {
             version: CPACK_VERSION,
             compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_FLAT,
+            encryption_method: ENCRYPT_NONE,
             payload_sha256: [0xAB; 32],
             compressed_size: 12345,
+            dictionary_mode: DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
         };
         let bytes = header.to_bytes();
         assert_eq!(bytes.len(), HEADER_SIZE);
@@ -241,6 +643,28 @@ mod tests {
         assert_eq!(dec_files[1].1, b"world");
     }
 
+    #[test]
+    fn test_decode_payload_rejects_path_traversal() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let files = vec![("../../etc/passwd".to_string(), b"evil".to_vec())];
+        let encoded = encode_payload(manifest, &files);
+        assert!(matches!(
+            decode_payload(&encoded),
+            Err(FrameError::UnsafeRelPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_decode_payload_rejects_absolute_path() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let files = vec![("/etc/passwd".to_string(), b"evil".to_vec())];
+        let encoded = encode_payload(manifest, &files);
+        assert!(matches!(
+            decode_payload(&encoded),
+            Err(FrameError::UnsafeRelPath(_))
+        ));
+    }
+
     #[test]
     fn test_payload_deterministic() {
         let manifest = b"{\"test\":true}";
@@ -254,10 +678,156 @@ mod tests {
     fn test_bad_magic() {
         let data = b"XXXX\x01\x01\x00\x00";
         let mut buf = data.to_vec();
-        buf.extend_from_slice(&[0u8; 40]);
+        buf.extend_from_slice(&[0u8; HEADER_SIZE - 8]);
         assert!(matches!(
             CpackHeader::from_bytes(&buf),
             Err(FrameError::BadMagic)
         ));
     }
+
+    #[test]
+    fn test_chunked_payload_roundtrip() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let chunks = vec![
+            ("aaa".to_string(), b"hello".to_vec()),
+            ("bbb".to_string(), b"world".to_vec()),
+        ];
+        let encoded = encode_chunked_payload(manifest, &chunks);
+        let (dec_manifest, dec_chunks) = decode_chunked_payload(&encoded).unwrap();
+        assert_eq!(dec_manifest, manifest);
+        assert_eq!(dec_chunks.len(), 2);
+        assert_eq!(dec_chunks.get("aaa").unwrap(), b"hello");
+        assert_eq!(dec_chunks.get("bbb").unwrap(), b"world");
+    }
+
+    #[test]
+    fn test_unsupported_payload_format() {
+        let mut header = CpackHeader {
+            version: CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_CHUNKED,
+            encryption_method: ENCRYPT_NONE,
+            payload_sha256: [0u8; 32],
+            compressed_size: 0,
+            dictionary_mode: DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        }
+        .to_bytes();
+        header[6] = 0xFF;
+        assert!(matches!(
+            CpackHeader::from_bytes(&header),
+            Err(FrameError::UnsupportedPayloadFormat(0xFF))
+        ));
+    }
+
+    #[test]
+    fn test_header_accepts_store_and_xz_compression_methods() {
+        for method in [COMPRESS_STORE, COMPRESS_XZ] {
+            let header = CpackHeader {
+                version: CPACK_VERSION,
+                compression_method: method,
+                payload_format: PAYLOAD_FLAT,
+                encryption_method: ENCRYPT_NONE,
+                payload_sha256: [0u8; 32],
+                compressed_size: 0,
+                dictionary_mode: DICT_MODE_NONE,
+                merkle_root: [0u8; 32],
+            }
+            .to_bytes();
+            let parsed = CpackHeader::from_bytes(&header).unwrap();
+            assert_eq!(parsed.compression_method, method);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_encryption_method() {
+        let mut header = CpackHeader {
+            version: CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_FLAT,
+            encryption_method: ENCRYPT_XCHACHA20POLY1305,
+            payload_sha256: [0u8; 32],
+            compressed_size: 0,
+            dictionary_mode: DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        }
+        .to_bytes();
+        header[7] = 0xFF;
+        assert!(matches!(
+            CpackHeader::from_bytes(&header),
+            Err(FrameError::UnsupportedEncryptionMethod(0xFF))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_dictionary_mode() {
+        let mut header = CpackHeader {
+            version: CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_FLAT,
+            encryption_method: ENCRYPT_NONE,
+            payload_sha256: [0u8; 32],
+            compressed_size: 0,
+            dictionary_mode: DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        }
+        .to_bytes();
+        header[48] = 0xFF;
+        assert!(matches!(
+            CpackHeader::from_bytes(&header),
+            Err(FrameError::UnsupportedDictionaryMode(0xFF))
+        ));
+    }
+
+    #[test]
+    fn test_dictionary_payload_roundtrip() {
+        use crate::codec::{compress_with_dictionary, train_dictionary};
+
+        let manifest = b"{\"version\":\"1.0\"}";
+        let samples = vec![
+            b"fn main() { println!(\"hi\"); }".to_vec(),
+            b"fn main() { println!(\"bye\"); }".to_vec(),
+        ];
+        let dictionary = train_dictionary(&samples).unwrap();
+
+        let files: Vec<(String, u64, Vec<u8>)> = samples
+            .iter()
+            .enumerate()
+            .map(|(i, content)| {
+                let compressed = compress_with_dictionary(content, &dictionary, 3).unwrap();
+                (format!("src/f{i}.rs"), content.len() as u64, compressed)
+            })
+            .collect();
+
+        let encoded = encode_dictionary_payload(manifest, &dictionary, &files);
+        let (dec_manifest, dec_files) = decode_dictionary_payload(&encoded).unwrap();
+        assert_eq!(dec_manifest, manifest);
+        assert_eq!(dec_files.len(), 2);
+        assert_eq!(dec_files[0].1, samples[0]);
+        assert_eq!(dec_files[1].1, samples[1]);
+    }
+
+    #[test]
+    fn test_decode_dictionary_payload_rejects_path_traversal() {
+        use crate::codec::{compress_with_dictionary, train_dictionary};
+
+        let manifest = b"{\"version\":\"1.0\"}";
+        let samples = vec![
+            b"fn main() { println!(\"hi\"); }".to_vec(),
+            b"fn main() { println!(\"bye\"); }".to_vec(),
+        ];
+        let dictionary = train_dictionary(&samples).unwrap();
+        let compressed = compress_with_dictionary(&samples[0], &dictionary, 3).unwrap();
+        let files = vec![(
+            "../../../etc/passwd".to_string(),
+            samples[0].len() as u64,
+            compressed,
+        )];
+
+        let encoded = encode_dictionary_payload(manifest, &dictionary, &files);
+        assert!(matches!(
+            decode_dictionary_payload(&encoded),
+            Err(FrameError::UnsafeRelPath(_))
+        ));
+    }
 }
@@ -4,10 +4,32 @@
 //! Format: header + zstd-compressed payload + SHA-256 integrity hash.
 //! Round-trip invariant: decompress(compress(dpack)) == dpack.
 
+pub mod armor;
+pub mod chunk;
+pub mod codec;
 pub mod compress;
+pub mod crypto;
+pub mod cstore;
 pub mod decompress;
 pub mod frame;
+pub mod merkle;
+pub mod mount;
+pub mod seekable;
+pub mod sign;
 
-pub use compress::compress_dpack;
-pub use decompress::decompress_cpack;
+pub use armor::{armor, dearmor};
+pub use merkle::{merkle_root, verify_inclusion, MerkleTree, Side};
+pub use mount::{mount_cpack, MountHandle};
+pub use seekable::{extract_file, list_entries};
+pub use sign::{sign_cpack, verify_cpack_signature, CpackSignature};
+pub use compress::{
+    chunk_dedup_stats, compress_dpack, compress_dpack_auto, compress_dpack_chunk_store,
+    compress_dpack_chunked, compress_dpack_encrypted, compress_dpack_seekable,
+    compress_dpack_with_base, compress_dpack_with_codec, compress_dpack_with_dictionary, DedupStats,
+};
+pub use decompress::{
+    decompress_cpack, decompress_cpack_audited, decompress_cpack_chunk_store,
+    decompress_cpack_chunked, decompress_cpack_delta, decompress_cpack_encrypted,
+    decompress_cpack_seekable, decompress_cpack_verified, decompress_cpack_with_dictionary,
+};
 pub use frame::{CpackHeader, CPACK_MAGIC, CPACK_VERSION};
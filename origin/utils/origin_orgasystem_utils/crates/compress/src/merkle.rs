@@ -0,0 +1,208 @@
+//! Merkle tree over a DPACK's file contents, so a consumer can confirm a
+//! single artifact belongs to a pinned pack root without decompressing
+//! everything else in the pack.
+//!
+//! Leaves are individual files (path + content, so two files can't be
+//! confused with each other just because they share content); interior
+//! nodes pair up the level below in sorted-path order. Leaf and interior
+//! hashes are domain-separated (`0x00` / `0x01` prefix) so a leaf digest can
+//! never be replayed as an interior digest or vice versa (a second-preimage
+//! attack against the tree shape).
+
+use sha2::{Digest, Sha256};
+
+const LEAF_DOMAIN: u8 = 0x00;
+const NODE_DOMAIN: u8 = 0x01;
+
+/// Which side of an interior node a sibling digest sits on, needed to fold
+/// an [`MerkleTree::inclusion_proof`] back into the root in the right order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// Hash of one file: domain-separated over its path and content so that
+/// two files with identical bytes at different paths never collide.
+pub fn leaf_digest(path: &str, content: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_DOMAIN]);
+    hasher.update((path.len() as u32).to_le_bytes());
+    hasher.update(path.as_bytes());
+    hasher.update(content);
+    hasher.finalize().into()
+}
+
+fn interior_digest(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_DOMAIN]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// A Merkle tree over a DPACK's sorted `(path, content)` entries, built once
+/// and then queried for a root digest and per-file inclusion proofs.
+pub struct MerkleTree {
+    /// `levels[0]` is the leaves; `levels.last()` holds just the root.
+    levels: Vec<Vec<[u8; 32]>>,
+    /// Paths in the same sorted order as `levels[0]`.
+    paths: Vec<String>,
+}
+
+impl MerkleTree {
+    /// Build the tree over `files`, sorting by path first so the result is
+    /// independent of the order the caller passes them in. An odd node out
+    /// at a level is promoted unchanged to the level above (its digest is
+    /// simply carried forward, not paired with itself), matching the
+    /// corresponding no-sibling-added step in `inclusion_proof`.
+    pub fn build(files: &[(String, Vec<u8>)]) -> Self {
+        let mut sorted: Vec<(String, Vec<u8>)> = files.to_vec();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let leaves: Vec<[u8; 32]> = sorted
+            .iter()
+            .map(|(path, content)| leaf_digest(path, content))
+            .collect();
+        let paths: Vec<String> = sorted.into_iter().map(|(path, _)| path).collect();
+
+        let mut levels = vec![leaves];
+        while levels.last().expect("levels always has at least one entry").len() > 1 {
+            let prev = levels.last().expect("checked non-empty above");
+            let mut next = Vec::with_capacity(prev.len().div_ceil(2));
+            for pair in prev.chunks(2) {
+                match pair {
+                    [left, right] => next.push(interior_digest(left, right)),
+                    [lone] => next.push(*lone),
+                    _ => unreachable!("chunks(2) never yields more than 2 elements"),
+                }
+            }
+            levels.push(next);
+        }
+
+        MerkleTree { levels, paths }
+    }
+
+    /// The single root digest, `[0u8; 32]` for an empty tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|top| top.first())
+            .copied()
+            .unwrap_or([0u8; 32])
+    }
+
+    /// The sibling digests from `path`'s leaf up to the root, in bottom-up
+    /// order, each tagged with which side of its interior node it sits on.
+    /// `None` if `path` isn't in the tree.
+    pub fn inclusion_proof(&self, path: &str) -> Option<Vec<(Side, [u8; 32])>> {
+        let mut index = self.paths.iter().position(|p| p == path)?;
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let is_right = index % 2 == 1;
+            let sibling_index = if is_right { index - 1 } else { index + 1 };
+            if let Some(sibling) = level.get(sibling_index) {
+                let side = if is_right { Side::Left } else { Side::Right };
+                proof.push((side, *sibling));
+            }
+            index /= 2;
+        }
+        Some(proof)
+    }
+}
+
+/// Build the Merkle tree over `files` and return just its root digest.
+pub fn merkle_root(files: &[(String, Vec<u8>)]) -> [u8; 32] {
+    MerkleTree::build(files).root()
+}
+
+/// Recompute the root by folding `proof`'s siblings onto `leaf` in order,
+/// and check it matches `root`.
+pub fn verify_inclusion(leaf: [u8; 32], proof: &[(Side, [u8; 32])], root: [u8; 32]) -> bool {
+    let mut current = leaf;
+    for (side, sibling) in proof {
+        current = match side {
+            Side::Left => interior_digest(sibling, &current),
+            Side::Right => interior_digest(&current, sibling),
+        };
+    }
+    current == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files() -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("a.txt".to_string(), b"alpha".to_vec()),
+            ("b.txt".to_string(), b"beta".to_vec()),
+            ("c.txt".to_string(), b"gamma".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn test_root_is_order_independent() {
+        let forward = merkle_root(&files());
+        let mut reversed = files();
+        reversed.reverse();
+        let backward = merkle_root(&reversed);
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn test_single_file_tree_root_is_its_leaf() {
+        let one = vec![("only.txt".to_string(), b"content".to_vec())];
+        assert_eq!(merkle_root(&one), leaf_digest("only.txt", b"content"));
+    }
+
+    #[test]
+    fn test_inclusion_proof_verifies_for_every_leaf() {
+        let files = files();
+        let tree = MerkleTree::build(&files);
+        let root = tree.root();
+
+        for (path, content) in &files {
+            let proof = tree.inclusion_proof(path).unwrap();
+            let leaf = leaf_digest(path, content);
+            assert!(verify_inclusion(leaf, &proof, root), "proof failed for {path}");
+        }
+    }
+
+    #[test]
+    fn test_inclusion_proof_rejects_wrong_content() {
+        let files = files();
+        let tree = MerkleTree::build(&files);
+        let root = tree.root();
+
+        let proof = tree.inclusion_proof("a.txt").unwrap();
+        let tampered_leaf = leaf_digest("a.txt", b"not alpha");
+        assert!(!verify_inclusion(tampered_leaf, &proof, root));
+    }
+
+    #[test]
+    fn test_inclusion_proof_absent_path_is_none() {
+        let tree = MerkleTree::build(&files());
+        assert!(tree.inclusion_proof("missing.txt").is_none());
+    }
+
+    #[test]
+    fn test_leaf_domain_separated_from_interior_node() {
+        // A two-leaf tree's root must not equal the bare SHA-256 concatenation
+        // an attacker could compute by confusing leaf and node encodings.
+        let two = vec![
+            ("a.txt".to_string(), b"alpha".to_vec()),
+            ("b.txt".to_string(), b"beta".to_vec()),
+        ];
+        let root = merkle_root(&two);
+        let leaf_a = leaf_digest("a.txt", b"alpha");
+        let leaf_b = leaf_digest("b.txt", b"beta");
+        assert_ne!(root, leaf_a);
+        assert_ne!(root, leaf_b);
+        let mut bare = Sha256::new();
+        bare.update(leaf_a);
+        bare.update(leaf_b);
+        let bare: [u8; 32] = bare.finalize().into();
+        assert_ne!(root, bare);
+    }
+}
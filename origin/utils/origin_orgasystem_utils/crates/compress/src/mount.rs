@@ -0,0 +1,367 @@
+//! Read-only FUSE mount of a seekable `.cpack` archive.
+//!
+//! [`mount_cpack`] exposes a [`crate::seekable`]-format cpack as an ordinary
+//! directory tree - `manifest.json` plus the `data/` files the manifest
+//! describes - without extracting anything to disk up front. Each file's
+//! frame is decompressed and SHA-256-verified (see
+//! [`crate::seekable::extract_file`]) only on its first `read`, then cached
+//! in memory for subsequent reads; directories and `getattr`/`readdir`
+//! never touch the payload at all, since the full file list and sizes come
+//! straight from the seekable trailer (see [`crate::seekable::list_entries`]).
+//!
+//! Requires a cpack produced by [`crate::compress::compress_dpack_seekable`]
+//! (`PAYLOAD_SEEKABLE`) - the other payload formats have no per-file seek
+//! point to read lazily from, so mounting one fails with
+//! [`FrameError::UnsupportedPayloadFormat`] rather than falling back to a
+//! full decompress.
+//!
+//! The mount stays active until the returned [`MountHandle`] is dropped,
+//! which unmounts it (via `fuser`'s own background-session teardown).
+
+use crate::frame::FrameError;
+use crate::seekable::{extract_file, list_entries};
+use fuser::{
+    FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, Request,
+};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// How long the kernel may cache attribute/entry lookups before re-asking -
+/// safe to set high since a mounted cpack's contents never change underneath
+/// the mount.
+const ATTR_TTL: Duration = Duration::from_secs(3600);
+
+/// Inode number of the mount's root directory.
+const ROOT_INO: u64 = 1;
+
+enum NodeKind {
+    Dir { children: Vec<u64> },
+    File { rel_path: String, size: u64 },
+}
+
+struct Node {
+    ino: u64,
+    name: String,
+    parent: u64,
+    kind: NodeKind,
+}
+
+/// In-memory directory tree plus a decompressed-content cache, built once at
+/// mount time from the cpack's manifest and seekable trailer index.
+struct CpackFs {
+    cpack_path: std::path::PathBuf,
+    nodes: HashMap<u64, Node>,
+    /// Inode -> that file's decompressed, hash-verified content, filled the
+    /// first time the file is read so later reads don't call
+    /// [`extract_file`] again.
+    cache: Mutex<HashMap<u64, Vec<u8>>>,
+}
+
+impl CpackFs {
+    fn build(cpack_path: &Path) -> Result<Self, FrameError> {
+        // Validates payload_format == PAYLOAD_SEEKABLE up front, so mounting
+        // a non-seekable cpack fails closed here rather than on the first
+        // lazy `read`.
+        let entries = list_entries(cpack_path)?;
+
+        let mut nodes: HashMap<u64, Node> = HashMap::new();
+        let mut next_ino = ROOT_INO + 1;
+
+        nodes.insert(
+            ROOT_INO,
+            Node {
+                ino: ROOT_INO,
+                name: String::new(),
+                parent: ROOT_INO,
+                kind: NodeKind::Dir {
+                    children: Vec::new(),
+                },
+            },
+        );
+
+        let manifest_ino = next_ino;
+        next_ino += 1;
+        nodes.insert(
+            manifest_ino,
+            Node {
+                ino: manifest_ino,
+                name: "manifest.json".to_string(),
+                parent: ROOT_INO,
+                kind: NodeKind::File {
+                    rel_path: String::new(), // sentinel: manifest.json, not a data/ entry
+                    size: 0,
+                },
+            },
+        );
+        if let NodeKind::Dir { children } = &mut nodes.get_mut(&ROOT_INO).unwrap().kind {
+            children.push(manifest_ino);
+        }
+
+        let data_ino = next_ino;
+        next_ino += 1;
+        nodes.insert(
+            data_ino,
+            Node {
+                ino: data_ino,
+                name: "data".to_string(),
+                parent: ROOT_INO,
+                kind: NodeKind::Dir {
+                    children: Vec::new(),
+                },
+            },
+        );
+        if let NodeKind::Dir { children } = &mut nodes.get_mut(&ROOT_INO).unwrap().kind {
+            children.push(data_ino);
+        }
+
+        for (rel_path, size, _sha256) in &entries {
+            let mut parent_ino = data_ino;
+            let components: Vec<&str> = rel_path.split('/').collect();
+            for (i, component) in components.iter().enumerate() {
+                let is_leaf = i == components.len() - 1;
+                let existing = match &nodes.get(&parent_ino).unwrap().kind {
+                    NodeKind::Dir { children } => children
+                        .iter()
+                        .copied()
+                        .find(|&ino| nodes[&ino].name == *component),
+                    NodeKind::File { .. } => None,
+                };
+                let child_ino = if let Some(ino) = existing {
+                    ino
+                } else {
+                    let ino = next_ino;
+                    next_ino += 1;
+                    let kind = if is_leaf {
+                        NodeKind::File {
+                            rel_path: rel_path.clone(),
+                            size: *size,
+                        }
+                    } else {
+                        NodeKind::Dir {
+                            children: Vec::new(),
+                        }
+                    };
+                    nodes.insert(
+                        ino,
+                        Node {
+                            ino,
+                            name: component.to_string(),
+                            parent: parent_ino,
+                            kind,
+                        },
+                    );
+                    if let NodeKind::Dir { children } =
+                        &mut nodes.get_mut(&parent_ino).unwrap().kind
+                    {
+                        children.push(ino);
+                    }
+                    ino
+                };
+                parent_ino = child_ino;
+            }
+        }
+
+        Ok(Self {
+            cpack_path: cpack_path.to_path_buf(),
+            nodes,
+            cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn attr_for(&self, node: &Node) -> FileAttr {
+        let (kind, size) = match &node.kind {
+            NodeKind::Dir { .. } => (FileType::Directory, 0),
+            NodeKind::File { size, .. } => (FileType::RegularFile, *size),
+        };
+        let perm = match kind {
+            FileType::Directory => 0o555,
+            _ => 0o444,
+        };
+        FileAttr {
+            ino: node.ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: UNIX_EPOCH,
+            mtime: UNIX_EPOCH,
+            ctime: UNIX_EPOCH,
+            crtime: UNIX_EPOCH,
+            kind,
+            perm,
+            nlink: 1,
+            uid: 0,
+            gid: 0,
+            rdev: 0,
+            blksize: 4096,
+            flags: 0,
+        }
+    }
+
+    /// Lazily decompress and verify `ino`'s content, caching the result.
+    fn content_for(&self, ino: u64) -> Result<Vec<u8>, FrameError> {
+        if let Some(cached) = self.cache.lock().unwrap().get(&ino) {
+            return Ok(cached.clone());
+        }
+        let node = self
+            .nodes
+            .get(&ino)
+            .ok_or_else(|| FrameError::EntryNotFound {
+                rel_path: format!("inode {ino}"),
+            })?;
+        let content = match &node.kind {
+            NodeKind::File { rel_path, .. } if rel_path.is_empty() => {
+                // manifest.json: read via a full decode since it isn't part
+                // of the per-file frame index.
+                let (manifest_json, _) =
+                    crate::seekable::decode_seekable_payload(&read_seekable_body(
+                        &self.cpack_path,
+                    )?)?;
+                manifest_json
+            }
+            NodeKind::File { rel_path, .. } => extract_file(&self.cpack_path, rel_path)?,
+            NodeKind::Dir { .. } => {
+                return Err(FrameError::EntryNotFound {
+                    rel_path: node.name.clone(),
+                })
+            }
+        };
+        self.cache.lock().unwrap().insert(ino, content.clone());
+        Ok(content)
+    }
+}
+
+/// Read a seekable cpack's payload body (post-header, pre-decompression-of-
+/// frames - [`decode_seekable_payload`](crate::seekable::decode_seekable_payload)
+/// still does the per-file decompression itself) so `manifest.json` can be
+/// recovered without threading a second codepath through [`extract_file`],
+/// which only knows how to resolve `data/`-tree paths.
+fn read_seekable_body(cpack_path: &Path) -> Result<Vec<u8>, FrameError> {
+    use crate::frame::{CpackHeader, HEADER_SIZE};
+    let data = std::fs::read(cpack_path)?;
+    if data.len() < HEADER_SIZE {
+        return Err(FrameError::HeaderTooShort {
+            got: data.len(),
+            need: HEADER_SIZE,
+        });
+    }
+    let header = CpackHeader::from_bytes(&data)?;
+    let body = &data[HEADER_SIZE..];
+    if body.len() != header.compressed_size as usize {
+        return Err(FrameError::PayloadTruncated);
+    }
+    Ok(body.to_vec())
+}
+
+impl Filesystem for CpackFs {
+    fn lookup(&mut self, _req: &Request, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let Some(parent_node) = self.nodes.get(&parent) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        let NodeKind::Dir { children } = &parent_node.kind else {
+            reply.error(libc::ENOTDIR);
+            return;
+        };
+        let name = name.to_string_lossy();
+        match children
+            .iter()
+            .copied()
+            .find(|&ino| self.nodes[&ino].name == name)
+        {
+            Some(ino) => reply.entry(&ATTR_TTL, &self.attr_for(&self.nodes[&ino]), 0),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn getattr(&mut self, _req: &Request, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.nodes.get(&ino) {
+            Some(node) => reply.attr(&ATTR_TTL, &self.attr_for(node)),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        match self.content_for(ino) {
+            Ok(content) => {
+                let offset = offset.max(0) as usize;
+                if offset >= content.len() {
+                    reply.data(&[]);
+                    return;
+                }
+                let end = (offset + size as usize).min(content.len());
+                reply.data(&content[offset..end]);
+            }
+            Err(_) => reply.error(libc::EIO),
+        }
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let Some(node) = self.nodes.get(&ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        let NodeKind::Dir { children } = &node.kind else {
+            reply.error(libc::ENOTDIR);
+            return;
+        };
+
+        let mut entries: Vec<(u64, FileType, String)> = vec![
+            (ino, FileType::Directory, ".".to_string()),
+            (node.parent, FileType::Directory, "..".to_string()),
+        ];
+        for &child_ino in children {
+            let child = &self.nodes[&child_ino];
+            let kind = match child.kind {
+                NodeKind::Dir { .. } => FileType::Directory,
+                NodeKind::File { .. } => FileType::RegularFile,
+            };
+            entries.push((child_ino, kind, child.name.clone()));
+        }
+
+        for (i, (entry_ino, kind, name)) in entries.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(entry_ino, (i + 1) as i64, kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+}
+
+/// A live FUSE mount of a `.cpack` file. The mount is active for as long as
+/// this handle is alive and is torn down when it's dropped.
+pub struct MountHandle {
+    _session: fuser::BackgroundSession,
+}
+
+/// Mount `cpack_path` (which must be a [`crate::compress::compress_dpack_seekable`]
+/// output) read-only at `mountpoint`, returning a handle that keeps the
+/// mount alive until dropped.
+pub fn mount_cpack(cpack_path: &Path, mountpoint: &Path) -> Result<MountHandle, FrameError> {
+    let fs = CpackFs::build(cpack_path)?;
+    let options = [
+        fuser::MountOption::RO,
+        fuser::MountOption::FSName("cpack".to_string()),
+    ];
+    let session = fuser::spawn_mount2(fs, mountpoint, &options)
+        .map_err(|e| FrameError::Io(std::io::Error::other(e)))?;
+    Ok(MountHandle { _session: session })
+}
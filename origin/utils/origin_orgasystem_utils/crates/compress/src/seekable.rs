@@ -0,0 +1,394 @@
+//! Seekable CPACK payload: each file compressed as its own independent zstd
+//! frame with a trailer index, so extracting one file from a large pack
+//! never requires decompressing the rest of it.
+//!
+//! Payload layout (written directly after the `CpackHeader`, with no further
+//! top-level compression pass - wrapping a second zstd frame around already-
+//! independent frames would defeat the point):
+//!   u64 LE: trailer offset, relative to the start of this section
+//!   u32 LE: manifest JSON length
+//!   bytes:  manifest JSON
+//!   Frames section: each file's content, sorted by path, compressed as its
+//!     own independent zstd frame (see [`crate::codec::compress_payload`]),
+//!     concatenated back-to-back
+//!   Trailer, at the offset above:
+//!     u32 LE: entry count
+//!     For each entry (same sorted order as the frames section):
+//!       u32 LE + bytes: relative path
+//!       u64 LE: frame offset (relative to the start of the frames section)
+//!       u64 LE: frame length (compressed bytes)
+//!       u64 LE: uncompressed length
+//!       32 bytes: SHA-256 of the uncompressed content
+
+use crate::codec::{compress_payload, decompress_payload};
+use crate::frame::{
+    sha256_bytes, validate_rel_path, CpackHeader, FrameError, COMPRESS_ZSTD, HEADER_SIZE,
+    PAYLOAD_SEEKABLE,
+};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// One file's location and integrity info inside a seekable payload's trailer.
+struct IndexEntry {
+    rel_path: String,
+    frame_offset: u64,
+    frame_len: u64,
+    uncompressed_len: u64,
+    sha256: [u8; 32],
+}
+
+/// Build a seekable payload from `files` (sorted by path internally), each
+/// compressed at `level` as its own independent zstd frame.
+pub fn encode_seekable_payload(
+    manifest_json: &[u8],
+    files: &[(String, Vec<u8>)],
+    level: i32,
+) -> Result<Vec<u8>, FrameError> {
+    let mut sorted: Vec<&(String, Vec<u8>)> = files.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut frames = Vec::new();
+    let mut entries = Vec::with_capacity(sorted.len());
+    for (path, content) in &sorted {
+        let frame = compress_payload(content, COMPRESS_ZSTD, level)?;
+        entries.push(IndexEntry {
+            rel_path: path.clone(),
+            frame_offset: frames.len() as u64,
+            frame_len: frame.len() as u64,
+            uncompressed_len: content.len() as u64,
+            sha256: sha256_bytes(content),
+        });
+        frames.extend_from_slice(&frame);
+    }
+
+    let mut trailer = Vec::new();
+    trailer.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+    for entry in &entries {
+        let plen = entry.rel_path.len() as u32;
+        trailer.extend_from_slice(&plen.to_le_bytes());
+        trailer.extend_from_slice(entry.rel_path.as_bytes());
+        trailer.extend_from_slice(&entry.frame_offset.to_le_bytes());
+        trailer.extend_from_slice(&entry.frame_len.to_le_bytes());
+        trailer.extend_from_slice(&entry.uncompressed_len.to_le_bytes());
+        trailer.extend_from_slice(&entry.sha256);
+    }
+
+    let mlen = manifest_json.len() as u32;
+    let prefix_len = 8 + 4 + manifest_json.len();
+    let trailer_offset = (prefix_len + frames.len()) as u64;
+
+    let mut buf = Vec::with_capacity(prefix_len + frames.len() + trailer.len());
+    buf.extend_from_slice(&trailer_offset.to_le_bytes());
+    buf.extend_from_slice(&mlen.to_le_bytes());
+    buf.extend_from_slice(manifest_json);
+    buf.extend_from_slice(&frames);
+    buf.extend_from_slice(&trailer);
+    Ok(buf)
+}
+
+/// Decode an entire seekable payload, decompressing every frame in order -
+/// used by [`crate::decompress::decompress_cpack_seekable`] for a full
+/// extract, where [`extract_file`]'s single-frame seek wouldn't help anyway.
+pub fn decode_seekable_payload(data: &[u8]) -> Result<(Vec<u8>, Vec<(String, Vec<u8>)>), FrameError> {
+    if data.len() < 12 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let trailer_offset = u64::from_le_bytes(data[0..8].try_into().unwrap()) as usize;
+    let mlen = u32::from_le_bytes(data[8..12].try_into().unwrap()) as usize;
+    let mut pos = 12;
+    if data.len() < pos + mlen {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let manifest_json = data[pos..pos + mlen].to_vec();
+    pos += mlen;
+    let frames_start = pos;
+
+    if data.len() < trailer_offset {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let entries = parse_trailer(&data[trailer_offset..])?;
+
+    let mut files = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let start = frames_start + entry.frame_offset as usize;
+        let end = start + entry.frame_len as usize;
+        if data.len() < end {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let content = decompress_payload(&data[start..end], COMPRESS_ZSTD)?;
+        if sha256_bytes(&content) != entry.sha256 {
+            return Err(FrameError::IntegrityMismatch);
+        }
+        files.push((entry.rel_path.clone(), content));
+    }
+    Ok((manifest_json, files))
+}
+
+fn parse_trailer(data: &[u8]) -> Result<Vec<IndexEntry>, FrameError> {
+    if data.len() < 4 {
+        return Err(FrameError::PayloadTruncated);
+    }
+    let count = u32::from_le_bytes(data[0..4].try_into().unwrap()) as usize;
+    let mut pos = 4;
+
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if data.len() < pos + 4 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let plen = u32::from_le_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        pos += 4;
+        if data.len() < pos + plen {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let rel_path = String::from_utf8(data[pos..pos + plen].to_vec())
+            .map_err(|_| FrameError::InvalidUtf8Path)?;
+        validate_rel_path(&rel_path)?;
+        pos += plen;
+
+        if data.len() < pos + 24 + 32 {
+            return Err(FrameError::PayloadTruncated);
+        }
+        let frame_offset = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        pos += 8;
+        let frame_len = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        pos += 8;
+        let uncompressed_len = u64::from_le_bytes(data[pos..pos + 8].try_into().unwrap());
+        pos += 8;
+        let mut sha256 = [0u8; 32];
+        sha256.copy_from_slice(&data[pos..pos + 32]);
+        pos += 32;
+
+        entries.push(IndexEntry {
+            rel_path,
+            frame_offset,
+            frame_len,
+            uncompressed_len,
+            sha256,
+        });
+    }
+    Ok(entries)
+}
+
+/// Read just the header and trailer of a seekable `.cpack` at `cpack_path`
+/// and return each entry's path, uncompressed length, and SHA-256, without
+/// decompressing any file content.
+pub fn list_entries(cpack_path: &Path) -> Result<Vec<(String, u64, [u8; 32])>, FrameError> {
+    let mut file = std::fs::File::open(cpack_path)?;
+    let header = read_header(&mut file)?;
+    if header.payload_format != PAYLOAD_SEEKABLE {
+        return Err(FrameError::UnsupportedPayloadFormat(header.payload_format));
+    }
+
+    let trailer = read_trailer(&mut file)?;
+    let entries = parse_trailer(&trailer)?;
+    Ok(entries
+        .into_iter()
+        .map(|e| (e.rel_path, e.uncompressed_len, e.sha256))
+        .collect())
+}
+
+/// Extract a single file from a seekable `.cpack` at `cpack_path` by seeking
+/// straight to its frame and decompressing only that frame, verifying its
+/// SHA-256 against the trailer entry before returning.
+pub fn extract_file(cpack_path: &Path, rel_path: &str) -> Result<Vec<u8>, FrameError> {
+    let mut file = std::fs::File::open(cpack_path)?;
+    let header = read_header(&mut file)?;
+    if header.payload_format != PAYLOAD_SEEKABLE {
+        return Err(FrameError::UnsupportedPayloadFormat(header.payload_format));
+    }
+
+    file.seek(SeekFrom::Start(HEADER_SIZE as u64))?;
+    let mut prefix = [0u8; 12];
+    file.read_exact(&mut prefix)?;
+    let mlen = u32::from_le_bytes(prefix[8..12].try_into().unwrap()) as u64;
+    let frames_start = HEADER_SIZE as u64 + 12 + mlen;
+
+    let trailer = read_trailer(&mut file)?;
+    let entries = parse_trailer(&trailer)?;
+    let entry = entries
+        .iter()
+        .find(|e| e.rel_path == rel_path)
+        .ok_or_else(|| FrameError::EntryNotFound {
+            rel_path: rel_path.to_string(),
+        })?;
+
+    file.seek(SeekFrom::Start(frames_start + entry.frame_offset))?;
+    let mut frame_bytes = vec![0u8; entry.frame_len as usize];
+    file.read_exact(&mut frame_bytes)?;
+
+    let content = decompress_payload(&frame_bytes, COMPRESS_ZSTD)?;
+    if sha256_bytes(&content) != entry.sha256 {
+        return Err(FrameError::IntegrityMismatch);
+    }
+    Ok(content)
+}
+
+fn read_header(file: &mut std::fs::File) -> Result<CpackHeader, FrameError> {
+    file.seek(SeekFrom::Start(0))?;
+    let mut header_buf = vec![0u8; HEADER_SIZE];
+    file.read_exact(&mut header_buf)?;
+    CpackHeader::from_bytes(&header_buf)
+}
+
+/// Read the 8-byte trailer offset right after the header, then the trailer
+/// bytes it points to, through to end of file.
+fn read_trailer(file: &mut std::fs::File) -> Result<Vec<u8>, FrameError> {
+    file.seek(SeekFrom::Start(HEADER_SIZE as u64))?;
+    let mut trailer_offset_buf = [0u8; 8];
+    file.read_exact(&mut trailer_offset_buf)?;
+    let trailer_offset = u64::from_le_bytes(trailer_offset_buf);
+
+    file.seek(SeekFrom::Start(HEADER_SIZE as u64 + trailer_offset))?;
+    let mut trailer = Vec::new();
+    file.read_to_end(&mut trailer)?;
+    Ok(trailer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files() -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("a.txt".to_string(), b"alpha content".to_vec()),
+            ("b/c.txt".to_string(), b"beta content, a bit longer".to_vec()),
+            ("z.txt".to_string(), b"zulu".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn test_encode_decode_seekable_payload_roundtrip() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let payload = encode_seekable_payload(manifest, &files(), 3).unwrap();
+        let (dec_manifest, dec_files) = decode_seekable_payload(&payload).unwrap();
+        assert_eq!(dec_manifest, manifest);
+        assert_eq!(dec_files, files());
+    }
+
+    #[test]
+    fn test_decode_seekable_payload_rejects_path_traversal() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let malicious = vec![("../../etc/passwd".to_string(), b"evil".to_vec())];
+        let body = encode_seekable_payload(manifest, &malicious, 3).unwrap();
+        assert!(matches!(
+            decode_seekable_payload(&body),
+            Err(FrameError::UnsafeRelPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_list_entries_and_extract_file_against_a_real_cpack() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let body = encode_seekable_payload(manifest, &files(), 3).unwrap();
+
+        let header = CpackHeader {
+            version: crate::frame::CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_SEEKABLE,
+            encryption_method: crate::crypto::ENCRYPT_NONE,
+            payload_sha256: [0u8; 32],
+            compressed_size: body.len() as u64,
+            dictionary_mode: crate::frame::DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        };
+        let mut out = header.to_bytes();
+        out.extend_from_slice(&body);
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), &out).unwrap();
+
+        let listed = list_entries(tmp.path()).unwrap();
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[0].0, "a.txt");
+
+        for (path, content) in files() {
+            let extracted = extract_file(tmp.path(), &path).unwrap();
+            assert_eq!(extracted, content);
+        }
+    }
+
+    #[test]
+    fn test_list_entries_rejects_path_traversal_in_trailer() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let malicious = vec![("../../etc/passwd".to_string(), b"evil".to_vec())];
+        let body = encode_seekable_payload(manifest, &malicious, 3).unwrap();
+
+        let header = CpackHeader {
+            version: crate::frame::CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_SEEKABLE,
+            encryption_method: crate::crypto::ENCRYPT_NONE,
+            payload_sha256: [0u8; 32],
+            compressed_size: body.len() as u64,
+            dictionary_mode: crate::frame::DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        };
+        let mut out = header.to_bytes();
+        out.extend_from_slice(&body);
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), &out).unwrap();
+
+        assert!(matches!(
+            list_entries(tmp.path()),
+            Err(FrameError::UnsafeRelPath(_))
+        ));
+    }
+
+    #[test]
+    fn test_extract_file_rejects_unknown_path() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let body = encode_seekable_payload(manifest, &files(), 3).unwrap();
+        let header = CpackHeader {
+            version: crate::frame::CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_SEEKABLE,
+            encryption_method: crate::crypto::ENCRYPT_NONE,
+            payload_sha256: [0u8; 32],
+            compressed_size: body.len() as u64,
+            dictionary_mode: crate::frame::DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        };
+        let mut out = header.to_bytes();
+        out.extend_from_slice(&body);
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), &out).unwrap();
+
+        let err = extract_file(tmp.path(), "missing.txt").unwrap_err();
+        assert!(matches!(err, FrameError::EntryNotFound { .. }));
+    }
+
+    #[test]
+    fn test_extract_file_detects_tampered_frame() {
+        let manifest = b"{\"version\":\"1.0\"}";
+        let body = encode_seekable_payload(manifest, &files(), 3).unwrap();
+        let header = CpackHeader {
+            version: crate::frame::CPACK_VERSION,
+            compression_method: COMPRESS_ZSTD,
+            payload_format: PAYLOAD_SEEKABLE,
+            encryption_method: crate::crypto::ENCRYPT_NONE,
+            payload_sha256: [0u8; 32],
+            compressed_size: body.len() as u64,
+            dictionary_mode: crate::frame::DICT_MODE_NONE,
+            merkle_root: [0u8; 32],
+        };
+        let mut out = header.to_bytes();
+        out.extend_from_slice(&body);
+
+        // Flip the last byte of the frames section, which belongs to z.txt's
+        // frame (the last file in sorted order), so extracting z.txt either
+        // fails to decompress or yields content whose hash no longer matches
+        // the trailer.
+        let trailer_offset = u64::from_le_bytes(body[0..8].try_into().unwrap()) as usize;
+        let tamper_at = HEADER_SIZE + trailer_offset - 1;
+        out[tamper_at] ^= 0xFF;
+
+        let tmp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(tmp.path(), &out).unwrap();
+
+        let result = extract_file(tmp.path(), "z.txt");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Canonical byte encoding: a stable, self-describing byte format for
+//! content-addressing structures (manifests, receipts) independent of
+//! serde/JSON formatting, field order, or whitespace.
+//!
+//! Every value is type-tagged and, where applicable, length-prefixed:
+//!   - null: tag byte only
+//!   - bool: tag byte + 1-byte payload (0 or 1)
+//!   - uint: tag byte + 8-byte big-endian payload
+//!   - string: tag byte + 4-byte big-endian length + UTF-8 bytes
+//!   - seq: tag byte + 4-byte big-endian count, then each element's
+//!     canonical encoding back to back
+//!   - map: tag byte + 4-byte big-endian count, then each (key, value)
+//!     pair with keys in raw byte order
+//!
+//! Callers build a structure's encoding field by field with
+//! [`CanonicalWriter::field`], which writes the field name as a canonical
+//! string ahead of its value, so the output commits to field *identity*,
+//! not just a flat tuple of values two differently-shaped structs could
+//! collide on.
+
+use std::collections::BTreeMap;
+
+const TAG_NULL: u8 = 0;
+const TAG_BOOL: u8 = 1;
+const TAG_UINT: u8 = 2;
+const TAG_STRING: u8 = 3;
+const TAG_SEQ: u8 = 4;
+const TAG_MAP: u8 = 5;
+
+/// Accumulates a canonical byte encoding.
+#[derive(Debug, Default)]
+pub struct CanonicalWriter {
+    buf: Vec<u8>,
+}
+
+impl CanonicalWriter {
+    /// Start a new, empty encoding.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consume the writer, returning the accumulated bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    /// Write a named field: the name (as a canonical string) followed by
+    /// its value.
+    pub fn field(&mut self, name: &str, write: impl FnOnce(&mut Self)) -> &mut Self {
+        self.string(name);
+        write(self);
+        self
+    }
+
+    pub fn null(&mut self) -> &mut Self {
+        self.buf.push(TAG_NULL);
+        self
+    }
+
+    pub fn bool(&mut self, v: bool) -> &mut Self {
+        self.buf.push(TAG_BOOL);
+        self.buf.push(v as u8);
+        self
+    }
+
+    pub fn uint(&mut self, v: u64) -> &mut Self {
+        self.buf.push(TAG_UINT);
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub fn string(&mut self, v: &str) -> &mut Self {
+        self.buf.push(TAG_STRING);
+        self.buf.extend_from_slice(&(v.len() as u32).to_be_bytes());
+        self.buf.extend_from_slice(v.as_bytes());
+        self
+    }
+
+    /// `Some(s)` as a string, `None` as null.
+    pub fn option_string(&mut self, v: Option<&str>) -> &mut Self {
+        match v {
+            Some(s) => self.string(s),
+            None => self.null(),
+        }
+    }
+
+    /// A sequence: `write_item` encodes each element in order.
+    pub fn seq<T>(&mut self, items: &[T], mut write_item: impl FnMut(&mut Self, &T)) -> &mut Self {
+        self.buf.push(TAG_SEQ);
+        self.buf
+            .extend_from_slice(&(items.len() as u32).to_be_bytes());
+        for item in items {
+            write_item(self, item);
+        }
+        self
+    }
+
+    /// A map, with entries in raw key byte order. A `BTreeMap<String, _>`
+    /// is already iterated this way (`String`'s `Ord` is byte order on
+    /// UTF-8), so this just walks it in iteration order.
+    pub fn map<V>(
+        &mut self,
+        entries: &BTreeMap<String, V>,
+        mut write_value: impl FnMut(&mut Self, &V),
+    ) -> &mut Self {
+        self.buf.push(TAG_MAP);
+        self.buf
+            .extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for (k, v) in entries {
+            self.string(k);
+            write_value(self, v);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_encoding_is_tagged_and_length_prefixed() {
+        let mut w = CanonicalWriter::new();
+        w.string("hi");
+        let bytes = w.into_bytes();
+        assert_eq!(bytes[0], TAG_STRING);
+        assert_eq!(&bytes[1..5], &2u32.to_be_bytes());
+        assert_eq!(&bytes[5..], b"hi");
+    }
+
+    #[test]
+    fn test_uint_encoding_is_fixed_width_big_endian() {
+        let mut w = CanonicalWriter::new();
+        w.uint(300);
+        let bytes = w.into_bytes();
+        assert_eq!(bytes[0], TAG_UINT);
+        assert_eq!(&bytes[1..9], &300u64.to_be_bytes());
+    }
+
+    #[test]
+    fn test_map_keys_in_byte_order() {
+        let mut entries = BTreeMap::new();
+        entries.insert("zebra".to_string(), 1u64);
+        entries.insert("apple".to_string(), 2u64);
+
+        let mut w = CanonicalWriter::new();
+        w.map(&entries, |w, v| {
+            w.uint(*v);
+        });
+        let bytes = w.into_bytes();
+
+        let apple_pos = find_string(&bytes, "apple");
+        let zebra_pos = find_string(&bytes, "zebra");
+        assert!(
+            apple_pos < zebra_pos,
+            "map entries must be sorted by key byte order"
+        );
+    }
+
+    #[test]
+    fn test_distinct_field_names_produce_distinct_encodings() {
+        let mut a = CanonicalWriter::new();
+        a.field("x", |w| {
+            w.uint(1);
+        });
+        let mut b = CanonicalWriter::new();
+        b.field("y", |w| {
+            w.uint(1);
+        });
+        assert_ne!(a.into_bytes(), b.into_bytes());
+    }
+
+    #[test]
+    fn test_encoding_deterministic_across_calls() {
+        let mut entries = BTreeMap::new();
+        entries.insert("a".to_string(), GlossaryLikeInt(1));
+        entries.insert("b".to_string(), GlossaryLikeInt(2));
+
+        let encode = |entries: &BTreeMap<String, GlossaryLikeInt>| {
+            let mut w = CanonicalWriter::new();
+            w.field("version", |w| {
+                w.uint(1);
+            });
+            w.field("entries", |w| {
+                w.map(entries, |w, v| {
+                    w.uint(v.0);
+                });
+            });
+            w.into_bytes()
+        };
+
+        assert_eq!(encode(&entries), encode(&entries));
+    }
+
+    struct GlossaryLikeInt(u64);
+
+    fn find_string(haystack: &[u8], needle: &str) -> usize {
+        haystack
+            .windows(needle.len())
+            .position(|w| w == needle.as_bytes())
+            .unwrap()
+    }
+}
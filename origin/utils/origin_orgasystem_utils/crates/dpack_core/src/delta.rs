@@ -0,0 +1,213 @@
+//! Incremental delta packs: a small DPACK carrying only the files that
+//! changed relative to a base manifest, plus a removed-paths list.
+//!
+//! A delta is only meaningful against the exact base it was computed from:
+//! [`DeltaManifest`] pins the base's `pack_hash` and seed fingerprint, and
+//! [`apply_delta`] fails closed if the base supplied at unfurl time doesn't
+//! match, or if the reconstructed tree doesn't hash to the delta's
+//! advertised `full_pack_hash`.
+
+use crate::manifest::{DpackManifest, FileEntry};
+use crate::pack::PackError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The delta between a base `DpackManifest` and a later full snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaManifest {
+    pub schema_version: String,
+    pub root_2i_seed_fingerprint: String,
+    pub created_at: String,
+    pub source_root: String,
+    /// `pack_hash` of the base manifest this delta was computed against.
+    pub base_pack_hash: String,
+    /// Seed fingerprint the base was bound to.
+    pub base_root_2i_seed_fingerprint: String,
+    /// `pack_hash` of the full tree this delta reconstructs to once merged
+    /// onto the base. Asserted by [`apply_delta`] after merging.
+    pub full_pack_hash: String,
+    /// Files that are new, or whose [`FileEntry`] (hash, size, chunks, kind,
+    /// mode or xattrs) differs from the base.
+    pub added_or_changed: BTreeMap<String, FileEntry>,
+    /// Paths present in the base but absent from the full tree.
+    pub removed: Vec<String>,
+}
+
+/// Diff `full` against `base`, producing the delta that reconstructs `full`
+/// when applied to `base` via [`apply_delta`].
+pub fn diff_manifests(base: &DpackManifest, full: &DpackManifest) -> DeltaManifest {
+    let mut added_or_changed = BTreeMap::new();
+    for (path, entry) in &full.files {
+        match base.files.get(path) {
+            Some(base_entry) if base_entry == entry => {}
+            _ => {
+                added_or_changed.insert(path.clone(), entry.clone());
+            }
+        }
+    }
+
+    let removed: Vec<String> = base
+        .files
+        .keys()
+        .filter(|path| !full.files.contains_key(*path))
+        .cloned()
+        .collect();
+
+    DeltaManifest {
+        schema_version: "1.0".to_string(),
+        root_2i_seed_fingerprint: full.root_2i_seed_fingerprint.clone(),
+        created_at: full.created_at.clone(),
+        source_root: full.source_root.clone(),
+        base_pack_hash: base.pack_hash.clone(),
+        base_root_2i_seed_fingerprint: base.root_2i_seed_fingerprint.clone(),
+        full_pack_hash: full.pack_hash.clone(),
+        added_or_changed,
+        removed,
+    }
+}
+
+/// Reconstruct the full manifest by applying `delta` to `base`. Fails
+/// closed if `base` isn't the exact manifest the delta was computed
+/// against, or if the merged result doesn't hash to `delta.full_pack_hash`.
+pub fn apply_delta(base: &DpackManifest, delta: &DeltaManifest) -> Result<DpackManifest, PackError> {
+    if base.pack_hash != delta.base_pack_hash {
+        return Err(PackError::VerificationFailed {
+            reason: format!(
+                "base pack_hash mismatch: delta expects {}, supplied base has {}",
+                short(&delta.base_pack_hash),
+                short(&base.pack_hash)
+            ),
+        });
+    }
+    if base.root_2i_seed_fingerprint != delta.base_root_2i_seed_fingerprint {
+        return Err(PackError::VerificationFailed {
+            reason: "base seed fingerprint does not match the delta's recorded base".to_string(),
+        });
+    }
+
+    let mut files = base.files.clone();
+    for path in &delta.removed {
+        files.remove(path);
+    }
+    for (path, entry) in &delta.added_or_changed {
+        files.insert(path.clone(), entry.clone());
+    }
+
+    let pack_hash = DpackManifest::compute_pack_hash(&files, &base.hash_scheme);
+    if pack_hash != delta.full_pack_hash {
+        return Err(PackError::VerificationFailed {
+            reason: format!(
+                "reconstructed pack_hash {} does not match delta's advertised full_pack_hash {}",
+                short(&pack_hash),
+                short(&delta.full_pack_hash)
+            ),
+        });
+    }
+
+    Ok(DpackManifest {
+        schema_version: delta.schema_version.clone(),
+        root_2i_seed_fingerprint: delta.root_2i_seed_fingerprint.clone(),
+        created_at: delta.created_at.clone(),
+        source_root: delta.source_root.clone(),
+        files,
+        pack_hash,
+        vcs: None,
+        hash_scheme: base.hash_scheme,
+    })
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..16.min(hash.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(files: BTreeMap<String, FileEntry>, seed_fp: &str) -> DpackManifest {
+        let hash_scheme = seed_core::hash::HashScheme::default();
+        let pack_hash = DpackManifest::compute_pack_hash(&files, &hash_scheme);
+        DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: seed_fp.to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/repo".to_string(),
+            files,
+            pack_hash,
+            vcs: None,
+            hash_scheme,
+        }
+    }
+
+    #[test]
+    fn test_diff_finds_added_changed_and_removed() {
+        let mut base_files = BTreeMap::new();
+        base_files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        base_files.insert("b.txt".to_string(), FileEntry::new("bbb".to_string(), 3));
+        let base = manifest(base_files, "fp1");
+
+        let mut full_files = BTreeMap::new();
+        full_files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3)); // unchanged
+        full_files.insert("b.txt".to_string(), FileEntry::new("bbb2".to_string(), 4)); // changed
+        full_files.insert("c.txt".to_string(), FileEntry::new("ccc".to_string(), 3)); // added
+        let full = manifest(full_files, "fp1");
+
+        let delta = diff_manifests(&base, &full);
+        assert_eq!(delta.added_or_changed.len(), 2);
+        assert!(delta.added_or_changed.contains_key("b.txt"));
+        assert!(delta.added_or_changed.contains_key("c.txt"));
+        assert!(delta.removed.is_empty());
+    }
+
+    #[test]
+    fn test_apply_delta_roundtrips_to_full_manifest() {
+        let mut base_files = BTreeMap::new();
+        base_files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        base_files.insert("b.txt".to_string(), FileEntry::new("bbb".to_string(), 3));
+        let base = manifest(base_files, "fp1");
+
+        let mut full_files = BTreeMap::new();
+        full_files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        full_files.insert("c.txt".to_string(), FileEntry::new("ccc".to_string(), 3));
+        let full = manifest(full_files.clone(), "fp1");
+
+        let delta = diff_manifests(&base, &full);
+        let reconstructed = apply_delta(&base, &delta).unwrap();
+        assert_eq!(reconstructed.files, full_files);
+        assert_eq!(reconstructed.pack_hash, full.pack_hash);
+    }
+
+    #[test]
+    fn test_apply_delta_rejects_wrong_base() {
+        let mut base_files = BTreeMap::new();
+        base_files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        let base = manifest(base_files, "fp1");
+
+        let mut full_files = BTreeMap::new();
+        full_files.insert("a.txt".to_string(), FileEntry::new("aaa2".to_string(), 4));
+        let full = manifest(full_files, "fp1");
+
+        let delta = diff_manifests(&base, &full);
+
+        let mut wrong_base_files = BTreeMap::new();
+        wrong_base_files.insert("a.txt".to_string(), FileEntry::new("zzz".to_string(), 99));
+        let wrong_base = manifest(wrong_base_files, "fp1");
+
+        let err = apply_delta(&wrong_base, &delta).unwrap_err();
+        assert!(matches!(err, PackError::VerificationFailed { .. }));
+    }
+
+    #[test]
+    fn test_apply_delta_rejects_wrong_seed_fingerprint() {
+        let mut base_files = BTreeMap::new();
+        base_files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        let base = manifest(base_files.clone(), "fp1");
+
+        let full = manifest(base_files, "fp1");
+        let mut delta = diff_manifests(&base, &full);
+        delta.base_root_2i_seed_fingerprint = "different_fp".to_string();
+
+        let err = apply_delta(&base, &delta).unwrap_err();
+        assert!(matches!(err, PackError::VerificationFailed { .. }));
+    }
+}
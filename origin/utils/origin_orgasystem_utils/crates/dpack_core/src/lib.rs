@@ -3,12 +3,25 @@
 //! A DPACK is a snapshot envelope that captures file paths, content hashes,
 //! and seed binding. It preserves paths verbatim and restores identical shape.
 
+pub mod canonical;
+pub mod delta;
+pub mod lock;
 pub mod manifest;
 pub mod pack;
 pub mod policy;
 pub mod receipt;
+pub mod vcs;
 
+pub use canonical::CanonicalWriter;
+pub use delta::{apply_delta, diff_manifests, DeltaManifest};
+pub use lock::{populate_dir_atomic, write_atomic, LockError, OutputLock};
 pub use manifest::{DpackManifest, FileEntry};
-pub use pack::{pack_repo, unfurl_pack, verify_pack};
-pub use policy::Policy;
+pub use pack::{
+    load_base_manifest, materialize_delta_pack, pack_repo, pack_repo_archive, pack_repo_delta,
+    pack_repo_objects, unfurl_pack, unfurl_pack_delta, validate_hex_hash, verify_pack,
+    verify_pack_delta, verify_shape_equivalence_at_commit, verify_shape_equivalence_ignoring,
+    ArchiveFormat,
+};
+pub use policy::{CompiledPolicy, Policy};
 pub use receipt::{AuditReceipt, GateResult, GateStatus};
+pub use vcs::{detect_vcs_provenance, VcsProvenance};
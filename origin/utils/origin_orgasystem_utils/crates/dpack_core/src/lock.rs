@@ -0,0 +1,192 @@
+//! Advisory locking for shared output directories.
+//!
+//! `originctl` commands that write a `.cpack` file or unfurl a DPACK all
+//! take an `--output` path that may be a shared target directory (e.g. a CI
+//! matrix packing several jobs into the same cache dir). Nothing prevented
+//! two concurrent processes from interleaving writes to the same path.
+//! [`OutputLock`] acquires an advisory exclusive lock on a lock file beside
+//! the output before a command touches it, and [`write_atomic`] stages the
+//! new content at a sibling temp path and renames it into place, so a
+//! reader never observes a partially written file.
+
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LockError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+const LOCK_SUFFIX: &str = ".lock";
+const BLOCKING_NOTICE_AFTER: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// An advisory exclusive lock held on `<path>.lock`, released on drop.
+///
+/// Pass `enabled = false` (the CLI's `--no-lock` escape hatch) to skip
+/// locking entirely, e.g. on read-only or lock-incapable network
+/// filesystems; `acquire` then returns an unlocked, no-op guard.
+pub struct OutputLock {
+    _file: Option<File>,
+}
+
+impl OutputLock {
+    /// Acquire an exclusive advisory lock keyed on `target` (the `.cpack`
+    /// file or DPACK/output directory about to be written). Blocks until
+    /// the lock is free, printing "blocking on <path>" to stderr if
+    /// acquisition takes longer than a couple of seconds, rather than
+    /// failing immediately.
+    pub fn acquire(target: &Path, enabled: bool) -> Result<Self, LockError> {
+        if !enabled {
+            return Ok(Self { _file: None });
+        }
+
+        let lock_path = lock_path_for(target);
+        if let Some(parent) = lock_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&lock_path)?;
+
+        let started = Instant::now();
+        let mut warned = false;
+        loop {
+            match fs2::FileExt::try_lock_exclusive(&file) {
+                Ok(()) => break,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    if !warned && started.elapsed() >= BLOCKING_NOTICE_AFTER {
+                        eprintln!("blocking on {}", lock_path.display());
+                        warned = true;
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(Self { _file: Some(file) })
+    }
+}
+
+fn lock_path_for(target: &Path) -> PathBuf {
+    let mut name = target
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(LOCK_SUFFIX);
+    match target.parent() {
+        Some(parent) => parent.join(name),
+        None => PathBuf::from(name),
+    }
+}
+
+/// Write `contents` to `dest` atomically: stage at a sibling temp file in
+/// the same directory as `dest` (so the rename is same-filesystem and
+/// therefore atomic), then rename into place.
+pub fn write_atomic(dest: &Path, contents: &[u8]) -> Result<(), LockError> {
+    let parent = dest.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+    let mut tmp_name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    tmp_name.push(".tmp");
+    let tmp_path = parent.join(tmp_name);
+    fs::write(&tmp_path, contents)?;
+    fs::rename(&tmp_path, dest)?;
+    Ok(())
+}
+
+/// Populate `dest` atomically: `populate` writes into a fresh sibling temp
+/// directory, which is then renamed over `dest`. Any pre-existing `dest` is
+/// removed first so the rename lands cleanly.
+///
+/// Generic over the populate closure's error type so callers can thread
+/// their own crate's error (e.g. `PackError`) straight through; it only
+/// needs a `From<io::Error>` impl, which every `#[from] std::io::Error`
+/// error enum in this workspace already has.
+pub fn populate_dir_atomic<E: From<io::Error>>(
+    dest: &Path,
+    populate: impl FnOnce(&Path) -> std::result::Result<(), E>,
+) -> std::result::Result<(), E> {
+    let parent = dest.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent)?;
+    let mut tmp_name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    tmp_name.push(".tmp");
+    let tmp_path = parent.join(tmp_name);
+    if tmp_path.exists() {
+        fs::remove_dir_all(&tmp_path)?;
+    }
+    fs::create_dir_all(&tmp_path)?;
+
+    populate(&tmp_path)?;
+
+    if dest.exists() {
+        fs::remove_dir_all(dest)?;
+    }
+    fs::rename(&tmp_path, dest)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_path_for_appends_suffix() {
+        let target = Path::new("/tmp/out/origin.cpack");
+        assert_eq!(
+            lock_path_for(target),
+            PathBuf::from("/tmp/out/origin.cpack.lock")
+        );
+    }
+
+    #[test]
+    fn test_disabled_lock_does_not_create_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("origin.cpack");
+        let _lock = OutputLock::acquire(&target, false).unwrap();
+        assert!(!lock_path_for(&target).exists());
+    }
+
+    #[test]
+    fn test_enabled_lock_creates_and_releases_lock_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("origin.cpack");
+        {
+            let _lock = OutputLock::acquire(&target, true).unwrap();
+            assert!(lock_path_for(&target).exists());
+        }
+        // Dropping the guard releases the flock; re-acquiring must not block.
+        let _lock2 = OutputLock::acquire(&target, true).unwrap();
+    }
+
+    #[test]
+    fn test_write_atomic_leaves_no_temp_file_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("out.bin");
+        write_atomic(&dest, b"hello").unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), b"hello");
+        assert!(!dest.with_extension("bin.tmp").exists());
+    }
+
+    #[test]
+    fn test_populate_dir_atomic_replaces_existing_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let dest = dir.path().join("out");
+        fs::create_dir_all(&dest).unwrap();
+        fs::write(dest.join("stale.txt"), b"old").unwrap();
+
+        populate_dir_atomic(&dest, |staging| -> Result<(), io::Error> {
+            fs::write(staging.join("fresh.txt"), b"new")?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(!dest.join("stale.txt").exists());
+        assert_eq!(fs::read(dest.join("fresh.txt")).unwrap(), b"new");
+    }
+}
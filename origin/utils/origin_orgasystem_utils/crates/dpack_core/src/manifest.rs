@@ -1,5 +1,8 @@
 //! DPACK manifest: the index of files, hashes, and metadata in a pack.
 
+use crate::canonical::CanonicalWriter;
+use crate::vcs::VcsProvenance;
+use seed_core::hash::HashScheme;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -16,39 +19,244 @@ pub struct DpackManifest {
     pub source_root: String,
     /// Map of relative path -> file entry.
     pub files: BTreeMap<String, FileEntry>,
-    /// SHA-256 of the sorted concatenation of all file hashes (pack integrity).
+    /// Digest of the canonical encoding of the path -> file-entry map (pack
+    /// integrity), under `hash_scheme`; see [`Self::compute_pack_hash`].
     pub pack_hash: String,
+    /// Git VCS provenance at pack time, if `source_root` was a checkout.
+    #[serde(default)]
+    pub vcs: Option<VcsProvenance>,
+    /// Algorithm + encoding `pack_hash` and every `FileEntry::sha256` in
+    /// `files` are digested with. Defaults to SHA-256 hex so manifests
+    /// written before this field existed still deserialize and verify.
+    #[serde(default)]
+    pub hash_scheme: HashScheme,
+}
+
+/// What kind of filesystem object a [`FileEntry`] represents. Defaults to
+/// `Regular` so manifests written before this field existed still
+/// deserialize as plain files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum EntryKind {
+    #[default]
+    Regular,
+    Directory,
+    /// A symlink; `target` is the raw link target, unresolved.
+    Symlink { target: String },
+    Fifo,
+    CharDevice { major: u32, minor: u32 },
+    BlockDevice { major: u32, minor: u32 },
+}
+
+impl EntryKind {
+    fn write_canonical(&self, w: &mut CanonicalWriter) {
+        match self {
+            EntryKind::Regular => {
+                w.string("regular");
+            }
+            EntryKind::Directory => {
+                w.string("directory");
+            }
+            EntryKind::Symlink { target } => {
+                w.string("symlink");
+                w.string(target);
+            }
+            EntryKind::Fifo => {
+                w.string("fifo");
+            }
+            EntryKind::CharDevice { major, minor } => {
+                w.string("char_device");
+                w.uint(*major as u64);
+                w.uint(*minor as u64);
+            }
+            EntryKind::BlockDevice { major, minor } => {
+                w.string("block_device");
+                w.uint(*major as u64);
+                w.uint(*minor as u64);
+            }
+        }
+    }
 }
 
 /// A single file entry in the manifest.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FileEntry {
-    /// SHA-256 hash of the file contents.
+    /// SHA-256 hash of the file contents. For non-regular entries this is
+    /// the hash of a stand-in byte string (the symlink target, or empty
+    /// for directories/special files) so every entry still participates
+    /// in `pack_hash`.
     pub sha256: String,
-    /// File size in bytes.
+    /// Size in bytes: file contents, symlink target length, or 0 for
+    /// directories/special files.
     pub size: u64,
+    /// Ordered FastCDC chunk hashes covering the file's bytes, for
+    /// formats that store content in a chunk store instead of inline
+    /// (see `compress::chunk`). Empty for whole-file entries.
+    #[serde(default)]
+    pub chunks: Vec<String>,
+    /// What kind of filesystem object this entry restores as.
+    #[serde(default)]
+    pub kind: EntryKind,
+    /// POSIX permission bits (e.g. `0o644`), 0 for manifests written
+    /// before mode capture existed.
+    #[serde(default)]
+    pub mode: u32,
+    /// Extended attribute name -> hex-encoded value, captured at pack
+    /// time. Empty for manifests written before xattr capture existed,
+    /// or on platforms without xattr support.
+    #[serde(default)]
+    pub xattrs: BTreeMap<String, String>,
+}
+
+impl FileEntry {
+    /// A whole-file entry: `sha256` is the hash of the complete contents
+    /// and `size` their length. `chunks` is left empty, `kind` is
+    /// `Regular`, and `mode`/`xattrs` are left at their zero/empty
+    /// defaults.
+    pub fn new(sha256: String, size: u64) -> Self {
+        Self {
+            sha256,
+            size,
+            chunks: Vec::new(),
+            kind: EntryKind::Regular,
+            mode: 0,
+            xattrs: BTreeMap::new(),
+        }
+    }
+
+    /// A full-fidelity entry capturing the entry's type, permission bits,
+    /// and extended attributes, as produced by [`crate::pack::pack_repo`].
+    pub fn with_metadata(
+        sha256: String,
+        size: u64,
+        kind: EntryKind,
+        mode: u32,
+        xattrs: BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            sha256,
+            size,
+            chunks: Vec::new(),
+            kind,
+            mode,
+            xattrs,
+        }
+    }
+
+    fn write_canonical(&self, w: &mut CanonicalWriter) {
+        w.field("sha256", |w| {
+            w.string(&self.sha256);
+        });
+        w.field("size", |w| {
+            w.uint(self.size);
+        });
+        w.field("chunks", |w| {
+            w.seq(&self.chunks, |w, c| {
+                w.string(c);
+            });
+        });
+        w.field("kind", |w| {
+            self.kind.write_canonical(w);
+        });
+        w.field("mode", |w| {
+            w.uint(self.mode as u64);
+        });
+        w.field("xattrs", |w| {
+            w.map(&self.xattrs, |w, v| {
+                w.string(v);
+            });
+        });
+    }
 }
 
 impl DpackManifest {
-    /// Compute the pack_hash from the file entries.
-    /// This is the SHA-256 of all file hashes sorted by path and concatenated.
-    pub fn compute_pack_hash(files: &BTreeMap<String, FileEntry>) -> String {
-        use sha2::{Digest, Sha256};
-        let mut hasher = Sha256::new();
-        // BTreeMap is already sorted by key
+    /// Canonical encoding of just the file entries (see
+    /// [`crate::canonical`]), used by [`Self::compute_pack_hash`].
+    fn canonical_files_bytes(files: &BTreeMap<String, FileEntry>) -> Vec<u8> {
+        let mut w = CanonicalWriter::new();
+        w.map(files, |w, entry| entry.write_canonical(w));
+        w.into_bytes()
+    }
+
+    /// Compute the pack_hash from the file entries, under `scheme`. This
+    /// digests the canonical byte encoding of the path -> file-entry map,
+    /// so the result depends only on each entry's path, hash, size, chunks,
+    /// kind, mode and xattrs, not on any serializer's incidental formatting.
+    pub fn compute_pack_hash(files: &BTreeMap<String, FileEntry>, scheme: &HashScheme) -> String {
+        scheme.digest(&Self::canonical_files_bytes(files))
+    }
+
+    /// Pre-canonical-encoding pack_hash: `path:sha256\n` concatenation,
+    /// sorted by path. Packs written before canonical encoding existed may
+    /// still carry a pack_hash in this format; [`Self::verify_integrity`]
+    /// falls back to it so they stay verifiable without a repack.
+    fn compute_pack_hash_legacy(files: &BTreeMap<String, FileEntry>, scheme: &HashScheme) -> String {
+        let mut buf = Vec::new();
         for (path, entry) in files {
-            hasher.update(path.as_bytes());
-            hasher.update(b":");
-            hasher.update(entry.sha256.as_bytes());
-            hasher.update(b"\n");
+            buf.extend_from_slice(path.as_bytes());
+            buf.extend_from_slice(b":");
+            buf.extend_from_slice(entry.sha256.as_bytes());
+            buf.extend_from_slice(b"\n");
         }
-        hex::encode(hasher.finalize())
+        scheme.digest(&buf)
     }
 
-    /// Verify that the pack_hash matches the file entries.
+    /// Verify that the pack_hash matches the file entries under this
+    /// manifest's recorded `hash_scheme`, accepting either the current
+    /// canonical encoding or the legacy concatenation.
     pub fn verify_integrity(&self) -> bool {
-        let expected = Self::compute_pack_hash(&self.files);
-        self.pack_hash == expected
+        self.pack_hash == Self::compute_pack_hash(&self.files, &self.hash_scheme)
+            || self.pack_hash == Self::compute_pack_hash_legacy(&self.files, &self.hash_scheme)
+    }
+
+    /// Canonical byte encoding of the whole manifest (see
+    /// [`crate::canonical`]), independent of serde/JSON formatting.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut w = CanonicalWriter::new();
+        w.field("schema_version", |w| {
+            w.string(&self.schema_version);
+        });
+        w.field("root_2i_seed_fingerprint", |w| {
+            w.string(&self.root_2i_seed_fingerprint);
+        });
+        w.field("created_at", |w| {
+            w.string(&self.created_at);
+        });
+        w.field("source_root", |w| {
+            w.string(&self.source_root);
+        });
+        w.field("files", |w| {
+            w.map(&self.files, |w, entry| entry.write_canonical(w));
+        });
+        w.field("pack_hash", |w| {
+            w.string(&self.pack_hash);
+        });
+        w.field("vcs", |w| match &self.vcs {
+            Some(vcs) => {
+                // A presence flag ahead of the fields, so `vcs` always
+                // decodes to the same shape (bool + 0 or 3 further values)
+                // regardless of variant.
+                w.bool(true);
+                w.string(&vcs.commit_id);
+                w.string(&vcs.reference);
+                w.bool(vcs.dirty);
+            }
+            None => {
+                w.bool(false);
+            }
+        });
+        w.field("hash_scheme", |w| {
+            w.string(&format!("{:?}", self.hash_scheme.algorithm));
+            w.string(&format!("{:?}", self.hash_scheme.encoding));
+        });
+        w.into_bytes()
+    }
+
+    /// Content-addressed ID for this manifest: `SHA-256(canonical_bytes())`.
+    /// Always SHA-256 regardless of `hash_scheme`, so two semantically
+    /// equal manifests hash identically across machines and serializer
+    /// versions, independent of either one's chosen content-hash scheme.
+    pub fn content_id(&self) -> String {
+        seed_core::compute_sha256(&self.canonical_bytes())
     }
 }
 
@@ -59,36 +267,20 @@ mod tests {
     #[test]
     fn test_pack_hash_deterministic() {
         let mut files = BTreeMap::new();
-        files.insert(
-            "a.txt".to_string(),
-            FileEntry {
-                sha256: "aaa".to_string(),
-                size: 3,
-            },
-        );
-        files.insert(
-            "b.txt".to_string(),
-            FileEntry {
-                sha256: "bbb".to_string(),
-                size: 3,
-            },
-        );
-        let h1 = DpackManifest::compute_pack_hash(&files);
-        let h2 = DpackManifest::compute_pack_hash(&files);
+        files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        files.insert("b.txt".to_string(), FileEntry::new("bbb".to_string(), 3));
+        let scheme = HashScheme::default();
+        let h1 = DpackManifest::compute_pack_hash(&files, &scheme);
+        let h2 = DpackManifest::compute_pack_hash(&files, &scheme);
         assert_eq!(h1, h2);
     }
 
     #[test]
     fn test_manifest_verify_integrity() {
         let mut files = BTreeMap::new();
-        files.insert(
-            "x.rs".to_string(),
-            FileEntry {
-                sha256: "abc123".to_string(),
-                size: 10,
-            },
-        );
-        let pack_hash = DpackManifest::compute_pack_hash(&files);
+        files.insert("x.rs".to_string(), FileEntry::new("abc123".to_string(), 10));
+        let hash_scheme = HashScheme::default();
+        let pack_hash = DpackManifest::compute_pack_hash(&files, &hash_scheme);
         let manifest = DpackManifest {
             schema_version: "1.0".to_string(),
             root_2i_seed_fingerprint: "seed_fp".to_string(),
@@ -96,6 +288,8 @@ mod tests {
             source_root: "/tmp/test".to_string(),
             files,
             pack_hash,
+            vcs: None,
+            hash_scheme,
         };
         assert!(manifest.verify_integrity());
     }
@@ -103,13 +297,7 @@ mod tests {
     #[test]
     fn test_manifest_verify_integrity_tampered() {
         let mut files = BTreeMap::new();
-        files.insert(
-            "x.rs".to_string(),
-            FileEntry {
-                sha256: "abc123".to_string(),
-                size: 10,
-            },
-        );
+        files.insert("x.rs".to_string(), FileEntry::new("abc123".to_string(), 10));
         let manifest = DpackManifest {
             schema_version: "1.0".to_string(),
             root_2i_seed_fingerprint: "seed_fp".to_string(),
@@ -117,7 +305,160 @@ mod tests {
             source_root: "/tmp/test".to_string(),
             files,
             pack_hash: "wrong_hash".to_string(),
+            vcs: None,
+            hash_scheme: HashScheme::default(),
         };
         assert!(!manifest.verify_integrity());
     }
+
+    #[test]
+    fn test_manifest_verify_integrity_with_blake3_scheme() {
+        let mut files = BTreeMap::new();
+        files.insert("x.rs".to_string(), FileEntry::new("abc123".to_string(), 10));
+        let hash_scheme = HashScheme {
+            algorithm: seed_core::hash::HashAlgorithm::Blake3,
+            encoding: seed_core::hash::HashEncoding::Base32,
+        };
+        let pack_hash = DpackManifest::compute_pack_hash(&files, &hash_scheme);
+        let manifest = DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: "seed_fp".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/test".to_string(),
+            files,
+            pack_hash,
+            vcs: None,
+            hash_scheme,
+        };
+        assert!(manifest.verify_integrity());
+    }
+
+    #[test]
+    fn test_manifest_without_hash_scheme_field_deserializes_as_sha256_hex() {
+        let mut files = BTreeMap::new();
+        files.insert("x.rs".to_string(), FileEntry::new("abc123".to_string(), 10));
+        let pack_hash = DpackManifest::compute_pack_hash(&files, &HashScheme::default());
+        let json = serde_json::json!({
+            "schema_version": "1.0",
+            "root_2i_seed_fingerprint": "seed_fp",
+            "created_at": "2025-01-01T00:00:00Z",
+            "source_root": "/tmp/test",
+            "files": files,
+            "pack_hash": pack_hash,
+        });
+        let manifest: DpackManifest = serde_json::from_value(json).unwrap();
+        assert!(manifest.verify_integrity(), "old packs without hash_scheme stay verifiable");
+    }
+
+    fn sample_manifest() -> DpackManifest {
+        let mut files = BTreeMap::new();
+        files.insert("a.txt".to_string(), FileEntry::new("aaa".to_string(), 3));
+        files.insert("b.txt".to_string(), FileEntry::new("bbb".to_string(), 3));
+        let hash_scheme = HashScheme::default();
+        let pack_hash = DpackManifest::compute_pack_hash(&files, &hash_scheme);
+        DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: "seed_fp".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/test".to_string(),
+            files,
+            pack_hash,
+            vcs: None,
+            hash_scheme,
+        }
+    }
+
+    #[test]
+    fn test_canonical_bytes_deterministic() {
+        let manifest = sample_manifest();
+        assert_eq!(manifest.canonical_bytes(), manifest.canonical_bytes());
+    }
+
+    #[test]
+    fn test_canonical_bytes_changes_with_content() {
+        let a = sample_manifest();
+        let mut b = sample_manifest();
+        b.source_root = "/tmp/other".to_string();
+        assert_ne!(a.canonical_bytes(), b.canonical_bytes());
+    }
+
+    #[test]
+    fn test_content_id_stable_and_sha256_shaped() {
+        let manifest = sample_manifest();
+        let id1 = manifest.content_id();
+        let id2 = manifest.content_id();
+        assert_eq!(id1, id2);
+        assert_eq!(id1.len(), 64);
+    }
+
+    #[test]
+    fn test_content_id_independent_of_hash_scheme_choice() {
+        // Two manifests differing only in which hash_scheme they recorded
+        // (but with the same pack_hash string) must still diverge in
+        // canonical_bytes, since hash_scheme is itself an encoded field -
+        // but content_id always hashes with SHA-256 regardless.
+        let mut sha = sample_manifest();
+        let mut blake = sample_manifest();
+        blake.hash_scheme = HashScheme {
+            algorithm: seed_core::hash::HashAlgorithm::Blake3,
+            encoding: seed_core::hash::HashEncoding::Base32,
+        };
+        sha.pack_hash = "same".to_string();
+        blake.pack_hash = "same".to_string();
+
+        assert_eq!(sha.content_id().len(), 64);
+        assert_eq!(blake.content_id().len(), 64);
+        assert_ne!(sha.content_id(), blake.content_id());
+    }
+
+    #[test]
+    fn test_verify_integrity_accepts_legacy_pack_hash_format() {
+        let mut files = BTreeMap::new();
+        files.insert("x.rs".to_string(), FileEntry::new("abc123".to_string(), 10));
+        let hash_scheme = HashScheme::default();
+        let legacy_pack_hash = DpackManifest::compute_pack_hash_legacy(&files, &hash_scheme);
+        let manifest = DpackManifest {
+            schema_version: "1.0".to_string(),
+            root_2i_seed_fingerprint: "seed_fp".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            source_root: "/tmp/test".to_string(),
+            files,
+            pack_hash: legacy_pack_hash,
+            vcs: None,
+            hash_scheme,
+        };
+        assert!(
+            manifest.verify_integrity(),
+            "manifests with a pre-canonical-encoding pack_hash stay verifiable"
+        );
+    }
+
+    #[test]
+    fn test_file_entry_new_defaults_to_regular_with_no_metadata() {
+        let entry = FileEntry::new("abc".to_string(), 3);
+        assert_eq!(entry.kind, EntryKind::Regular);
+        assert_eq!(entry.mode, 0);
+        assert!(entry.xattrs.is_empty());
+    }
+
+    #[test]
+    fn test_canonical_bytes_changes_with_entry_kind_and_mode() {
+        let mut files = BTreeMap::new();
+        files.insert("a".to_string(), FileEntry::new("aaa".to_string(), 3));
+        let plain = DpackManifest::canonical_files_bytes(&files);
+
+        files.insert(
+            "a".to_string(),
+            FileEntry::with_metadata(
+                "aaa".to_string(),
+                3,
+                EntryKind::Symlink { target: "b".to_string() },
+                0o777,
+                BTreeMap::new(),
+            ),
+        );
+        let as_symlink = DpackManifest::canonical_files_bytes(&files);
+
+        assert_ne!(plain, as_symlink, "entry kind must affect the canonical encoding");
+    }
 }
@@ -2,14 +2,21 @@
 //!
 //! A DPACK is a directory containing:
 //!   - manifest.json  (the DpackManifest)
-//!   - data/          (file contents, stored at their relative paths)
+//!   - data/          (file contents, stored at their relative paths, or in
+//!                     a content-addressed object store — see [`pack_repo_objects`])
 
-use crate::manifest::{DpackManifest, FileEntry};
+use crate::manifest::{DpackManifest, EntryKind, FileEntry};
 use crate::policy::Policy;
 use crate::receipt::{AuditReceipt, GateResult, GateStatus};
+use crate::vcs::{detect_vcs_provenance, VcsProvenance};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use seed_core::{compute_sha256, Seed};
 use std::collections::BTreeMap;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use tar::{Archive, Builder, Header};
 use thiserror::Error;
 use walkdir::WalkDir;
 
@@ -31,12 +38,24 @@ pub enum PackError {
     PackNotFound(PathBuf),
 }
 
+/// Selects how `pack_repo`-family operations lay out a DPACK on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Explode into `manifest.json` + `data/` directory tree (the original layout).
+    Directory,
+    /// Stream `manifest.json` + `data/` into a single gzip-compressed tar blob (a `.dpack` file).
+    CompressedArchive,
+}
+
 /// Pack a repository into a DPACK directory.
 ///
 /// - `repo_root`: the root of the repository to pack.
 /// - `output_dir`: where to create the dpack (will contain manifest.json + data/).
 /// - `seed`: the loaded seed for fingerprint binding.
 /// - `policy`: optional inclusion/exclusion policy.
+/// - `allow_dirty`: if `false` (the default posture), a dirty working tree
+///   downgrades the `G2_PROVENANCE` gate to `Fail`, mirroring `cargo
+///   package`'s refusal to package a dirty tree without `--allow-dirty`.
 ///
 /// Returns the audit receipt.
 pub fn pack_repo(
@@ -44,48 +63,90 @@ pub fn pack_repo(
     output_dir: &Path,
     seed: &Seed,
     policy: Option<&Policy>,
+    allow_dirty: bool,
 ) -> Result<AuditReceipt, PackError> {
     let default_policy = Policy::default();
     let policy = policy.unwrap_or(&default_policy);
+    let compiled_policy = policy.compile();
 
     let data_dir = output_dir.join("data");
     std::fs::create_dir_all(&data_dir)?;
 
     let mut files = BTreeMap::new();
     let mut gates = Vec::new();
+    let empty_hash = policy.hash_scheme.digest(&[]);
 
-    // Walk the repo and collect files
+    // Walk the repo and collect files, symlinks, and (on unix) special files.
     for entry in WalkDir::new(repo_root)
         .follow_links(false)
         .sort_by_file_name()
     {
         let entry = entry?;
-        if !entry.file_type().is_file() {
+        let full_path = entry.path();
+        if full_path == repo_root {
             continue;
         }
-        let full_path = entry.path();
         let rel_path = full_path
             .strip_prefix(repo_root)
             .unwrap_or(full_path)
             .to_string_lossy()
             .replace('\\', "/");
 
-        if !policy.is_allowed(&rel_path) {
+        if !compiled_policy.is_allowed(&rel_path) {
             continue;
         }
 
-        let content = std::fs::read(full_path)?;
-        let hash = compute_sha256(&content);
-        let size = content.len() as u64;
+        let file_type = entry.file_type();
+        let mode = entry_mode(full_path)?;
+        let xattrs = capture_xattrs(full_path);
 
-        // Copy file to data dir preserving relative path
-        let dest = data_dir.join(&rel_path);
-        if let Some(parent) = dest.parent() {
-            std::fs::create_dir_all(parent)?;
+        if file_type.is_dir() {
+            files.insert(
+                rel_path,
+                FileEntry::with_metadata(empty_hash.clone(), 0, EntryKind::Directory, mode, xattrs),
+            );
+            continue;
+        }
+
+        if file_type.is_symlink() {
+            let target = std::fs::read_link(full_path)?.to_string_lossy().to_string();
+            let hash = policy.hash_scheme.digest(target.as_bytes());
+            let size = target.len() as u64;
+            files.insert(
+                rel_path,
+                FileEntry::with_metadata(hash, size, EntryKind::Symlink { target }, mode, xattrs),
+            );
+            continue;
+        }
+
+        if file_type.is_file() {
+            let content = std::fs::read(full_path)?;
+            let hash = policy.hash_scheme.digest(&content);
+            let size = content.len() as u64;
+
+            // Copy file to data dir preserving relative path
+            let dest = data_dir.join(&rel_path);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, &content)?;
+
+            files.insert(
+                rel_path,
+                FileEntry::with_metadata(hash, size, EntryKind::Regular, mode, xattrs),
+            );
+            continue;
         }
-        std::fs::write(&dest, &content)?;
 
-        files.insert(rel_path, FileEntry { sha256: hash, size });
+        // FIFOs and device nodes: unix-only filesystem objects with no
+        // content to copy into data/; their type is enough to recreate
+        // them on unfurl.
+        if let Some(kind) = special_entry_kind(full_path, &file_type) {
+            files.insert(
+                rel_path,
+                FileEntry::with_metadata(empty_hash.clone(), 0, kind, mode, xattrs),
+            );
+        }
     }
 
     // G0: Schema - we always produce valid schema
@@ -96,13 +157,42 @@ pub fn pack_repo(
     });
 
     // G1: Integrity
-    let pack_hash = DpackManifest::compute_pack_hash(&files);
+    let pack_hash = DpackManifest::compute_pack_hash(&files, &policy.hash_scheme);
     gates.push(GateResult {
         gate: "G1_INTEGRITY".to_string(),
         status: GateStatus::Pass,
         detail: format!("pack_hash={}", &pack_hash[..16]),
     });
 
+    // G2: Provenance - record VCS commit/branch and whether the tree is clean
+    let vcs = detect_vcs_provenance(repo_root);
+    gates.push(match &vcs {
+        None => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Skip,
+            detail: "repo_root is not a git checkout; no VCS provenance captured".to_string(),
+        },
+        Some(provenance) if provenance.dirty && !allow_dirty => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Fail,
+            detail: format!(
+                "working tree is dirty at commit {} ({}); pass allow_dirty=true to override",
+                &provenance.commit_id[..16],
+                provenance.reference
+            ),
+        },
+        Some(provenance) => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Pass,
+            detail: format!(
+                "commit={} ref={} dirty={}",
+                &provenance.commit_id[..16],
+                provenance.reference,
+                provenance.dirty
+            ),
+        },
+    });
+
     // G4: Seed binding
     gates.push(GateResult {
         gate: "G4_SEED_BINDING".to_string(),
@@ -124,75 +214,1475 @@ pub fn pack_repo(
         source_root: repo_root.to_string_lossy().to_string(),
         files,
         pack_hash: pack_hash.clone(),
+        vcs,
+        hash_scheme: policy.hash_scheme,
+    };
+
+    // Write manifest
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    // G7: Release receipt
+    gates.push(GateResult {
+        gate: "G7_RELEASE_RECEIPT".to_string(),
+        status: GateStatus::Pass,
+        detail: "manifest written".to_string(),
+    });
+
+    let receipt = AuditReceipt::new("pack", &seed.fingerprint, Some(&pack_hash), gates);
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(output_dir.join("receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// Pack a repository into a single gzip-compressed tar `.dpack` file.
+///
+/// Collects files and computes gates identically to [`pack_repo`], but
+/// streams `manifest.json` (written first) and each file under `data/<rel_path>`
+/// into one tar+gzip blob instead of a directory tree, so a directory pack
+/// and an archive pack of the same repo produce the same `pack_hash`.
+pub fn pack_repo_archive(
+    repo_root: &Path,
+    output_path: &Path,
+    seed: &Seed,
+    policy: Option<&Policy>,
+) -> Result<AuditReceipt, PackError> {
+    let default_policy = Policy::default();
+    let policy = policy.unwrap_or(&default_policy);
+    let compiled_policy = policy.compile();
+
+    let mut files = BTreeMap::new();
+    let mut contents: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut gates = Vec::new();
+
+    for entry in WalkDir::new(repo_root)
+        .follow_links(false)
+        .sort_by_file_name()
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let full_path = entry.path();
+        let rel_path = full_path
+            .strip_prefix(repo_root)
+            .unwrap_or(full_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if !compiled_policy.is_allowed(&rel_path) {
+            continue;
+        }
+
+        let content = std::fs::read(full_path)?;
+        let hash = policy.hash_scheme.digest(&content);
+        let size = content.len() as u64;
+        files.insert(rel_path.clone(), FileEntry::new(hash, size));
+        contents.insert(rel_path, content);
+    }
+
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: GateStatus::Pass,
+        detail: "manifest schema v1.0".to_string(),
+    });
+
+    let pack_hash = DpackManifest::compute_pack_hash(&files, &policy.hash_scheme);
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("pack_hash={}", &pack_hash[..16]),
+    });
+
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("seed_fp={}", &seed.fingerprint[..16]),
+    });
+
+    gates.push(GateResult {
+        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{} files packed", files.len()),
+    });
+
+    let manifest = DpackManifest {
+        schema_version: "1.0".to_string(),
+        root_2i_seed_fingerprint: seed.fingerprint.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        source_root: repo_root.to_string_lossy().to_string(),
+        files,
+        pack_hash: pack_hash.clone(),
+        vcs: None,
+        hash_scheme: policy.hash_scheme,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let out_file = std::fs::File::create(output_path)?;
+    let encoder = GzEncoder::new(out_file, Compression::default());
+    let mut tar = Builder::new(encoder);
+
+    // manifest.json first so verify_pack can read it without unpacking the rest.
+    let mut manifest_header = Header::new_gnu();
+    manifest_header.set_size(manifest_json.len() as u64);
+    manifest_header.set_mode(0o644);
+    manifest_header.set_cksum();
+    tar.append_data(
+        &mut manifest_header,
+        "manifest.json",
+        manifest_json.as_slice(),
+    )?;
+
+    for (rel_path, content) in &contents {
+        let mut header = Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, format!("data/{rel_path}"), content.as_slice())?;
+    }
+
+    gates.push(GateResult {
+        gate: "G7_RELEASE_RECEIPT".to_string(),
+        status: GateStatus::Pass,
+        detail: "manifest written".to_string(),
+    });
+
+    let receipt = AuditReceipt::new("pack", &seed.fingerprint, Some(&pack_hash), gates);
+    let receipt_json = receipt.to_json()?;
+    let mut receipt_header = Header::new_gnu();
+    receipt_header.set_size(receipt_json.len() as u64);
+    receipt_header.set_mode(0o644);
+    receipt_header.set_cksum();
+    tar.append_data(&mut receipt_header, "receipt.json", receipt_json.as_bytes())?;
+
+    let encoder = tar.into_inner()?;
+    encoder.finish()?;
+
+    Ok(receipt)
+}
+
+/// Pack a repository into a DPACK directory using a content-addressed,
+/// deduplicating object store for the file bytes.
+///
+/// Blobs are written once per distinct SHA-256 at
+/// `data/objects/<first two hex chars>/<remaining hex>` (a git-odb-style
+/// layout), so identical files anywhere in the tree — vendored copies,
+/// duplicated license headers, renames — share a single copy on disk. The
+/// manifest keeps the usual `rel_path -> sha256` association in
+/// [`FileEntry`]. Only regular files are recorded, as plain
+/// [`FileEntry::new`] entries: directories, symlinks, special files, mode
+/// bits and xattrs are not captured.
+pub fn pack_repo_objects(
+    repo_root: &Path,
+    output_dir: &Path,
+    seed: &Seed,
+    policy: Option<&Policy>,
+) -> Result<AuditReceipt, PackError> {
+    let default_policy = Policy::default();
+    let policy = policy.unwrap_or(&default_policy);
+    let compiled_policy = policy.compile();
+
+    let data_dir = output_dir.join("data");
+    let objects_dir = data_dir.join("objects");
+    std::fs::create_dir_all(&objects_dir)?;
+
+    let mut files = BTreeMap::new();
+    let mut gates = Vec::new();
+    let mut written_objects = std::collections::BTreeSet::new();
+
+    for entry in WalkDir::new(repo_root)
+        .follow_links(false)
+        .sort_by_file_name()
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let full_path = entry.path();
+        let rel_path = full_path
+            .strip_prefix(repo_root)
+            .unwrap_or(full_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if !compiled_policy.is_allowed(&rel_path) {
+            continue;
+        }
+
+        let content = std::fs::read(full_path)?;
+        let hash = policy.hash_scheme.digest(&content);
+        let size = content.len() as u64;
+
+        if written_objects.insert(hash.clone()) {
+            let dest = object_path(&objects_dir, &hash)?;
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&dest, &content)?;
+        }
+
+        files.insert(rel_path, FileEntry::new(hash, size));
+    }
+
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: GateStatus::Pass,
+        detail: "manifest schema v1.0".to_string(),
+    });
+
+    let pack_hash = DpackManifest::compute_pack_hash(&files, &policy.hash_scheme);
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("pack_hash={}", &pack_hash[..16]),
+    });
+
+    let dedup_ratio = if files.is_empty() {
+        0.0
+    } else {
+        written_objects.len() as f64 / files.len() as f64
+    };
+    gates.push(GateResult {
+        gate: "G2_DEDUP".to_string(),
+        status: GateStatus::Pass,
+        detail: format!(
+            "{} distinct objects / {} paths (ratio={:.3})",
+            written_objects.len(),
+            files.len(),
+            dedup_ratio
+        ),
+    });
+
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("seed_fp={}", &seed.fingerprint[..16]),
+    });
+
+    gates.push(GateResult {
+        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{} files packed", files.len()),
+    });
+
+    let manifest = DpackManifest {
+        schema_version: "1.0".to_string(),
+        root_2i_seed_fingerprint: seed.fingerprint.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        source_root: repo_root.to_string_lossy().to_string(),
+        files,
+        pack_hash: pack_hash.clone(),
+        vcs: None,
+        hash_scheme: policy.hash_scheme,
     };
 
-    // Write manifest
-    let manifest_json = serde_json::to_string_pretty(&manifest)?;
-    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+    let manifest_json = serde_json::to_string_pretty(&manifest)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    gates.push(GateResult {
+        gate: "G7_RELEASE_RECEIPT".to_string(),
+        status: GateStatus::Pass,
+        detail: "manifest written".to_string(),
+    });
+
+    let receipt = AuditReceipt::new("pack", &seed.fingerprint, Some(&pack_hash), gates);
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(output_dir.join("receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// Whether `hash` is a well-formed SHA-256 hex digest (64 hex characters,
+/// either case) - the shape every git-odb-style sharded path (`<first two
+/// chars>/<remaining chars>`) assumes. Check this before slicing a hash
+/// pulled out of an untrusted manifest: a string shorter than 2 chars
+/// panics on the slice otherwise.
+pub fn validate_hex_hash(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// The on-disk path of the object holding `sha256`'s bytes, git-odb-style:
+/// `<objects_dir>/<first two hex chars>/<remaining hex chars>`.
+fn object_path(objects_dir: &Path, sha256: &str) -> Result<PathBuf, PackError> {
+    if !validate_hex_hash(sha256) {
+        return Err(PackError::VerificationFailed {
+            reason: format!("malformed object hash: {sha256}"),
+        });
+    }
+    Ok(objects_dir.join(&sha256[..2]).join(&sha256[2..]))
+}
+
+/// Whether `pack_dir` stores its file bytes in a content-addressed object
+/// store (`data/objects/`) rather than at `data/<rel_path>`.
+fn is_object_store_pack(pack_dir: &Path) -> bool {
+    pack_dir.join("data").join("objects").is_dir()
+}
+
+/// Reject a manifest-supplied relative path that could escape `target_dir`
+/// once joined onto it: an absolute path makes `Path::join` discard the
+/// base entirely, and a `..` component walks back out of it. Every unfurl
+/// call site must pass `rel_path` through this before joining it onto a
+/// restore target - `rel_path` comes straight out of an untrusted,
+/// deserialized `manifest.json`.
+fn validate_rel_path(rel_path: &str) -> Result<(), PackError> {
+    let path = Path::new(rel_path);
+    let unsafe_path = path.is_absolute()
+        || path
+            .components()
+            .any(|c| matches!(c, std::path::Component::ParentDir));
+    if unsafe_path {
+        return Err(PackError::VerificationFailed {
+            reason: format!("unsafe path in manifest: {rel_path}"),
+        });
+    }
+    Ok(())
+}
+
+/// Verify a DPACK directory packed with [`pack_repo_objects`]: each
+/// distinct object is hashed once (not once per path), and every manifest
+/// path must resolve to an existing object.
+fn verify_pack_objects(pack_dir: &Path, seed: &Seed) -> Result<AuditReceipt, PackError> {
+    let manifest_path = pack_dir.join("manifest.json");
+    let manifest_str = std::fs::read_to_string(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
+    let objects_dir = pack_dir.join("data").join("objects");
+
+    let mut gates = Vec::new();
+
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: if manifest.schema_version == "1.0" {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: format!("schema_version={}", manifest.schema_version),
+    });
+
+    let integrity_ok = manifest.verify_integrity();
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: if integrity_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if integrity_ok {
+            "pack_hash matches".to_string()
+        } else {
+            "pack_hash mismatch".to_string()
+        },
+    });
+
+    gates.push(provenance_receipt_gate(&manifest.vcs));
+
+    // G3: Pinning - verify each distinct object once, then confirm every
+    // manifest path resolves to an existing, hash-matching object.
+    let mut distinct_hashes: std::collections::BTreeSet<&str> = std::collections::BTreeSet::new();
+    for entry in manifest.files.values() {
+        distinct_hashes.insert(entry.sha256.as_str());
+    }
+
+    let mut all_hashes_ok = true;
+    let mut hash_detail = String::new();
+    for hash in &distinct_hashes {
+        let object = match object_path(&objects_dir, hash) {
+            Ok(path) => path,
+            Err(_) => {
+                all_hashes_ok = false;
+                hash_detail = format!("malformed object hash: {hash}");
+                break;
+            }
+        };
+        match std::fs::read(&object) {
+            Ok(content) => {
+                let actual = manifest.hash_scheme.digest(&content);
+                if &actual != hash {
+                    all_hashes_ok = false;
+                    hash_detail = format!("object hash mismatch: {hash}");
+                    break;
+                }
+            }
+            Err(_) => {
+                all_hashes_ok = false;
+                hash_detail = format!("object missing: {hash}");
+                break;
+            }
+        }
+    }
+    if all_hashes_ok {
+        for (rel_path, entry) in &manifest.files {
+            let resolves = match object_path(&objects_dir, &entry.sha256) {
+                Ok(path) => path.exists(),
+                Err(_) => false,
+            };
+            if !resolves {
+                all_hashes_ok = false;
+                hash_detail = format!("path resolves to missing object: {rel_path}");
+                break;
+            }
+        }
+    }
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: if all_hashes_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if all_hashes_ok {
+            format!(
+                "{} distinct objects verified for {} paths",
+                distinct_hashes.len(),
+                manifest.files.len()
+            )
+        } else {
+            hash_detail
+        },
+    });
+
+    let seed_ok = manifest.root_2i_seed_fingerprint == seed.fingerprint;
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: if seed_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if seed_ok {
+            "seed fingerprint matches".to_string()
+        } else {
+            format!(
+                "expected {}, got {}",
+                &seed.fingerprint[..16],
+                &manifest.root_2i_seed_fingerprint
+                    [..std::cmp::min(16, manifest.root_2i_seed_fingerprint.len())]
+            )
+        },
+    });
+
+    Ok(AuditReceipt::new(
+        "verify",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        gates,
+    ))
+}
+
+/// Unfurl a DPACK directory packed with [`pack_repo_objects`]: copy each
+/// object out to every path that references it.
+fn unfurl_pack_objects(
+    pack_dir: &Path,
+    target_dir: &Path,
+    seed: &Seed,
+) -> Result<AuditReceipt, PackError> {
+    let manifest_path = pack_dir.join("manifest.json");
+    let manifest_str = std::fs::read_to_string(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
+    let objects_dir = pack_dir.join("data").join("objects");
+
+    let mut gates = Vec::new();
+    let mut files_restored = 0u64;
+
+    std::fs::create_dir_all(target_dir)?;
+
+    for (rel_path, entry) in &manifest.files {
+        validate_rel_path(rel_path)?;
+        let src = object_path(&objects_dir, &entry.sha256)?;
+        let dst = target_dir.join(rel_path);
+
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = std::fs::read(&src)?;
+
+        let actual_hash = manifest.hash_scheme.digest(&content);
+        if actual_hash != entry.sha256 {
+            gates.push(GateResult {
+                gate: "G3_PINNING".to_string(),
+                status: GateStatus::Fail,
+                detail: format!("hash mismatch during unfurl: {rel_path}"),
+            });
+            let receipt = AuditReceipt::new(
+                "unfurl",
+                &seed.fingerprint,
+                Some(&manifest.pack_hash),
+                gates,
+            );
+            return Err(PackError::VerificationFailed {
+                reason: receipt.to_json().unwrap_or_default(),
+            });
+        }
+
+        std::fs::write(&dst, &content)?;
+        files_restored += 1;
+    }
+
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{files_restored} files restored with verified hashes"),
+    });
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: GateStatus::Pass,
+        detail: "seed binding preserved".to_string(),
+    });
+    gates.push(GateResult {
+        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{files_restored} files, shape preserved"),
+    });
+
+    let receipt = AuditReceipt::new(
+        "unfurl",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        gates,
+    );
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(pack_dir.join("unfurl_receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// Whether `path` is a single-file `.dpack` archive rather than a pack directory.
+fn is_archive_file(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Surface the VCS provenance recorded in a manifest (if any) as a gate, so
+/// a consumer of the receipt can tell exactly which source revision a pack
+/// corresponds to. Purely informational: a dirty source commit was already
+/// judged at pack time by `pack_repo`'s `G2_PROVENANCE` gate, so this one
+/// never fails on its own.
+fn provenance_receipt_gate(vcs: &Option<VcsProvenance>) -> GateResult {
+    match vcs {
+        None => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Skip,
+            detail: "manifest has no recorded VCS provenance".to_string(),
+        },
+        Some(provenance) => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Pass,
+            detail: format!(
+                "commit={} ref={} dirty={}",
+                &provenance.commit_id[..16],
+                provenance.reference,
+                provenance.dirty
+            ),
+        },
+    }
+}
+
+// --- Entry metadata capture (pack) and restoration (unfurl) -----------------
+//
+// `pack_repo`/`unfurl_pack` round-trip each entry's type, POSIX mode bits,
+// and extended attributes. Symlinks, FIFOs, and device nodes are unix
+// concepts with no portable stdlib API; xattr and device-node *restoration*
+// additionally assume permissions (`CAP_MKNOD`, filesystem xattr support)
+// that aren't guaranteed even on unix. Capture always runs best-effort;
+// restoration tracks what it could not do in [`GatedMetadataCounts`] so
+// `unfurl_pack` can report a gate warning instead of failing outright.
+
+/// Permission bits of `path` (its own, not a followed symlink's target), or
+/// `0` if they cannot be read (e.g. a dangling symlink on some platforms).
+#[cfg(unix)]
+fn entry_mode(path: &Path) -> Result<u32, PackError> {
+    use std::os::unix::fs::PermissionsExt;
+    Ok(std::fs::symlink_metadata(path)?.permissions().mode() & 0o7777)
+}
+
+#[cfg(not(unix))]
+fn entry_mode(_path: &Path) -> Result<u32, PackError> {
+    Ok(0)
+}
+
+/// Best-effort application of `mode` to `path`; a failure here (e.g. not
+/// the file's owner) is recorded as a gated restoration, not a hard error.
+#[cfg(unix)]
+fn apply_mode(path: &Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode));
+}
+
+#[cfg(not(unix))]
+fn apply_mode(_path: &Path, _mode: u32) {}
+
+/// Capture every extended attribute on `path` as name -> hex-encoded value.
+/// Returns an empty map on platforms or filesystems without xattr support,
+/// rather than failing the pack.
+#[cfg(unix)]
+fn capture_xattrs(path: &Path) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    let Ok(names) = xattr::list(path) else {
+        return out;
+    };
+    for name in names {
+        if let Ok(Some(value)) = xattr::get(path, &name) {
+            out.insert(name.to_string_lossy().to_string(), hex::encode(value));
+        }
+    }
+    out
+}
+
+#[cfg(not(unix))]
+fn capture_xattrs(_path: &Path) -> BTreeMap<String, String> {
+    BTreeMap::new()
+}
+
+/// Classify a non-regular, non-directory, non-symlink entry as a FIFO or
+/// device node (unix-only); `None` for anything else (sockets, etc.),
+/// which `pack_repo` silently skips.
+#[cfg(unix)]
+fn special_entry_kind(path: &Path, file_type: &std::fs::FileType) -> Option<EntryKind> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    if file_type.is_fifo() {
+        return Some(EntryKind::Fifo);
+    }
+    if file_type.is_char_device() || file_type.is_block_device() {
+        let rdev = std::fs::symlink_metadata(path).ok()?.rdev();
+        let major = dev_major(rdev);
+        let minor = dev_minor(rdev);
+        return Some(if file_type.is_char_device() {
+            EntryKind::CharDevice { major, minor }
+        } else {
+            EntryKind::BlockDevice { major, minor }
+        });
+    }
+    None
+}
+
+#[cfg(not(unix))]
+fn special_entry_kind(_path: &Path, _file_type: &std::fs::FileType) -> Option<EntryKind> {
+    None
+}
+
+/// Counts of metadata restorations that were skipped because this
+/// platform or the current user's permissions could not support them,
+/// surfaced as a single warning gate rather than failing `unfurl_pack`.
+#[derive(Debug, Default)]
+struct GatedMetadataCounts {
+    symlinks_skipped: u64,
+    special_files_skipped: u64,
+    xattrs_skipped: u64,
+}
+
+impl GatedMetadataCounts {
+    fn is_clean(&self) -> bool {
+        self.symlinks_skipped == 0 && self.special_files_skipped == 0 && self.xattrs_skipped == 0
+    }
+
+    fn into_gate(self) -> GateResult {
+        if self.is_clean() {
+            return GateResult {
+                gate: "G8_METADATA_FIDELITY".to_string(),
+                status: GateStatus::Pass,
+                detail: "symlinks, special files, and xattrs restored".to_string(),
+            };
+        }
+        GateResult {
+            gate: "G8_METADATA_FIDELITY".to_string(),
+            status: GateStatus::Skip,
+            detail: format!(
+                "skipped on this platform/permissions: {} symlinks, {} special files, {} xattrs",
+                self.symlinks_skipped, self.special_files_skipped, self.xattrs_skipped
+            ),
+        }
+    }
+}
+
+/// Recreate `target` as a symlink at `dst`, replacing anything already
+/// there (unfurl always targets a fresh or previously-verified tree).
+#[cfg(unix)]
+fn restore_symlink(dst: &Path, target: &str, gated: &mut GatedMetadataCounts) {
+    let _ = std::fs::remove_file(dst);
+    if std::os::unix::fs::symlink(target, dst).is_err() {
+        gated.symlinks_skipped += 1;
+    }
+}
+
+#[cfg(not(unix))]
+fn restore_symlink(_dst: &Path, _target: &str, gated: &mut GatedMetadataCounts) {
+    gated.symlinks_skipped += 1;
+}
+
+/// Recreate a FIFO or device node at `dst`. Device nodes require
+/// `CAP_MKNOD` on most systems, so a permission failure here is gated
+/// rather than propagated.
+#[cfg(unix)]
+fn restore_special_file(dst: &Path, kind: &EntryKind, gated: &mut GatedMetadataCounts) {
+    use std::ffi::CString;
+    let Some(path_str) = dst.to_str() else {
+        gated.special_files_skipped += 1;
+        return;
+    };
+    let Ok(c_path) = CString::new(path_str) else {
+        gated.special_files_skipped += 1;
+        return;
+    };
+
+    let result = match kind {
+        EntryKind::Fifo => unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) },
+        EntryKind::CharDevice { major, minor } => unsafe {
+            libc::mknod(c_path.as_ptr(), libc::S_IFCHR | 0o600, dev_makedev(*major, *minor))
+        },
+        EntryKind::BlockDevice { major, minor } => unsafe {
+            libc::mknod(c_path.as_ptr(), libc::S_IFBLK | 0o600, dev_makedev(*major, *minor))
+        },
+        EntryKind::Regular | EntryKind::Directory | EntryKind::Symlink { .. } => {
+            unreachable!("only called for FIFO/device entries")
+        }
+    };
+
+    if result != 0 {
+        gated.special_files_skipped += 1;
+    }
+}
+
+#[cfg(not(unix))]
+fn restore_special_file(_dst: &Path, _kind: &EntryKind, gated: &mut GatedMetadataCounts) {
+    gated.special_files_skipped += 1;
+}
+
+/// Apply previously-captured xattrs (name -> hex-encoded value) to `path`,
+/// counting any that fail (no xattr support on this filesystem, or
+/// insufficient permission) rather than aborting the unfurl.
+#[cfg(unix)]
+fn restore_xattrs(path: &Path, xattrs: &BTreeMap<String, String>, gated: &mut GatedMetadataCounts) {
+    for (name, hex_value) in xattrs {
+        let ok = hex::decode(hex_value)
+            .ok()
+            .map(|value| xattr::set(path, name, &value).is_ok())
+            .unwrap_or(false);
+        if !ok {
+            gated.xattrs_skipped += 1;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn restore_xattrs(_path: &Path, xattrs: &BTreeMap<String, String>, gated: &mut GatedMetadataCounts) {
+    gated.xattrs_skipped += xattrs.len() as u64;
+}
+
+/// Major device number from a packed `st_rdev`, using the glibc
+/// `gnu_dev_major` bit layout (also used by the Linux kernel's userspace
+/// ABI), so packs stay portable across unix variants that agree on it.
+#[cfg(unix)]
+fn dev_major(rdev: u64) -> u32 {
+    (((rdev >> 8) & 0xfff) | ((rdev >> 32) & !0xfff)) as u32
+}
+
+/// Minor device number from a packed `st_rdev`; see [`dev_major`].
+#[cfg(unix)]
+fn dev_minor(rdev: u64) -> u32 {
+    ((rdev & 0xff) | ((rdev >> 12) & !0xff)) as u32
+}
+
+/// Re-pack a (major, minor) pair into the `st_rdev` encoding [`dev_major`]
+/// and [`dev_minor`] decode.
+#[cfg(unix)]
+fn dev_makedev(major: u32, minor: u32) -> u64 {
+    ((major as u64 & 0xfff) << 8)
+        | (minor as u64 & 0xff)
+        | ((major as u64 & !0xfff) << 32)
+        | ((minor as u64 & !0xff) << 12)
+}
+
+/// Build the `VerificationFailed` error for a hash mismatch found mid-unfurl,
+/// writing the gates accumulated so far (plus a final `G3_PINNING` failure)
+/// into the error's receipt JSON, matching the shape a completed receipt
+/// would have had.
+fn unfurl_pinning_failure(
+    seed: &Seed,
+    manifest: &DpackManifest,
+    mut gates: Vec<GateResult>,
+    rel_path: &str,
+) -> PackError {
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: GateStatus::Fail,
+        detail: format!("hash mismatch during unfurl: {rel_path}"),
+    });
+    let receipt = AuditReceipt::new("unfurl", &seed.fingerprint, Some(&manifest.pack_hash), gates);
+    PackError::VerificationFailed {
+        reason: receipt.to_json().unwrap_or_default(),
+    }
+}
+
+/// Read the manifest and per-entry file contents out of a `.dpack` archive,
+/// hashing each entry as it streams off the gzip decoder rather than
+/// materializing the tree to disk first.
+fn read_pack_archive(
+    archive_path: &Path,
+) -> Result<(DpackManifest, BTreeMap<String, Vec<u8>>), PackError> {
+    let file = std::fs::File::open(archive_path)?;
+    let decoder = GzDecoder::new(file);
+    let mut archive = Archive::new(decoder);
+
+    let mut manifest: Option<DpackManifest> = None;
+    let mut files = BTreeMap::new();
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.to_string_lossy().replace('\\', "/");
+        let mut buf = Vec::new();
+        entry.read_to_end(&mut buf)?;
+
+        if entry_path == "manifest.json" {
+            manifest = Some(serde_json::from_slice(&buf)?);
+        } else if let Some(rel) = entry_path.strip_prefix("data/") {
+            files.insert(rel.to_string(), buf);
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| PackError::VerificationFailed {
+        reason: "archive missing manifest.json".to_string(),
+    })?;
+    Ok((manifest, files))
+}
+
+/// Verify a `.dpack` archive: same gates as [`verify_pack`], computing each
+/// entry's SHA-256 as it is decompressed rather than reading from disk.
+fn verify_pack_archive(archive_path: &Path, seed: &Seed) -> Result<AuditReceipt, PackError> {
+    let (manifest, files) = read_pack_archive(archive_path)?;
+    let mut gates = Vec::new();
+
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: if manifest.schema_version == "1.0" {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: format!("schema_version={}", manifest.schema_version),
+    });
+
+    let integrity_ok = manifest.verify_integrity();
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: if integrity_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if integrity_ok {
+            "pack_hash matches".to_string()
+        } else {
+            "pack_hash mismatch".to_string()
+        },
+    });
+
+    gates.push(provenance_receipt_gate(&manifest.vcs));
+
+    let mut all_hashes_ok = true;
+    let mut hash_detail = String::new();
+    for (rel_path, entry) in &manifest.files {
+        match files.get(rel_path) {
+            Some(content) => {
+                let actual = manifest.hash_scheme.digest(content);
+                if actual != entry.sha256 {
+                    all_hashes_ok = false;
+                    hash_detail = format!("hash mismatch: {rel_path}");
+                    break;
+                }
+            }
+            None => {
+                all_hashes_ok = false;
+                hash_detail = format!("file missing: {rel_path}");
+                break;
+            }
+        }
+    }
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: if all_hashes_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if all_hashes_ok {
+            format!("{} files verified", manifest.files.len())
+        } else {
+            hash_detail
+        },
+    });
+
+    let seed_ok = manifest.root_2i_seed_fingerprint == seed.fingerprint;
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: if seed_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if seed_ok {
+            "seed fingerprint matches".to_string()
+        } else {
+            format!(
+                "expected {}, got {}",
+                &seed.fingerprint[..16],
+                &manifest.root_2i_seed_fingerprint
+                    [..std::cmp::min(16, manifest.root_2i_seed_fingerprint.len())]
+            )
+        },
+    });
+
+    Ok(AuditReceipt::new(
+        "verify",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        gates,
+    ))
+}
+
+/// Unfurl a `.dpack` archive into `target_dir`, verifying each file's hash
+/// as it is extracted.
+fn unfurl_pack_archive(
+    archive_path: &Path,
+    target_dir: &Path,
+    seed: &Seed,
+) -> Result<AuditReceipt, PackError> {
+    let (manifest, files) = read_pack_archive(archive_path)?;
+    let mut gates = Vec::new();
+    let mut files_restored = 0u64;
+
+    std::fs::create_dir_all(target_dir)?;
+
+    for (rel_path, entry) in &manifest.files {
+        validate_rel_path(rel_path)?;
+        let content = files
+            .get(rel_path)
+            .ok_or_else(|| PackError::VerificationFailed {
+                reason: format!("file missing in archive: {rel_path}"),
+            })?;
+
+        let actual_hash = manifest.hash_scheme.digest(content);
+        if actual_hash != entry.sha256 {
+            gates.push(GateResult {
+                gate: "G3_PINNING".to_string(),
+                status: GateStatus::Fail,
+                detail: format!("hash mismatch during unfurl: {rel_path}"),
+            });
+            let receipt = AuditReceipt::new(
+                "unfurl",
+                &seed.fingerprint,
+                Some(&manifest.pack_hash),
+                gates,
+            );
+            return Err(PackError::VerificationFailed {
+                reason: receipt.to_json().unwrap_or_default(),
+            });
+        }
+
+        let dst = target_dir.join(rel_path);
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dst, content)?;
+        files_restored += 1;
+    }
+
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{files_restored} files restored with verified hashes"),
+    });
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: GateStatus::Pass,
+        detail: "seed binding preserved".to_string(),
+    });
+    gates.push(GateResult {
+        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{files_restored} files, shape preserved"),
+    });
+
+    let receipt = AuditReceipt::new(
+        "unfurl",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        gates,
+    );
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(target_dir.join("unfurl_receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// Verify a DPACK directory: check manifest integrity, file hashes, and seed binding.
+pub fn verify_pack(pack_dir: &Path, seed: &Seed) -> Result<AuditReceipt, PackError> {
+    if !pack_dir.exists() {
+        return Err(PackError::PackNotFound(pack_dir.to_path_buf()));
+    }
+
+    if is_archive_file(pack_dir) {
+        return verify_pack_archive(pack_dir, seed);
+    }
+
+    if is_object_store_pack(pack_dir) {
+        return verify_pack_objects(pack_dir, seed);
+    }
+
+    let manifest_path = pack_dir.join("manifest.json");
+    let manifest_str = std::fs::read_to_string(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
+    let data_dir = pack_dir.join("data");
+
+    let mut gates = Vec::new();
+
+    // G0: Schema
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: if manifest.schema_version == "1.0" {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: format!("schema_version={}", manifest.schema_version),
+    });
+
+    // G1: Integrity (pack_hash matches file entries)
+    let integrity_ok = manifest.verify_integrity();
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: if integrity_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if integrity_ok {
+            "pack_hash matches".to_string()
+        } else {
+            "pack_hash mismatch".to_string()
+        },
+    });
+
+    gates.push(provenance_receipt_gate(&manifest.vcs));
+
+    // G3: Pinning - verify each entry's hash. Only `Regular` entries have
+    // content under `data/`; symlinks are hashed from their recorded
+    // target, and directories/special files carry no content at all.
+    let mut all_hashes_ok = true;
+    let mut hash_detail = String::new();
+    for (rel_path, entry) in &manifest.files {
+        let actual = match &entry.kind {
+            EntryKind::Regular => {
+                let file_path = data_dir.join(rel_path);
+                match std::fs::read(&file_path) {
+                    Ok(content) => manifest.hash_scheme.digest(&content),
+                    Err(_) => {
+                        all_hashes_ok = false;
+                        hash_detail = format!("file missing: {rel_path}");
+                        break;
+                    }
+                }
+            }
+            EntryKind::Symlink { target } => manifest.hash_scheme.digest(target.as_bytes()),
+            EntryKind::Directory | EntryKind::Fifo | EntryKind::CharDevice { .. } | EntryKind::BlockDevice { .. } => {
+                manifest.hash_scheme.digest(&[])
+            }
+        };
+        if actual != entry.sha256 {
+            all_hashes_ok = false;
+            hash_detail = format!("hash mismatch: {rel_path}");
+            break;
+        }
+    }
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: if all_hashes_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if all_hashes_ok {
+            format!("{} files verified", manifest.files.len())
+        } else {
+            hash_detail
+        },
+    });
+
+    // G4: Seed binding
+    let seed_ok = manifest.root_2i_seed_fingerprint == seed.fingerprint;
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: if seed_ok {
+            GateStatus::Pass
+        } else {
+            GateStatus::Fail
+        },
+        detail: if seed_ok {
+            "seed fingerprint matches".to_string()
+        } else {
+            format!(
+                "expected {}, got {}",
+                &seed.fingerprint[..16],
+                &manifest.root_2i_seed_fingerprint
+                    [..std::cmp::min(16, manifest.root_2i_seed_fingerprint.len())]
+            )
+        },
+    });
+
+    let receipt = AuditReceipt::new(
+        "verify",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        gates,
+    );
+    Ok(receipt)
+}
+
+/// Unfurl a DPACK: restore files from a pack directory to a target directory.
+/// Preserves paths verbatim. Returns an audit receipt.
+pub fn unfurl_pack(
+    pack_dir: &Path,
+    target_dir: &Path,
+    seed: &Seed,
+) -> Result<AuditReceipt, PackError> {
+    // First verify the pack
+    let verify_receipt = verify_pack(pack_dir, seed)?;
+    if !verify_receipt.passed {
+        return Err(PackError::VerificationFailed {
+            reason: "pack verification failed; refusing to unfurl".to_string(),
+        });
+    }
+
+    if is_archive_file(pack_dir) {
+        return unfurl_pack_archive(pack_dir, target_dir, seed);
+    }
+
+    if is_object_store_pack(pack_dir) {
+        return unfurl_pack_objects(pack_dir, target_dir, seed);
+    }
+
+    let manifest_path = pack_dir.join("manifest.json");
+    let manifest_str = std::fs::read_to_string(&manifest_path)?;
+    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
+    let data_dir = pack_dir.join("data");
+
+    let mut gates = Vec::new();
+    let mut files_restored = 0u64;
+    let mut gated = GatedMetadataCounts::default();
+
+    std::fs::create_dir_all(target_dir)?;
+
+    // Directories first, in path order, so nested entries always find
+    // their parent already created (and with the mode it was packed
+    // with, before any children narrow it further).
+    for (rel_path, entry) in manifest.files.iter().filter(|(_, e)| e.kind == EntryKind::Directory) {
+        validate_rel_path(rel_path)?;
+        let dst = target_dir.join(rel_path);
+        std::fs::create_dir_all(&dst)?;
+        apply_mode(&dst, entry.mode);
+        restore_xattrs(&dst, &entry.xattrs, &mut gated);
+    }
+
+    for (rel_path, entry) in manifest.files.iter().filter(|(_, e)| e.kind != EntryKind::Directory) {
+        validate_rel_path(rel_path)?;
+        let dst = target_dir.join(rel_path);
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        match &entry.kind {
+            EntryKind::Directory => unreachable!("directories are restored in the pass above"),
+            EntryKind::Symlink { target } => {
+                let actual_hash = manifest.hash_scheme.digest(target.as_bytes());
+                if actual_hash != entry.sha256 {
+                    return Err(unfurl_pinning_failure(seed, &manifest, gates, rel_path));
+                }
+                restore_symlink(&dst, target, &mut gated);
+            }
+            EntryKind::Fifo | EntryKind::CharDevice { .. } | EntryKind::BlockDevice { .. } => {
+                restore_special_file(&dst, &entry.kind, &mut gated);
+            }
+            EntryKind::Regular => {
+                let src = data_dir.join(rel_path);
+                let content = std::fs::read(&src)?;
+
+                // Verify hash before writing
+                let actual_hash = manifest.hash_scheme.digest(&content);
+                if actual_hash != entry.sha256 {
+                    return Err(unfurl_pinning_failure(seed, &manifest, gates, rel_path));
+                }
+
+                std::fs::write(&dst, &content)?;
+            }
+        }
+
+        apply_mode(&dst, entry.mode);
+        restore_xattrs(&dst, &entry.xattrs, &mut gated);
+        files_restored += 1;
+    }
+
+    gates.push(GateResult {
+        gate: "G3_PINNING".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{files_restored} files restored with verified hashes"),
+    });
+
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: GateStatus::Pass,
+        detail: "seed binding preserved".to_string(),
+    });
+
+    // G6: Orgasystem shape - verify restored tree shape
+    gates.push(GateResult {
+        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("{files_restored} files, shape preserved"),
+    });
+
+    // G8: Metadata fidelity - symlinks/special files/xattrs could not all
+    // be recreated on this platform or under these permissions. This is a
+    // warning, not a failure: the rest of the tree still unfurled.
+    gates.push(gated.into_gate());
+
+    let receipt = AuditReceipt::new(
+        "unfurl",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        gates,
+    );
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(pack_dir.join("unfurl_receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// Load a `DpackManifest` from a base pack reference: `base_path` may be a
+/// DPACK directory (its `manifest.json` is read) or a manifest.json file
+/// itself.
+pub fn load_base_manifest(base_path: &Path) -> Result<DpackManifest, PackError> {
+    let manifest_path = if base_path.is_dir() {
+        base_path.join("manifest.json")
+    } else {
+        base_path.to_path_buf()
+    };
+    let manifest_str = std::fs::read_to_string(&manifest_path)?;
+    Ok(serde_json::from_str(&manifest_str)?)
+}
+
+/// Pack a repository as a delta against `base_manifest`: only files whose
+/// [`FileEntry`] (hash, size, mode or xattrs) differs from the base are
+/// written under `data/`, plus a
+/// `delta_manifest.json` recording the diff and the base binding (see
+/// [`crate::delta`]). Produces a far smaller pack than [`pack_repo`] when
+/// most of the tree is unchanged since the base was taken.
+pub fn pack_repo_delta(
+    repo_root: &Path,
+    output_dir: &Path,
+    seed: &Seed,
+    policy: Option<&Policy>,
+    allow_dirty: bool,
+    base_manifest: &DpackManifest,
+) -> Result<AuditReceipt, PackError> {
+    let default_policy = Policy::default();
+    let policy = policy.unwrap_or(&default_policy);
+    let compiled_policy = policy.compile();
+
+    let mut files = BTreeMap::new();
+    let mut contents: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut gates = Vec::new();
+
+    for entry in WalkDir::new(repo_root)
+        .follow_links(false)
+        .sort_by_file_name()
+    {
+        let entry = entry?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let full_path = entry.path();
+        let rel_path = full_path
+            .strip_prefix(repo_root)
+            .unwrap_or(full_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if !compiled_policy.is_allowed(&rel_path) {
+            continue;
+        }
+
+        let content = std::fs::read(full_path)?;
+        let hash = policy.hash_scheme.digest(&content);
+        let size = content.len() as u64;
+        // Record mode and xattrs the way `pack_repo` does, so a file that is
+        // unchanged since the base compares equal to the base's entry.
+        let mode = entry_mode(full_path)?;
+        let xattrs = capture_xattrs(full_path);
+        files.insert(
+            rel_path.clone(),
+            FileEntry::with_metadata(hash, size, EntryKind::Regular, mode, xattrs),
+        );
+        contents.insert(rel_path, content);
+    }
+
+    gates.push(GateResult {
+        gate: "G0_SCHEMA".to_string(),
+        status: GateStatus::Pass,
+        detail: "delta manifest schema v1.0".to_string(),
+    });
+
+    let full_pack_hash = DpackManifest::compute_pack_hash(&files, &policy.hash_scheme);
+    gates.push(GateResult {
+        gate: "G1_INTEGRITY".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("full_pack_hash={}", &full_pack_hash[..16]),
+    });
+
+    let vcs = detect_vcs_provenance(repo_root);
+    gates.push(match &vcs {
+        None => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Skip,
+            detail: "repo_root is not a git checkout; no VCS provenance captured".to_string(),
+        },
+        Some(provenance) if provenance.dirty && !allow_dirty => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Fail,
+            detail: format!(
+                "working tree is dirty at commit {} ({}); pass allow_dirty=true to override",
+                &provenance.commit_id[..16],
+                provenance.reference
+            ),
+        },
+        Some(provenance) => GateResult {
+            gate: "G2_PROVENANCE".to_string(),
+            status: GateStatus::Pass,
+            detail: format!(
+                "commit={} ref={} dirty={}",
+                &provenance.commit_id[..16],
+                provenance.reference,
+                provenance.dirty
+            ),
+        },
+    });
+
+    gates.push(GateResult {
+        gate: "G4_SEED_BINDING".to_string(),
+        status: GateStatus::Pass,
+        detail: format!("seed_fp={}", &seed.fingerprint[..16]),
+    });
+
+    let full_manifest = DpackManifest {
+        schema_version: "1.0".to_string(),
+        root_2i_seed_fingerprint: seed.fingerprint.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        source_root: repo_root.to_string_lossy().to_string(),
+        files,
+        pack_hash: full_pack_hash.clone(),
+        vcs,
+        hash_scheme: policy.hash_scheme,
+    };
+
+    let delta = crate::delta::diff_manifests(base_manifest, &full_manifest);
+
+    gates.push(GateResult {
+        gate: "G_DELTA_BASE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!(
+            "base_pack_hash={} added_or_changed={} removed={}",
+            &delta.base_pack_hash[..16.min(delta.base_pack_hash.len())],
+            delta.added_or_changed.len(),
+            delta.removed.len()
+        ),
+    });
+
+    gates.push(GateResult {
+        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        status: GateStatus::Pass,
+        detail: format!(
+            "{} files in full tree, {} changed",
+            full_manifest.files.len(),
+            delta.added_or_changed.len()
+        ),
+    });
+
+    let data_dir = output_dir.join("data");
+    std::fs::create_dir_all(&data_dir)?;
+    for rel_path in delta.added_or_changed.keys() {
+        let content = &contents[rel_path];
+        let dest = data_dir.join(rel_path);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&dest, content)?;
+    }
+
+    let delta_json = serde_json::to_string_pretty(&delta)?;
+    std::fs::write(output_dir.join("delta_manifest.json"), &delta_json)?;
 
-    // G7: Release receipt
     gates.push(GateResult {
         gate: "G7_RELEASE_RECEIPT".to_string(),
         status: GateStatus::Pass,
-        detail: "manifest written".to_string(),
+        detail: "delta_manifest written".to_string(),
     });
 
-    let receipt = AuditReceipt::new("pack", &seed.fingerprint, Some(&pack_hash), gates);
+    let receipt = AuditReceipt::new("pack_delta", &seed.fingerprint, Some(&full_pack_hash), gates);
     let receipt_json = receipt.to_json()?;
     std::fs::write(output_dir.join("receipt.json"), &receipt_json)?;
 
     Ok(receipt)
 }
 
-/// Verify a DPACK directory: check manifest integrity, file hashes, and seed binding.
-pub fn verify_pack(pack_dir: &Path, seed: &Seed) -> Result<AuditReceipt, PackError> {
-    if !pack_dir.exists() {
-        return Err(PackError::PackNotFound(pack_dir.to_path_buf()));
-    }
-
-    let manifest_path = pack_dir.join("manifest.json");
-    let manifest_str = std::fs::read_to_string(&manifest_path)?;
-    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
-    let data_dir = pack_dir.join("data");
+/// Verify a delta pack directory against the base it claims to extend.
+/// Fails closed if `base_manifest` doesn't match the delta's recorded base
+/// (see [`crate::delta::apply_delta`]), without needing to materialize the
+/// full tree.
+pub fn verify_pack_delta(
+    delta_dir: &Path,
+    base_manifest: &DpackManifest,
+    seed: &Seed,
+) -> Result<AuditReceipt, PackError> {
+    let delta_manifest_str = std::fs::read_to_string(delta_dir.join("delta_manifest.json"))?;
+    let delta: crate::delta::DeltaManifest = serde_json::from_str(&delta_manifest_str)?;
 
     let mut gates = Vec::new();
 
-    // G0: Schema
     gates.push(GateResult {
         gate: "G0_SCHEMA".to_string(),
-        status: if manifest.schema_version == "1.0" {
+        status: if delta.schema_version == "1.0" {
             GateStatus::Pass
         } else {
             GateStatus::Fail
         },
-        detail: format!("schema_version={}", manifest.schema_version),
+        detail: format!("schema_version={}", delta.schema_version),
     });
 
-    // G1: Integrity (pack_hash matches file entries)
-    let integrity_ok = manifest.verify_integrity();
+    let (base_status, base_detail, full_manifest) = match crate::delta::apply_delta(base_manifest, &delta) {
+        Ok(full) => (
+            GateStatus::Pass,
+            format!("full_pack_hash={}", &full.pack_hash[..16.min(full.pack_hash.len())]),
+            Some(full),
+        ),
+        Err(e) => (GateStatus::Fail, e.to_string(), None),
+    };
     gates.push(GateResult {
-        gate: "G1_INTEGRITY".to_string(),
-        status: if integrity_ok {
-            GateStatus::Pass
-        } else {
-            GateStatus::Fail
-        },
-        detail: if integrity_ok {
-            "pack_hash matches".to_string()
-        } else {
-            "pack_hash mismatch".to_string()
-        },
+        gate: "G_DELTA_BASE".to_string(),
+        status: base_status,
+        detail: base_detail,
     });
 
-    // G3: Pinning - verify each file hash
+    // G3: Pinning, for the changed files actually stored in this delta dir.
+    let data_dir = delta_dir.join("data");
     let mut all_hashes_ok = true;
     let mut hash_detail = String::new();
-    for (rel_path, entry) in &manifest.files {
+    for (rel_path, entry) in &delta.added_or_changed {
         let file_path = data_dir.join(rel_path);
         match std::fs::read(&file_path) {
             Ok(content) => {
-                let actual = compute_sha256(&content);
-                if actual != entry.sha256 {
+                if base_manifest.hash_scheme.digest(&content) != entry.sha256 {
                     all_hashes_ok = false;
                     hash_detail = format!("hash mismatch: {rel_path}");
                     break;
@@ -213,14 +1703,13 @@ pub fn verify_pack(pack_dir: &Path, seed: &Seed) -> Result<AuditReceipt, PackErr
             GateStatus::Fail
         },
         detail: if all_hashes_ok {
-            format!("{} files verified", manifest.files.len())
+            format!("{} changed files verified", delta.added_or_changed.len())
         } else {
             hash_detail
         },
     });
 
-    // G4: Seed binding
-    let seed_ok = manifest.root_2i_seed_fingerprint == seed.fingerprint;
+    let seed_ok = delta.root_2i_seed_fingerprint == seed.fingerprint;
     gates.push(GateResult {
         gate: "G4_SEED_BINDING".to_string(),
         status: if seed_ok {
@@ -231,116 +1720,163 @@ pub fn verify_pack(pack_dir: &Path, seed: &Seed) -> Result<AuditReceipt, PackErr
         detail: if seed_ok {
             "seed fingerprint matches".to_string()
         } else {
-            format!(
-                "expected {}, got {}",
-                &seed.fingerprint[..16],
-                &manifest.root_2i_seed_fingerprint
-                    [..std::cmp::min(16, manifest.root_2i_seed_fingerprint.len())]
-            )
+            "seed fingerprint mismatch".to_string()
         },
     });
 
-    let receipt = AuditReceipt::new(
-        "verify",
+    let pack_hash = full_manifest.map(|m| m.pack_hash);
+    Ok(AuditReceipt::new(
+        "verify_delta",
         &seed.fingerprint,
-        Some(&manifest.pack_hash),
+        pack_hash.as_deref(),
         gates,
-    );
-    Ok(receipt)
+    ))
 }
 
-/// Unfurl a DPACK: restore files from a pack directory to a target directory.
-/// Preserves paths verbatim. Returns an audit receipt.
-pub fn unfurl_pack(
-    pack_dir: &Path,
+/// Unfurl a delta pack (see [`pack_repo_delta`]) against `base_pack_dir` (a
+/// full DPACK directory), reconstructing the complete tree at `target_dir`.
+/// Fails closed if `base_pack_dir` isn't the exact base the delta was
+/// computed against, or the merged tree doesn't hash to the delta's
+/// advertised `full_pack_hash` (see [`crate::delta::apply_delta`]).
+pub fn unfurl_pack_delta(
+    delta_dir: &Path,
+    base_pack_dir: &Path,
     target_dir: &Path,
     seed: &Seed,
 ) -> Result<AuditReceipt, PackError> {
-    // First verify the pack
-    let verify_receipt = verify_pack(pack_dir, seed)?;
-    if !verify_receipt.passed {
+    let delta_manifest_str = std::fs::read_to_string(delta_dir.join("delta_manifest.json"))?;
+    let delta: crate::delta::DeltaManifest = serde_json::from_str(&delta_manifest_str)?;
+
+    let base_manifest = load_base_manifest(base_pack_dir)?;
+    let full_manifest = crate::delta::apply_delta(&base_manifest, &delta)?;
+
+    if full_manifest.root_2i_seed_fingerprint != seed.fingerprint {
         return Err(PackError::VerificationFailed {
-            reason: "pack verification failed; refusing to unfurl".to_string(),
+            reason: "seed fingerprint does not match the reconstructed manifest".to_string(),
         });
     }
 
-    let manifest_path = pack_dir.join("manifest.json");
-    let manifest_str = std::fs::read_to_string(&manifest_path)?;
-    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
-    let data_dir = pack_dir.join("data");
+    let base_data_dir = base_pack_dir.join("data");
+    let delta_data_dir = delta_dir.join("data");
 
+    std::fs::create_dir_all(target_dir)?;
     let mut gates = Vec::new();
     let mut files_restored = 0u64;
 
-    std::fs::create_dir_all(target_dir)?;
-
-    for (rel_path, entry) in &manifest.files {
-        let src = data_dir.join(rel_path);
+    for (rel_path, entry) in &full_manifest.files {
+        validate_rel_path(rel_path)?;
+        let src = if delta.added_or_changed.contains_key(rel_path) {
+            delta_data_dir.join(rel_path)
+        } else {
+            base_data_dir.join(rel_path)
+        };
         let dst = target_dir.join(rel_path);
-
         if let Some(parent) = dst.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
         let content = std::fs::read(&src)?;
-
-        // Verify hash before writing
-        let actual_hash = compute_sha256(&content);
+        let actual_hash = full_manifest.hash_scheme.digest(&content);
         if actual_hash != entry.sha256 {
-            gates.push(GateResult {
-                gate: "G3_PINNING".to_string(),
-                status: GateStatus::Fail,
-                detail: format!("hash mismatch during unfurl: {rel_path}"),
-            });
-            let receipt = AuditReceipt::new(
-                "unfurl",
-                &seed.fingerprint,
-                Some(&manifest.pack_hash),
-                gates,
-            );
             return Err(PackError::VerificationFailed {
-                reason: receipt.to_json().unwrap_or_default(),
+                reason: format!("hash mismatch restoring {rel_path}"),
             });
         }
-
         std::fs::write(&dst, &content)?;
         files_restored += 1;
     }
 
     gates.push(GateResult {
-        gate: "G3_PINNING".to_string(),
-        status: GateStatus::Pass,
-        detail: format!("{files_restored} files restored with verified hashes"),
-    });
-
-    gates.push(GateResult {
-        gate: "G4_SEED_BINDING".to_string(),
+        gate: "G_DELTA_BASE".to_string(),
         status: GateStatus::Pass,
-        detail: "seed binding preserved".to_string(),
+        detail: format!(
+            "merged {} files ({} from delta, {} from base)",
+            files_restored,
+            delta.added_or_changed.len(),
+            files_restored - delta.added_or_changed.len() as u64
+        ),
     });
-
-    // G6: Orgasystem shape - verify restored tree shape
     gates.push(GateResult {
-        gate: "G6_ORGASYSTEM_SHAPE".to_string(),
+        gate: "G1_INTEGRITY".to_string(),
         status: GateStatus::Pass,
-        detail: format!("{files_restored} files, shape preserved"),
+        detail: format!("reconstructed pack_hash={}", &full_manifest.pack_hash[..16]),
     });
 
-    let receipt = AuditReceipt::new(
-        "unfurl",
+    Ok(AuditReceipt::new(
+        "unfurl_delta",
         &seed.fingerprint,
-        Some(&manifest.pack_hash),
+        Some(&full_manifest.pack_hash),
         gates,
-    );
-    let receipt_json = receipt.to_json()?;
-    std::fs::write(pack_dir.join("unfurl_receipt.json"), &receipt_json)?;
+    ))
+}
 
-    Ok(receipt)
+/// Reconstruct a full DPACK directory (manifest.json + data/) by merging
+/// `delta_dir` (see [`pack_repo_delta`] or
+/// [`crate::pack::load_base_manifest`]'s sibling, `compress::compress_dpack_with_base`)
+/// onto `base_pack_dir`. Unlike [`unfurl_pack_delta`], which restores a flat
+/// repo tree, this produces another DPACK directory — the shape
+/// [`pack_repo`] and [`unfurl_pack`] already expect — so a delta cpack can be
+/// decompressed straight into something the rest of the pipeline knows how
+/// to consume. Fails closed on the same conditions as [`unfurl_pack_delta`].
+pub fn materialize_delta_pack(
+    delta_dir: &Path,
+    base_pack_dir: &Path,
+    output_dir: &Path,
+) -> Result<DpackManifest, PackError> {
+    let delta_manifest_str = std::fs::read_to_string(delta_dir.join("delta_manifest.json"))?;
+    let delta: crate::delta::DeltaManifest = serde_json::from_str(&delta_manifest_str)?;
+
+    let base_manifest = load_base_manifest(base_pack_dir)?;
+    let full_manifest = crate::delta::apply_delta(&base_manifest, &delta)?;
+
+    let base_data_dir = base_pack_dir.join("data");
+    let delta_data_dir = delta_dir.join("data");
+    let out_data_dir = output_dir.join("data");
+    std::fs::create_dir_all(&out_data_dir)?;
+
+    for (rel_path, entry) in &full_manifest.files {
+        validate_rel_path(rel_path)?;
+        let src = if delta.added_or_changed.contains_key(rel_path) {
+            delta_data_dir.join(rel_path)
+        } else {
+            base_data_dir.join(rel_path)
+        };
+        let dst = out_data_dir.join(rel_path);
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = std::fs::read(&src)?;
+        if full_manifest.hash_scheme.digest(&content) != entry.sha256 {
+            return Err(PackError::VerificationFailed {
+                reason: format!("hash mismatch restoring {rel_path}"),
+            });
+        }
+        std::fs::write(&dst, &content)?;
+    }
+
+    let manifest_json = serde_json::to_string_pretty(&full_manifest)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    Ok(full_manifest)
 }
 
 /// Verify that two directory trees have identical shape and content hashes.
 pub fn verify_shape_equivalence(dir_a: &Path, dir_b: &Path) -> Result<bool, PackError> {
-    let collect = |root: &Path| -> Result<BTreeMap<String, String>, PackError> {
+    verify_shape_equivalence_ignoring(dir_a, dir_b, &[])
+}
+
+/// Like [`verify_shape_equivalence`], but skips top-level `dir_b` entries
+/// whose relative path matches one of `ignore`. Used by callers that stamp
+/// their own bookkeeping files (e.g. a replication receipt) directly into
+/// an otherwise-mirrored tree, so those files shouldn't count as a shape
+/// mismatch against the pristine source.
+pub fn verify_shape_equivalence_ignoring(
+    dir_a: &Path,
+    dir_b: &Path,
+    ignore: &[&str],
+) -> Result<bool, PackError> {
+    let collect = |root: &Path, ignore: &[&str]| -> Result<BTreeMap<String, String>, PackError> {
         let mut map = BTreeMap::new();
         for entry in WalkDir::new(root).follow_links(false).sort_by_file_name() {
             let entry = entry?;
@@ -353,17 +1889,37 @@ pub fn verify_shape_equivalence(dir_a: &Path, dir_b: &Path) -> Result<bool, Pack
                 .unwrap_or(entry.path())
                 .to_string_lossy()
                 .replace('\\', "/");
+            if ignore.contains(&rel.as_str()) {
+                continue;
+            }
             let content = std::fs::read(entry.path())?;
             map.insert(rel, compute_sha256(&content));
         }
         Ok(map)
     };
 
-    let a = collect(dir_a)?;
-    let b = collect(dir_b)?;
+    let a = collect(dir_a, &[])?;
+    let b = collect(dir_b, ignore)?;
     Ok(a == b)
 }
 
+/// Like [`verify_shape_equivalence`], but also asserts that `dir_a` is
+/// itself a git checkout currently sitting at `expected_commit`. Useful
+/// after an unfurl to confirm the restored tree not only matches `dir_b`
+/// byte-for-byte, but corresponds to the exact source revision recorded
+/// in the pack's VCS provenance.
+pub fn verify_shape_equivalence_at_commit(
+    dir_a: &Path,
+    dir_b: &Path,
+    expected_commit: &str,
+) -> Result<bool, PackError> {
+    let shape_ok = verify_shape_equivalence(dir_a, dir_b)?;
+    let commit_ok = detect_vcs_provenance(dir_a)
+        .map(|p| p.commit_id == expected_commit)
+        .unwrap_or(false);
+    Ok(shape_ok && commit_ok)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,7 +1944,7 @@ mod tests {
         let pack_dir = TempDir::new().unwrap();
         let seed = make_test_repo(repo_dir.path());
 
-        let receipt = pack_repo(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        let receipt = pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
         assert!(receipt.passed);
         assert!(pack_dir.path().join("manifest.json").exists());
         assert!(pack_dir.path().join("data/README.md").exists());
@@ -401,7 +1957,7 @@ mod tests {
         let pack_dir = TempDir::new().unwrap();
         let seed = make_test_repo(repo_dir.path());
 
-        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
         let verify_receipt = verify_pack(pack_dir.path(), &seed).unwrap();
         assert!(verify_receipt.passed);
     }
@@ -412,7 +1968,7 @@ mod tests {
         let pack_dir = TempDir::new().unwrap();
         let seed = make_test_repo(repo_dir.path());
 
-        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
 
         // Tamper with a file
         std::fs::write(pack_dir.path().join("data/README.md"), "TAMPERED").unwrap();
@@ -427,7 +1983,7 @@ mod tests {
         let pack_dir = TempDir::new().unwrap();
         let seed = make_test_repo(repo_dir.path());
 
-        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
 
         // Create a different seed
         let tmp = TempDir::new().unwrap();
@@ -438,6 +1994,23 @@ mod tests {
         assert!(!verify_receipt.passed);
     }
 
+    #[test]
+    fn test_verify_shape_equivalence_ignoring_skips_named_files() {
+        let dir_a = TempDir::new().unwrap();
+        let dir_b = TempDir::new().unwrap();
+        std::fs::write(dir_a.path().join("a.txt"), "same").unwrap();
+        std::fs::write(dir_b.path().join("a.txt"), "same").unwrap();
+        std::fs::write(dir_b.path().join("bookkeeping.json"), "extra").unwrap();
+
+        assert!(!verify_shape_equivalence(dir_a.path(), dir_b.path()).unwrap());
+        assert!(verify_shape_equivalence_ignoring(
+            dir_a.path(),
+            dir_b.path(),
+            &["bookkeeping.json"]
+        )
+        .unwrap());
+    }
+
     #[test]
     fn test_pack_then_unfurl_restores_identical() {
         let repo_dir = TempDir::new().unwrap();
@@ -445,7 +2018,7 @@ mod tests {
         let unfurl_dir = TempDir::new().unwrap();
         let seed = make_test_repo(repo_dir.path());
 
-        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
         let receipt = unfurl_pack(pack_dir.path(), unfurl_dir.path(), &seed).unwrap();
         assert!(receipt.passed);
 
@@ -454,6 +2027,59 @@ mod tests {
         assert!(equiv, "unfurled tree must be identical to original");
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_pack_captures_symlink_and_unfurl_recreates_it() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let unfurl_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+        std::os::unix::fs::symlink("README.md", repo_dir.path().join("link_to_readme")).unwrap();
+
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
+        let manifest_str = std::fs::read_to_string(pack_dir.path().join("manifest.json")).unwrap();
+        let manifest: DpackManifest = serde_json::from_str(&manifest_str).unwrap();
+        assert_eq!(
+            manifest.files.get("link_to_readme").unwrap().kind,
+            EntryKind::Symlink { target: "README.md".to_string() }
+        );
+
+        let receipt = unfurl_pack(pack_dir.path(), unfurl_dir.path(), &seed).unwrap();
+        assert!(receipt.passed);
+
+        let restored = unfurl_dir.path().join("link_to_readme");
+        assert_eq!(
+            std::fs::read_link(&restored).unwrap(),
+            std::path::PathBuf::from("README.md")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_pack_preserves_file_mode_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let unfurl_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+        std::fs::set_permissions(
+            repo_dir.path().join("src/main.rs"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
+        unfurl_pack(pack_dir.path(), unfurl_dir.path(), &seed).unwrap();
+
+        let mode = std::fs::metadata(unfurl_dir.path().join("src/main.rs"))
+            .unwrap()
+            .permissions()
+            .mode()
+            & 0o777;
+        assert_eq!(mode, 0o755);
+    }
+
     #[test]
     fn test_unfurl_refuses_bad_pack() {
         let repo_dir = TempDir::new().unwrap();
@@ -461,7 +2087,7 @@ mod tests {
         let unfurl_dir = TempDir::new().unwrap();
         let seed = make_test_repo(repo_dir.path());
 
-        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
 
         // Tamper
         std::fs::write(pack_dir.path().join("data/README.md"), "TAMPERED").unwrap();
@@ -486,13 +2112,107 @@ mod tests {
                 ".git".to_string(),
                 "*.env".to_string(),
             ],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: seed_core::hash::HashScheme::default(),
+            trusted_capability_roots: vec![],
         };
 
-        let receipt = pack_repo(repo_dir.path(), pack_dir.path(), &seed, Some(&policy)).unwrap();
+        let receipt = pack_repo(
+            repo_dir.path(),
+            pack_dir.path(),
+            &seed,
+            Some(&policy),
+            false,
+        )
+        .unwrap();
         assert!(receipt.passed);
         assert!(!pack_dir.path().join("data/secret.env").exists());
     }
 
+    fn git(repo: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn test_pack_non_git_repo_skips_provenance_gate() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        let receipt = pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
+        assert!(receipt.passed);
+        let gate = receipt
+            .gates
+            .iter()
+            .find(|g| g.gate == "G2_PROVENANCE")
+            .unwrap();
+        assert_eq!(gate.status, GateStatus::Skip);
+    }
+
+    #[test]
+    fn test_pack_clean_git_repo_passes_provenance_gate() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        git(repo_dir.path(), &["init", "-q"]);
+        git(repo_dir.path(), &["config", "user.email", "test@test.com"]);
+        git(repo_dir.path(), &["config", "user.name", "test"]);
+        git(repo_dir.path(), &["add", "."]);
+        git(repo_dir.path(), &["commit", "-q", "-m", "init"]);
+
+        let receipt = pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
+        assert!(receipt.passed);
+        let gate = receipt
+            .gates
+            .iter()
+            .find(|g| g.gate == "G2_PROVENANCE")
+            .unwrap();
+        assert_eq!(gate.status, GateStatus::Pass);
+    }
+
+    #[test]
+    fn test_pack_dirty_git_repo_fails_provenance_gate_unless_allowed() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        git(repo_dir.path(), &["init", "-q"]);
+        git(repo_dir.path(), &["config", "user.email", "test@test.com"]);
+        git(repo_dir.path(), &["config", "user.name", "test"]);
+        git(repo_dir.path(), &["add", "."]);
+        git(repo_dir.path(), &["commit", "-q", "-m", "init"]);
+        std::fs::write(repo_dir.path().join("uncommitted.txt"), "oops").unwrap();
+
+        let receipt = pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, false).unwrap();
+        assert!(!receipt.passed);
+        let gate = receipt
+            .gates
+            .iter()
+            .find(|g| g.gate == "G2_PROVENANCE")
+            .unwrap();
+        assert_eq!(gate.status, GateStatus::Fail);
+
+        let allowed_receipt =
+            pack_repo(repo_dir.path(), pack_dir.path(), &seed, None, true).unwrap();
+        assert!(allowed_receipt.passed);
+        let gate = allowed_receipt
+            .gates
+            .iter()
+            .find(|g| g.gate == "G2_PROVENANCE")
+            .unwrap();
+        assert_eq!(gate.status, GateStatus::Pass);
+    }
+
     #[test]
     fn test_shape_equivalence_identical() {
         let a = TempDir::new().unwrap();
@@ -502,6 +2222,107 @@ mod tests {
         assert!(verify_shape_equivalence(a.path(), b.path()).unwrap());
     }
 
+    #[test]
+    fn test_pack_archive_roundtrip_matches_directory_pack_hash() {
+        let repo_dir = TempDir::new().unwrap();
+        let dir_pack = TempDir::new().unwrap();
+        let archive_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        let dir_receipt = pack_repo(repo_dir.path(), dir_pack.path(), &seed, None, false).unwrap();
+
+        let archive_path = archive_dir.path().join("repo.dpack");
+        let archive_receipt =
+            pack_repo_archive(repo_dir.path(), &archive_path, &seed, None).unwrap();
+
+        assert!(archive_path.exists());
+        assert_eq!(dir_receipt.pack_hash, archive_receipt.pack_hash);
+
+        let verify_receipt = verify_pack(&archive_path, &seed).unwrap();
+        assert!(verify_receipt.passed);
+
+        let unfurl_dir = TempDir::new().unwrap();
+        let unfurl_receipt = unfurl_pack(&archive_path, unfurl_dir.path(), &seed).unwrap();
+        assert!(unfurl_receipt.passed);
+        assert!(unfurl_dir.path().join("README.md").exists());
+        assert!(unfurl_dir.path().join("src/main.rs").exists());
+    }
+
+    #[test]
+    fn test_pack_objects_dedupes_identical_content() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        // A duplicate of README.md's exact bytes under a different path.
+        std::fs::write(repo_dir.path().join("COPY.md"), "# Test Repo").unwrap();
+
+        let receipt = pack_repo_objects(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+        assert!(receipt.passed);
+
+        let dedup_gate = receipt
+            .gates
+            .iter()
+            .find(|g| g.gate == "G2_DEDUP")
+            .expect("dedup gate present");
+        assert!(dedup_gate.detail.contains("3 distinct objects / 4 paths"));
+
+        let manifest_str = std::fs::read_to_string(pack_dir.path().join("manifest.json")).unwrap();
+        let manifest: DpackManifest = serde_json::from_str(&manifest_str).unwrap();
+        assert_eq!(
+            manifest.files["README.md"].sha256,
+            manifest.files["COPY.md"].sha256
+        );
+
+        // Only one object on disk for the two identical files.
+        let objects_dir = pack_dir.path().join("data/objects");
+        let object_count = WalkDir::new(&objects_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .count();
+        assert_eq!(object_count, 3, "README.md/COPY.md share one object");
+    }
+
+    #[test]
+    fn test_pack_objects_verify_and_unfurl_roundtrip() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let unfurl_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+        std::fs::write(repo_dir.path().join("COPY.md"), "# Test Repo").unwrap();
+
+        pack_repo_objects(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+
+        let verify_receipt = verify_pack(pack_dir.path(), &seed).unwrap();
+        assert!(verify_receipt.passed);
+
+        let unfurl_receipt = unfurl_pack(pack_dir.path(), unfurl_dir.path(), &seed).unwrap();
+        assert!(unfurl_receipt.passed);
+        assert!(unfurl_dir.path().join("README.md").exists());
+        assert!(unfurl_dir.path().join("COPY.md").exists());
+        assert_eq!(
+            std::fs::read_to_string(unfurl_dir.path().join("COPY.md")).unwrap(),
+            "# Test Repo"
+        );
+    }
+
+    #[test]
+    fn test_pack_objects_detects_missing_object() {
+        let repo_dir = TempDir::new().unwrap();
+        let pack_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        pack_repo_objects(repo_dir.path(), pack_dir.path(), &seed, None).unwrap();
+
+        // Delete every object to simulate corruption/truncation.
+        std::fs::remove_dir_all(pack_dir.path().join("data/objects")).unwrap();
+        std::fs::create_dir_all(pack_dir.path().join("data/objects")).unwrap();
+
+        let verify_receipt = verify_pack(pack_dir.path(), &seed).unwrap();
+        assert!(!verify_receipt.passed);
+    }
+
     #[test]
     fn test_shape_equivalence_different() {
         let a = TempDir::new().unwrap();
@@ -510,4 +2331,127 @@ mod tests {
         std::fs::write(b.path().join("f.txt"), "world").unwrap();
         assert!(!verify_shape_equivalence(a.path(), b.path()).unwrap());
     }
+
+    #[test]
+    fn test_pack_repo_delta_then_verify_and_unfurl_roundtrip() {
+        let repo_dir = TempDir::new().unwrap();
+        let base_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        pack_repo(repo_dir.path(), base_dir.path(), &seed, None, false).unwrap();
+        let base_manifest = load_base_manifest(base_dir.path()).unwrap();
+
+        // Change one file and add another, matching the base's unchanged remainder.
+        std::fs::write(repo_dir.path().join("README.md"), "# Test Repo v2").unwrap();
+        std::fs::write(repo_dir.path().join("src/lib.rs"), "pub fn hi() {}").unwrap();
+
+        let delta_dir = TempDir::new().unwrap();
+        let receipt = pack_repo_delta(
+            repo_dir.path(),
+            delta_dir.path(),
+            &seed,
+            None,
+            false,
+            &base_manifest,
+        )
+        .unwrap();
+        assert!(receipt.passed);
+        assert!(delta_dir.path().join("delta_manifest.json").exists());
+        assert!(delta_dir.path().join("data/README.md").exists());
+        assert!(delta_dir.path().join("data/src/lib.rs").exists());
+        assert!(!delta_dir.path().join("data/src/main.rs").exists());
+
+        let verify_receipt = verify_pack_delta(delta_dir.path(), &base_manifest, &seed).unwrap();
+        assert!(verify_receipt.passed);
+
+        let unfurl_dir = TempDir::new().unwrap();
+        let unfurl_receipt =
+            unfurl_pack_delta(delta_dir.path(), base_dir.path(), unfurl_dir.path(), &seed).unwrap();
+        assert!(unfurl_receipt.passed);
+        assert!(verify_shape_equivalence(repo_dir.path(), unfurl_dir.path()).unwrap());
+    }
+
+    #[test]
+    fn test_materialize_delta_pack_reconstructs_full_dpack_directory() {
+        let repo_dir = TempDir::new().unwrap();
+        let base_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        pack_repo(repo_dir.path(), base_dir.path(), &seed, None, false).unwrap();
+        let base_manifest = load_base_manifest(base_dir.path()).unwrap();
+
+        std::fs::write(repo_dir.path().join("README.md"), "# Test Repo v2").unwrap();
+
+        let delta_dir = TempDir::new().unwrap();
+        pack_repo_delta(
+            repo_dir.path(),
+            delta_dir.path(),
+            &seed,
+            None,
+            false,
+            &base_manifest,
+        )
+        .unwrap();
+
+        let full_dir = TempDir::new().unwrap();
+        let full_manifest =
+            materialize_delta_pack(delta_dir.path(), base_dir.path(), full_dir.path()).unwrap();
+
+        assert!(full_dir.path().join("manifest.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(full_dir.path().join("data/README.md")).unwrap(),
+            "# Test Repo v2"
+        );
+        assert_eq!(
+            std::fs::read_to_string(full_dir.path().join("data/src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+
+        let verify_receipt = verify_pack(full_dir.path(), &seed).unwrap();
+        assert!(verify_receipt.passed);
+        assert_eq!(
+            verify_receipt.pack_hash.unwrap(),
+            full_manifest.pack_hash
+        );
+    }
+
+    #[test]
+    fn test_verify_pack_delta_fails_closed_on_wrong_base() {
+        let repo_dir = TempDir::new().unwrap();
+        let base_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(repo_dir.path());
+
+        pack_repo(repo_dir.path(), base_dir.path(), &seed, None, false).unwrap();
+        let base_manifest = load_base_manifest(base_dir.path()).unwrap();
+
+        std::fs::write(repo_dir.path().join("README.md"), "# Test Repo v2").unwrap();
+        let delta_dir = TempDir::new().unwrap();
+        pack_repo_delta(
+            repo_dir.path(),
+            delta_dir.path(),
+            &seed,
+            None,
+            false,
+            &base_manifest,
+        )
+        .unwrap();
+
+        // Re-pack the (now-changed) repo as a stand-in for a different base:
+        // its pack_hash won't match the one the delta was actually computed
+        // against.
+        let different_base_dir = TempDir::new().unwrap();
+        pack_repo(
+            repo_dir.path(),
+            different_base_dir.path(),
+            &seed,
+            None,
+            false,
+        )
+        .unwrap();
+        let different_base_manifest = load_base_manifest(different_base_dir.path()).unwrap();
+
+        let verify_receipt =
+            verify_pack_delta(delta_dir.path(), &different_base_manifest, &seed).unwrap();
+        assert!(!verify_receipt.passed);
+    }
 }
@@ -1,7 +1,26 @@
 //! Pack/replication policy: allowlist and denylist for file inclusion.
+//!
+//! Patterns use gitignore semantics: a pattern with no `/` (other than a
+//! trailing one) matches its basename at any depth, a leading `/` anchors
+//! it to the pack root, a trailing `/` restricts it to directories, and a
+//! leading `!` re-includes a path an earlier pattern excluded. Exclude
+//! patterns are evaluated in order with **last-match-wins** — the final
+//! matching rule decides, not the first exclude.
+//!
+//! Policy files can be layered: `Policy::load` resolves an `includes:`
+//! list of other policy files (merged in include-then-local order) and an
+//! `unset:` list of exact-string overrides, flattening everything into a
+//! single [`Policy`] before pack time. See [`RawPolicyFile`].
 
+use seed_core::hash::HashScheme;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Longest `includes:` chain `Policy::load` will follow before giving up,
+/// as a backstop against pathologically deep (but acyclic) include
+/// stacks; cycles are caught separately via the `visited` set.
+const MAX_INCLUDE_DEPTH: usize = 32;
 
 /// Policy controlling which files are included in or excluded from packs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,46 +28,300 @@ pub struct Policy {
     /// Glob patterns for files to include. Empty means include all.
     #[serde(default)]
     pub include: Vec<String>,
-    /// Glob patterns for files to exclude. Applied after include.
+    /// Glob patterns for files to exclude, gitignore-style. Applied after
+    /// include, in order, with `!`-prefixed entries re-including a path
+    /// excluded by an earlier pattern.
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Path to a trusted TUF-style `root.json` (see
+    /// `replication_core::trust`) that replication signature gates verify
+    /// against. `None` means signature verification is not configured and
+    /// is skipped.
+    #[serde(default)]
+    pub trusted_root: Option<PathBuf>,
+    /// Hostnames an `R3_REMOTE_FETCH` redirect is allowed to land on when it
+    /// differs from the request's original host. Empty means no
+    /// cross-host redirect is permitted; the original host is always
+    /// implicitly allowed.
+    #[serde(default)]
+    pub allowed_redirect_hosts: Vec<String>,
+    /// When true, `replicate_rootball_zip` stores entries uncompressed
+    /// (`CompressionMethod::Stored`) instead of the default `Deflated`.
+    /// Deflate is smaller; store is faster to produce and read and skips a
+    /// second compression pass over already-compressed file content.
+    #[serde(default)]
+    pub zip_store_only: bool,
+    /// Algorithm + encoding used to hash pack content and manifest
+    /// fingerprints. Defaults to SHA-256 hex so packs produced before this
+    /// field existed stay verifiable. Stewards on large repos can switch to
+    /// BLAKE3 for speed; `verify`/`unfurl` always read the scheme recorded
+    /// in the manifest being checked rather than assuming this policy's
+    /// current value.
+    #[serde(default)]
+    pub hash_scheme: HashScheme,
+    /// `did:key` identifiers trusted as the root of a UCAN-style capability
+    /// delegation chain (see `replication_core::capability`). Empty means
+    /// capability authorization is not configured, and the RG1 gate falls
+    /// back to recording the seed binding with no authorization check.
+    #[serde(default)]
+    pub trusted_capability_roots: Vec<String>,
 }
 
 impl Default for Policy {
     fn default() -> Self {
         Self {
             include: vec![],
-            exclude: vec![
-                ".git/**".to_string(),
-                ".git".to_string(),
-            ],
+            exclude: vec![".git/**".to_string(), ".git".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
         }
     }
 }
 
 impl Policy {
-    /// Load policy from a YAML file.
+    /// Load a policy from a YAML file, resolving `includes:` layering and
+    /// `unset:` overrides (see [module docs](self) and
+    /// [`RawPolicyFile`]). The returned `Policy` is fully flattened, so
+    /// `pack_repo` behavior is unchanged once loading completes.
     pub fn load(path: &Path) -> Result<Self, PolicyError> {
+        let mut visited = HashSet::new();
+        Self::load_layered(path, &mut visited, 0)
+    }
+
+    fn load_layered(
+        path: &Path,
+        visited: &mut HashSet<PathBuf>,
+        depth: usize,
+    ) -> Result<Self, PolicyError> {
+        if depth > MAX_INCLUDE_DEPTH {
+            return Err(PolicyError::IncludeDepthExceeded(path.to_path_buf()));
+        }
+
+        let canonical = path
+            .canonicalize()
+            .map_err(|_| PolicyError::IncludeCycle(path.to_path_buf()))?;
+        if !visited.insert(canonical.clone()) {
+            return Err(PolicyError::IncludeCycle(canonical));
+        }
+
         let content = std::fs::read_to_string(path)?;
-        let policy: Self = serde_yaml::from_str(&content)?;
-        Ok(policy)
+        let raw: RawPolicyFile = serde_yaml::from_str(&content)?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        let mut trusted_root = None;
+        let mut allowed_redirect_hosts = Vec::new();
+        let mut zip_store_only = false;
+        let mut hash_scheme = None;
+        let mut trusted_capability_roots = Vec::new();
+        for rel in &raw.includes {
+            let layer = Self::load_layered(&base_dir.join(rel), visited, depth + 1)?;
+            include.extend(layer.include);
+            exclude.extend(layer.exclude);
+            trusted_root = trusted_root.or(layer.trusted_root);
+            allowed_redirect_hosts.extend(layer.allowed_redirect_hosts);
+            zip_store_only |= layer.zip_store_only;
+            hash_scheme = hash_scheme.or(Some(layer.hash_scheme));
+            trusted_capability_roots.extend(layer.trusted_capability_roots);
+        }
+        include.extend(raw.include);
+        exclude.extend(raw.exclude);
+        allowed_redirect_hosts.extend(raw.allowed_redirect_hosts);
+        zip_store_only |= raw.zip_store_only;
+        trusted_capability_roots.extend(raw.trusted_capability_roots);
+        if let Some(rel) = &raw.trusted_root {
+            trusted_root = Some(base_dir.join(rel));
+        }
+        if let Some(scheme) = raw.hash_scheme {
+            hash_scheme = Some(scheme);
+        }
+
+        for pattern in &raw.unset {
+            include.retain(|p| p != pattern);
+            exclude.retain(|p| p != pattern);
+        }
+
+        visited.remove(&canonical);
+        Ok(Self {
+            include,
+            exclude,
+            trusted_root,
+            allowed_redirect_hosts,
+            zip_store_only,
+            hash_scheme: hash_scheme.unwrap_or_default(),
+            trusted_capability_roots,
+        })
+    }
+
+    /// Compile this policy's patterns once, for cheap repeated matching
+    /// (e.g. across every file in a large `WalkDir` pass).
+    pub fn compile(&self) -> CompiledPolicy {
+        CompiledPolicy {
+            exclude: self.exclude.iter().map(|p| Pattern::compile(p)).collect(),
+            include: self.include.iter().map(|p| Pattern::compile(p)).collect(),
+        }
     }
 
     /// Check if a relative path is allowed by this policy.
+    ///
+    /// Compiles the policy on every call; prefer [`Policy::compile`] and
+    /// reuse the resulting [`CompiledPolicy`] when checking many paths.
+    pub fn is_allowed(&self, rel_path: &str) -> bool {
+        self.compile().is_allowed(rel_path)
+    }
+}
+
+/// On-disk shape of a policy YAML file, before layering is resolved.
+///
+/// Modeled on Mercurial's `%include` config layering: `includes` names
+/// other policy files (resolved relative to this file's directory) that
+/// are merged in include-then-local order, and `unset` removes a pattern
+/// — by exact string — contributed by an earlier layer, so an override
+/// file can re-enable something a base policy excluded.
+#[derive(Debug, Deserialize)]
+struct RawPolicyFile {
+    #[serde(default)]
+    includes: Vec<String>,
+    #[serde(default)]
+    unset: Vec<String>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    trusted_root: Option<String>,
+    #[serde(default)]
+    allowed_redirect_hosts: Vec<String>,
+    #[serde(default)]
+    zip_store_only: bool,
+    #[serde(default)]
+    hash_scheme: Option<HashScheme>,
+    #[serde(default)]
+    trusted_capability_roots: Vec<String>,
+}
+
+/// A [`Policy`] with its patterns compiled once, ready for repeated matching.
+#[derive(Debug, Clone)]
+pub struct CompiledPolicy {
+    exclude: Vec<Pattern>,
+    include: Vec<Pattern>,
+}
+
+impl CompiledPolicy {
+    /// Check if a relative path is allowed, applying last-match-wins
+    /// semantics over the exclude/negation rules, then the include filter.
     pub fn is_allowed(&self, rel_path: &str) -> bool {
-        // Check excludes first
+        let mut allowed = true;
         for pattern in &self.exclude {
-            if glob_match(pattern, rel_path) {
-                return false;
+            if pattern.matches(rel_path) {
+                allowed = !pattern.negated;
             }
         }
-        // If includes is empty, everything (not excluded) is allowed
+        if !allowed {
+            return false;
+        }
+
         if self.include.is_empty() {
             return true;
         }
-        // Otherwise must match at least one include pattern
-        for pattern in &self.include {
-            if glob_match(pattern, rel_path) {
+        self.include.iter().any(|p| p.matches(rel_path))
+    }
+}
+
+/// A single compiled gitignore-style pattern.
+#[derive(Debug, Clone)]
+struct Pattern {
+    negated: bool,
+    dir_only: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `**`: matches zero or more whole path segments.
+    DoubleStar,
+    /// A single path segment, itself a sequence of glob tokens.
+    Literal(Vec<GlobToken>),
+}
+
+#[derive(Debug, Clone)]
+enum GlobToken {
+    Char(char),
+    /// `*`: any run of characters except `/`.
+    Star,
+    /// `?`: a single character except `/`.
+    Question,
+    /// `[abc]`, `[a-z]`, `[!abc]`: a character class.
+    Class {
+        negate: bool,
+        singles: Vec<char>,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Pattern {
+    fn compile(raw: &str) -> Self {
+        let mut s = raw;
+        let negated = if let Some(rest) = s.strip_prefix('!') {
+            s = rest;
+            true
+        } else {
+            false
+        };
+
+        let dir_only = s.len() > 1 && s.ends_with('/');
+        if dir_only {
+            s = &s[..s.len() - 1];
+        }
+
+        let anchored_leading = s.starts_with('/');
+        let body = if anchored_leading { &s[1..] } else { s };
+        // A pattern with a slash anywhere but the end is anchored to the
+        // pack root; one with no slash at all matches at any depth.
+        let anchored = anchored_leading || body.contains('/');
+
+        let mut segments = Vec::new();
+        if !anchored {
+            segments.push(Segment::DoubleStar);
+        }
+        for seg in body.split('/') {
+            if seg == "**" {
+                segments.push(Segment::DoubleStar);
+            } else {
+                segments.push(Segment::Literal(compile_segment(seg)));
+            }
+        }
+
+        Self {
+            negated,
+            dir_only,
+            segments,
+        }
+    }
+
+    /// Whether this pattern matches `rel_path`, including the
+    /// directory-recursive rule: a pattern that matches an ancestor
+    /// directory also matches everything beneath it.
+    fn matches(&self, rel_path: &str) -> bool {
+        let path_segs: Vec<&str> = rel_path.split('/').collect();
+        if path_segs.is_empty() {
+            return false;
+        }
+
+        // A dir-only pattern can only match an ancestor directory, never
+        // the leaf path itself (we only ever match against files).
+        let max_k = if self.dir_only {
+            path_segs.len().saturating_sub(1)
+        } else {
+            path_segs.len()
+        };
+
+        for k in 1..=max_k {
+            if segs_match(&self.segments, &path_segs[..k]) {
                 return true;
             }
         }
@@ -56,48 +329,115 @@ impl Policy {
     }
 }
 
-/// Minimal glob matching: supports * (single segment) and ** (recursive).
-fn glob_match(pattern: &str, path: &str) -> bool {
-    // Handle exact match
-    if pattern == path {
-        return true;
+fn segs_match(pattern: &[Segment], path: &[&str]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(Segment::DoubleStar) => {
+            let rest = &pattern[1..];
+            if segs_match(rest, path) {
+                return true;
+            }
+            !path.is_empty() && segs_match(pattern, &path[1..])
+        }
+        Some(Segment::Literal(tokens)) => {
+            if path.is_empty() {
+                return false;
+            }
+            tokens_match(tokens, &path[0].chars().collect::<Vec<_>>()) && segs_match(&pattern[1..], &path[1..])
+        }
     }
+}
 
-    // Handle ** prefix (recursive match)
-    if let Some(suffix) = pattern.strip_prefix("**/") {
-        // Match suffix against any path suffix
-        if path.ends_with(suffix) {
-            return true;
-        }
-        // Also try matching at any directory level
-        for (i, _) in path.char_indices() {
-            if path[i..].starts_with('/') {
-                if glob_match(suffix, &path[i + 1..]) {
+fn tokens_match(tokens: &[GlobToken], text: &[char]) -> bool {
+    match tokens.first() {
+        None => text.is_empty(),
+        Some(GlobToken::Star) => {
+            let rest = &tokens[1..];
+            for k in 0..=text.len() {
+                if tokens_match(rest, &text[k..]) {
                     return true;
                 }
             }
+            false
+        }
+        Some(GlobToken::Question) => !text.is_empty() && tokens_match(&tokens[1..], &text[1..]),
+        Some(GlobToken::Char(c)) => {
+            !text.is_empty() && text[0] == *c && tokens_match(&tokens[1..], &text[1..])
+        }
+        Some(GlobToken::Class {
+            negate,
+            singles,
+            ranges,
+        }) => {
+            if text.is_empty() {
+                return false;
+            }
+            let ch = text[0];
+            let mut hit = singles.contains(&ch) || ranges.iter().any(|&(lo, hi)| ch >= lo && ch <= hi);
+            if *negate {
+                hit = !hit;
+            }
+            hit && tokens_match(&tokens[1..], &text[1..])
         }
-        return glob_match(suffix, path);
     }
+}
 
-    // Handle ** suffix (matches everything under a path)
-    if let Some(prefix) = pattern.strip_suffix("/**") {
-        return path.starts_with(prefix)
-            && (path.len() == prefix.len()
-                || path.as_bytes().get(prefix.len()) == Some(&b'/'));
+/// Compile a single path segment (no `/`) into glob tokens.
+fn compile_segment(seg: &str) -> Vec<GlobToken> {
+    let chars: Vec<char> = seg.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' => {
+                tokens.push(GlobToken::Star);
+                i += 1;
+            }
+            '?' => {
+                tokens.push(GlobToken::Question);
+                i += 1;
+            }
+            '[' => {
+                let mut j = i + 1;
+                let negate = j < chars.len() && (chars[j] == '!' || chars[j] == '^');
+                if negate {
+                    j += 1;
+                }
+                let start = j;
+                while j < chars.len() && chars[j] != ']' {
+                    j += 1;
+                }
+                let (singles, ranges) = parse_class_body(&chars[start..j]);
+                tokens.push(GlobToken::Class {
+                    negate,
+                    singles,
+                    ranges,
+                });
+                i = if j < chars.len() { j + 1 } else { j };
+            }
+            c => {
+                tokens.push(GlobToken::Char(c));
+                i += 1;
+            }
+        }
     }
+    tokens
+}
 
-    // Handle single * wildcard
-    if pattern.contains('*') {
-        let parts: Vec<&str> = pattern.split('*').collect();
-        if parts.len() == 2 {
-            return path.starts_with(parts[0])
-                && path.ends_with(parts[1])
-                && !path[parts[0].len()..path.len() - parts[1].len()].contains('/');
+fn parse_class_body(chars: &[char]) -> (Vec<char>, Vec<(char, char)>) {
+    let mut singles = Vec::new();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            ranges.push((chars[i], chars[i + 2]));
+            i += 3;
+        } else {
+            singles.push(chars[i]);
+            i += 1;
         }
     }
-
-    false
+    (singles, ranges)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -106,11 +446,16 @@ pub enum PolicyError {
     Io(#[from] std::io::Error),
     #[error("YAML parse error: {0}")]
     Yaml(#[from] serde_yaml::Error),
+    #[error("policy include cycle detected at {0}")]
+    IncludeCycle(PathBuf),
+    #[error("policy include depth exceeded {MAX_INCLUDE_DEPTH} at {0}; check for a long include chain")]
+    IncludeDepthExceeded(PathBuf),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn test_default_policy_excludes_git() {
@@ -121,21 +466,164 @@ mod tests {
     }
 
     #[test]
-    fn test_glob_match_star() {
-        assert!(glob_match("*.rs", "main.rs"));
-        assert!(!glob_match("*.rs", "src/main.rs"));
+    fn test_pattern_star_matches_any_depth() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["*.rs".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("main.rs"));
+        assert!(!policy.is_allowed("src/main.rs"), "no-slash pattern matches basename at any depth");
     }
 
     #[test]
-    fn test_glob_match_double_star_prefix() {
-        assert!(glob_match("**/*.rs", "src/main.rs"));
-        assert!(glob_match("**/*.rs", "a/b/c/main.rs"));
+    fn test_pattern_double_star_prefix() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["**/*.rs".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("src/main.rs"));
+        assert!(!policy.is_allowed("a/b/c/main.rs"));
+    }
+
+    #[test]
+    fn test_pattern_double_star_suffix() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec![".git/**".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed(".git/objects/abc"));
+        assert!(policy.is_allowed("src/main.rs"));
+    }
+
+    #[test]
+    fn test_pattern_anchored_leading_slash() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["/build".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("build"));
+        assert!(policy.is_allowed("src/build"), "leading / anchors to the root");
+    }
+
+    #[test]
+    fn test_pattern_directory_only_suffix() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["logs/".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("logs/today.txt"));
+        assert!(policy.is_allowed("logs"), "dir-only pattern never matches the leaf path itself");
+    }
+
+    #[test]
+    fn test_pattern_negation_reincludes() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["*.log".to_string(), "!important.log".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("debug.log"));
+        assert!(policy.is_allowed("important.log"), "later ! rule wins (last-match-wins)");
+    }
+
+    #[test]
+    fn test_pattern_last_match_wins_reexclusion() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["!keep.log".to_string(), "*.log".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        // The broader *.log comes after the negation, so it wins.
+        assert!(!policy.is_allowed("keep.log"));
+    }
+
+    #[test]
+    fn test_pattern_character_class() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["file[0-9].tmp".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("file1.tmp"));
+        assert!(policy.is_allowed("fileA.tmp"));
+    }
+
+    #[test]
+    fn test_pattern_negated_character_class() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["file[!0-9].tmp".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("fileA.tmp"));
+        assert!(policy.is_allowed("file1.tmp"));
     }
 
     #[test]
-    fn test_glob_match_double_star_suffix() {
-        assert!(glob_match(".git/**", ".git/objects/abc"));
-        assert!(!glob_match(".git/**", "src/main.rs"));
+    fn test_pattern_question_mark() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["a?c".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        assert!(!policy.is_allowed("abc"));
+        assert!(policy.is_allowed("ac"));
+        assert!(policy.is_allowed("abbc"));
     }
 
     #[test]
@@ -143,9 +631,174 @@ mod tests {
         let policy = Policy {
             include: vec!["*.rs".to_string(), "Cargo.toml".to_string()],
             exclude: vec![],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
         };
         assert!(policy.is_allowed("main.rs"));
         assert!(policy.is_allowed("Cargo.toml"));
         assert!(!policy.is_allowed("README.md"));
     }
+
+    #[test]
+    fn test_compiled_policy_reused_across_calls() {
+        let policy = Policy {
+            include: vec![],
+            exclude: vec!["*.tmp".to_string()],
+            trusted_root: None,
+            allowed_redirect_hosts: vec![],
+
+            zip_store_only: false,
+            hash_scheme: HashScheme::default(),
+            trusted_capability_roots: vec![],
+        };
+        let compiled = policy.compile();
+        assert!(!compiled.is_allowed("a.tmp"));
+        assert!(compiled.is_allowed("a.rs"));
+    }
+
+    #[test]
+    fn test_load_layered_includes_merge_in_order() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "exclude:\n  - \"*.log\"\n  - \"*.tmp\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("local.yaml"),
+            "includes:\n  - base.yaml\nexclude:\n  - \"*.bak\"\n",
+        )
+        .unwrap();
+
+        let policy = Policy::load(&dir.path().join("local.yaml")).unwrap();
+        assert_eq!(policy.exclude, vec!["*.log", "*.tmp", "*.bak"]);
+        assert!(!policy.is_allowed("debug.log"));
+        assert!(!policy.is_allowed("archive.bak"));
+        assert!(policy.is_allowed("main.rs"));
+    }
+
+    #[test]
+    fn test_load_layered_includes_resolve_relative_to_including_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("base")).unwrap();
+        std::fs::create_dir_all(dir.path().join("project")).unwrap();
+        std::fs::write(
+            dir.path().join("base/org.yaml"),
+            "exclude:\n  - \"*.secret\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("project/local.yaml"),
+            "includes:\n  - ../base/org.yaml\n",
+        )
+        .unwrap();
+
+        let policy = Policy::load(&dir.path().join("project/local.yaml")).unwrap();
+        assert!(!policy.is_allowed("creds.secret"));
+    }
+
+    #[test]
+    fn test_load_layered_allowed_redirect_hosts_merge_in_order() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "allowed_redirect_hosts:\n  - mirror.example.org\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("local.yaml"),
+            "includes:\n  - base.yaml\nallowed_redirect_hosts:\n  - cdn.example.com\n",
+        )
+        .unwrap();
+
+        let policy = Policy::load(&dir.path().join("local.yaml")).unwrap();
+        assert_eq!(
+            policy.allowed_redirect_hosts,
+            vec!["mirror.example.org", "cdn.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_load_layered_hash_scheme_local_overrides_base() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "hash_scheme:\n  algorithm: Sha512\n  encoding: Hex\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("local.yaml"),
+            "includes:\n  - base.yaml\nhash_scheme:\n  algorithm: Blake3\n  encoding: Base32\n",
+        )
+        .unwrap();
+
+        let policy = Policy::load(&dir.path().join("local.yaml")).unwrap();
+        assert_eq!(policy.hash_scheme.algorithm, seed_core::hash::HashAlgorithm::Blake3);
+        assert_eq!(policy.hash_scheme.encoding, seed_core::hash::HashEncoding::Base32);
+    }
+
+    #[test]
+    fn test_load_layered_hash_scheme_inherits_from_base_when_unset_locally() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "hash_scheme:\n  algorithm: Sha512\n  encoding: Hex\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("local.yaml"), "includes:\n  - base.yaml\n").unwrap();
+
+        let policy = Policy::load(&dir.path().join("local.yaml")).unwrap();
+        assert_eq!(policy.hash_scheme.algorithm, seed_core::hash::HashAlgorithm::Sha512);
+    }
+
+    #[test]
+    fn test_load_layered_unset_reenables_base_exclude() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("base.yaml"),
+            "exclude:\n  - \"*.log\"\n  - \"*.tmp\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("override.yaml"),
+            "includes:\n  - base.yaml\nunset:\n  - \"*.log\"\n",
+        )
+        .unwrap();
+
+        let policy = Policy::load(&dir.path().join("override.yaml")).unwrap();
+        assert_eq!(policy.exclude, vec!["*.tmp"]);
+        assert!(policy.is_allowed("debug.log"), "unset re-enables a base exclude");
+        assert!(!policy.is_allowed("cache.tmp"));
+    }
+
+    #[test]
+    fn test_load_layered_detects_include_cycle() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.yaml"), "includes:\n  - b.yaml\n").unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "includes:\n  - a.yaml\n").unwrap();
+
+        let err = Policy::load(&dir.path().join("a.yaml")).unwrap_err();
+        assert!(matches!(err, PolicyError::IncludeCycle(_)));
+    }
+
+    #[test]
+    fn test_load_layered_rejects_include_chain_past_max_depth() {
+        let dir = TempDir::new().unwrap();
+        let chain_len = MAX_INCLUDE_DEPTH + 2;
+        for i in 0..chain_len {
+            let contents = if i + 1 < chain_len {
+                format!("includes:\n  - layer{}.yaml\n", i + 1)
+            } else {
+                "exclude: []\n".to_string()
+            };
+            std::fs::write(dir.path().join(format!("layer{i}.yaml")), contents).unwrap();
+        }
+
+        let err = Policy::load(&dir.path().join("layer0.yaml")).unwrap_err();
+        assert!(matches!(err, PolicyError::IncludeDepthExceeded(_)));
+    }
 }
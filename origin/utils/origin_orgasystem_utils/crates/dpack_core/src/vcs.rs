@@ -0,0 +1,105 @@
+//! Git VCS provenance capture, in the spirit of `cargo package`'s
+//! `.cargo_vcs_info.json`: record the HEAD commit, branch/tag, and
+//! working-tree cleanliness at pack time.
+//!
+//! Detection shells out to the `git` binary rather than adding a VCS
+//! library dependency, so it degrades gracefully to `None` whenever `git`
+//! is unavailable or `repo_root` isn't a checkout at all.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+/// VCS provenance recorded in a [`crate::manifest::DpackManifest`] at pack time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VcsProvenance {
+    /// HEAD commit id (full hex SHA).
+    pub commit_id: String,
+    /// Branch or tag name, or `"HEAD"` if detached.
+    pub reference: String,
+    /// Whether the working tree had uncommitted or untracked changes.
+    pub dirty: bool,
+}
+
+/// Detect git VCS provenance for `repo_root`.
+///
+/// Returns `None` if `repo_root` is not a git checkout or the `git` binary
+/// is unavailable — callers should treat this as "provenance unknown", not
+/// an error.
+pub fn detect_vcs_provenance(repo_root: &Path) -> Option<VcsProvenance> {
+    let commit_id = run_git(repo_root, &["rev-parse", "HEAD"])?;
+    let reference = run_git(repo_root, &["symbolic-ref", "--short", "-q", "HEAD"])
+        .unwrap_or_else(|| "HEAD".to_string());
+    let status = run_git(repo_root, &["status", "--porcelain"])?;
+    let dirty = !status.is_empty();
+
+    Some(VcsProvenance {
+        commit_id,
+        reference,
+        dirty,
+    })
+}
+
+fn run_git(repo_root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_root)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    fn init_clean_repo(repo: &Path) {
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "test@test.com"]);
+        git(repo, &["config", "user.name", "test"]);
+        std::fs::write(repo.join("a.txt"), "hi").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+    }
+
+    #[test]
+    fn test_non_repo_returns_none() {
+        let dir = TempDir::new().unwrap();
+        assert!(detect_vcs_provenance(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_clean_checkout_detected() {
+        let dir = TempDir::new().unwrap();
+        init_clean_repo(dir.path());
+
+        let provenance = detect_vcs_provenance(dir.path()).unwrap();
+        assert!(!provenance.dirty);
+        assert_eq!(provenance.commit_id.len(), 40);
+    }
+
+    #[test]
+    fn test_dirty_checkout_detected() {
+        let dir = TempDir::new().unwrap();
+        init_clean_repo(dir.path());
+        std::fs::write(dir.path().join("b.txt"), "uncommitted").unwrap();
+
+        let provenance = detect_vcs_provenance(dir.path()).unwrap();
+        assert!(provenance.dirty);
+    }
+}
@@ -7,7 +7,7 @@
 //! - No floating-point ambiguity (all strings/ints/bools).
 
 use crate::denotum::Denotum;
-use sha2::{Digest, Sha256};
+use seed_core::hash::HashScheme;
 
 /// Serialize a Denotum to canonical JSON bytes.
 ///
@@ -24,12 +24,14 @@ pub fn canonical_json_pretty(d: &Denotum) -> Result<Vec<u8>, serde_json::Error>
     serde_json::to_vec_pretty(d)
 }
 
-/// Compute the SHA-256 fingerprint of a Denotum's canonical form.
-pub fn canonical_fingerprint(d: &Denotum) -> Result<String, serde_json::Error> {
+/// Compute the fingerprint of a Denotum's canonical form under `scheme`.
+///
+/// Pass [`HashScheme::default()`] for the original SHA-256 hex fingerprint;
+/// stewards on BLAKE3 packs should pass the scheme carried by the manifest
+/// they're validating against so the fingerprint stays comparable.
+pub fn canonical_fingerprint(d: &Denotum, scheme: &HashScheme) -> Result<String, serde_json::Error> {
     let bytes = canonical_json(d)?;
-    let mut hasher = Sha256::new();
-    hasher.update(&bytes);
-    Ok(hex::encode(hasher.finalize()))
+    Ok(scheme.digest(&bytes))
 }
 
 #[cfg(test)]
@@ -93,8 +95,9 @@ mod tests {
     #[test]
     fn test_fingerprint_stable() {
         let d = test_denotum();
-        let fp1 = canonical_fingerprint(&d).unwrap();
-        let fp2 = canonical_fingerprint(&d).unwrap();
+        let scheme = HashScheme::default();
+        let fp1 = canonical_fingerprint(&d, &scheme).unwrap();
+        let fp2 = canonical_fingerprint(&d, &scheme).unwrap();
         assert_eq!(fp1, fp2);
         assert_eq!(fp1.len(), 64);
     }
@@ -104,8 +107,22 @@ mod tests {
         let d1 = test_denotum();
         let mut d2 = test_denotum();
         d2.version = "v2.0".to_string();
-        let fp1 = canonical_fingerprint(&d1).unwrap();
-        let fp2 = canonical_fingerprint(&d2).unwrap();
+        let scheme = HashScheme::default();
+        let fp1 = canonical_fingerprint(&d1, &scheme).unwrap();
+        let fp2 = canonical_fingerprint(&d2, &scheme).unwrap();
+        assert_ne!(fp1, fp2);
+    }
+
+    #[test]
+    fn test_fingerprint_changes_on_scheme() {
+        let d = test_denotum();
+        let sha256 = HashScheme::default();
+        let blake3 = HashScheme {
+            algorithm: seed_core::hash::HashAlgorithm::Blake3,
+            encoding: seed_core::hash::HashEncoding::Hex,
+        };
+        let fp1 = canonical_fingerprint(&d, &sha256).unwrap();
+        let fp2 = canonical_fingerprint(&d, &blake3).unwrap();
         assert_ne!(fp1, fp2);
     }
 }
@@ -0,0 +1,435 @@
+//! UCAN-style capability delegation tokens gating [`crate::denotum::Prism`]
+//! operators against [`crate::denotum::PostureLadder`] levels.
+//!
+//! A [`Token`] grants a set of [`Cap`]s from `issuer` to `audience`, each
+//! `Cap` pairing a posture-ladder resource (e.g. `posture:L2`) with an
+//! operator ability (e.g. `op:FRAME`). A holder may re-delegate further,
+//! narrowing (never broadening) what it passes on, and attaching the
+//! parent token as `proof`. [`verify_token`] walks that chain root-to-leaf
+//! and fails closed: absent or expired tokens grant nothing, which callers
+//! should treat as posture L0.
+//!
+//! This mirrors `replication_core::capability`'s UCAN design (same
+//! `did:key:<hex ed25519 pubkey>` identifiers, same attenuate-by-prefix
+//! rule, same canonical-bytes-with-signature-cleared signing scheme) but
+//! scopes capabilities to free-form posture/operator strings instead of a
+//! closed `Ability` enum, since the posture ladder and its operators are
+//! defined per-seed rather than fixed by this crate.
+
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// A `did:key:<hex ed25519 pubkey>` identifier.
+pub type Did = String;
+
+/// Prefix for this repo's simplified `did:key` identifiers.
+pub const DID_KEY_PREFIX: &str = "did:key:";
+
+/// One granted capability: an operator ability over a posture-ladder
+/// resource scope, e.g. `resource: "posture:L2", ability: "op:FRAME"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cap {
+    pub resource: String,
+    pub ability: String,
+}
+
+impl Cap {
+    /// True if `self` authorizes exactly `resource`/`ability`: `self.ability`
+    /// is a prefix of (or equal to) `ability`, and `self.resource` is a
+    /// prefix of (or equal to) `resource`.
+    fn permits(&self, resource: &str, ability: &str) -> bool {
+        resource.starts_with(&self.resource) && ability.starts_with(&self.ability)
+    }
+
+    /// True if `self` is at least as narrow as `parent`: never a broader
+    /// resource scope or ability.
+    fn attenuates(&self, parent: &Cap) -> bool {
+        self.resource.starts_with(&parent.resource) && self.ability.starts_with(&parent.ability)
+    }
+}
+
+/// A UCAN-style bearer token: `issuer` delegates `capabilities` to
+/// `audience`, valid within `[not_before, expires_at)`, attested by `proof`
+/// (the parent token this one was delegated from - `None` for a
+/// self-issued root) and `signature` (hex ed25519, over this token's
+/// canonical bytes with `signature` itself cleared).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Token {
+    pub issuer: Did,
+    pub audience: Did,
+    pub capabilities: Vec<Cap>,
+    /// Unix timestamp (seconds); the token is invalid before this.
+    pub not_before: i64,
+    /// Unix timestamp (seconds); the token is invalid at or after this.
+    pub expires_at: i64,
+    #[serde(default)]
+    pub proof: Option<Box<Token>>,
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TokenError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("hex decode error: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("token is unsigned")]
+    Unsigned,
+    #[error("malformed did:key or signature: {0}")]
+    Malformed(String),
+    #[error("signature by issuer {issuer} failed to verify")]
+    InvalidSignature { issuer: Did },
+    #[error("token audience {audience} does not match next issuer {next_issuer}")]
+    AudienceMismatch { audience: Did, next_issuer: Did },
+    #[error("capability {resource}/{ability} is not attenuated from its proof")]
+    NotAttenuated { resource: String, ability: String },
+    #[error("token issued by {issuer} is outside its validity window at {now}")]
+    Expired { issuer: Did, now: i64 },
+    #[error("root issuer {issuer} does not match the trusted seed steward")]
+    UntrustedSteward { issuer: Did },
+    #[error("no capability in the token grants {resource}/{ability}")]
+    NotGranted { resource: String, ability: String },
+}
+
+/// This repo's `did:key` for an ed25519 public key: `did:key:<hex bytes>`.
+pub fn did_key_of(verifying_key: &VerifyingKey) -> Did {
+    format!("{DID_KEY_PREFIX}{}", hex::encode(verifying_key.as_bytes()))
+}
+
+fn verifying_key_from_did(did: &str) -> Result<VerifyingKey, TokenError> {
+    let hex_key = did
+        .strip_prefix(DID_KEY_PREFIX)
+        .ok_or_else(|| TokenError::Malformed(format!("{did} is not a did:key")))?;
+    let key_bytes: [u8; 32] = hex::decode(hex_key)?
+        .try_into()
+        .map_err(|_| TokenError::Malformed(format!("did:key {did} is not a 32-byte ed25519 key")))?;
+    VerifyingKey::from_bytes(&key_bytes).map_err(|e| TokenError::Malformed(e.to_string()))
+}
+
+/// Canonical bytes signed for a token: itself (including its `proof`, whose
+/// signature is already fixed) with its own `signature` field cleared.
+fn canonical_bytes(token: &Token) -> Result<Vec<u8>, TokenError> {
+    let mut unsigned = token.clone();
+    unsigned.signature = None;
+    Ok(serde_json::to_vec(&unsigned)?)
+}
+
+impl Token {
+    /// Issue a self-issued root token: `issuer_key` (the seed steward's
+    /// key) grants `capabilities` to `audience` directly, with no proof
+    /// chain.
+    pub fn issue_root(
+        issuer_key: &SigningKey,
+        audience: &Did,
+        capabilities: Vec<Cap>,
+        not_before: i64,
+        expires_at: i64,
+    ) -> Token {
+        let mut token = Token {
+            issuer: did_key_of(&issuer_key.verifying_key()),
+            audience: audience.clone(),
+            capabilities,
+            not_before,
+            expires_at,
+            proof: None,
+            signature: None,
+        };
+        token.sign(issuer_key);
+        token
+    }
+
+    /// Delegate from `self` (the holder re-delegating as `issuer_key`, which
+    /// must match `self.audience`) to `audience`, narrowing to
+    /// `capabilities`. The caller is responsible for ensuring `capabilities`
+    /// attenuates `self`'s grants - [`verify_token`] checks this on the
+    /// receiving end regardless.
+    pub fn delegate(
+        &self,
+        issuer_key: &SigningKey,
+        audience: &Did,
+        capabilities: Vec<Cap>,
+        not_before: i64,
+        expires_at: i64,
+    ) -> Token {
+        let mut token = Token {
+            issuer: did_key_of(&issuer_key.verifying_key()),
+            audience: audience.clone(),
+            capabilities,
+            not_before,
+            expires_at,
+            proof: Some(Box::new(self.clone())),
+            signature: None,
+        };
+        token.sign(issuer_key);
+        token
+    }
+
+    fn sign(&mut self, issuer_key: &SigningKey) {
+        self.signature = None;
+        let body = serde_json::to_vec(self).expect("Token always serializes");
+        self.signature = Some(hex::encode(issuer_key.sign(&body).to_bytes()));
+    }
+}
+
+/// The effective capability set granted by a successful [`verify_token`]
+/// call, plus chain bookkeeping for audit trails.
+pub struct Grant {
+    pub chain_len: usize,
+    pub root_issuer: Did,
+    pub granted: Vec<Cap>,
+}
+
+/// Verify that `token` grants `ability` over `resource`, with the chain
+/// terminating in a root token issued by `trusted_steward`. Fails closed:
+/// any problem anywhere in the chain rejects the whole token, not just the
+/// offending link - callers should treat an `Err` (or an absent token) as
+/// posture L0.
+pub fn verify_token(
+    token: &Token,
+    resource: &str,
+    ability: &str,
+    trusted_steward: &Did,
+    now: i64,
+) -> Result<Grant, TokenError> {
+    // Walk from the leaf (the token presented) down through `proof` to the
+    // root (the self-issued token with no proof), then verify root-to-leaf.
+    let mut chain = vec![token];
+    while let Some(parent) = chain.last().expect("chain always has at least the leaf").proof.as_deref() {
+        chain.push(parent);
+    }
+    chain.reverse(); // root ..= leaf
+
+    let root = chain[0];
+    if &root.issuer != trusted_steward {
+        return Err(TokenError::UntrustedSteward {
+            issuer: root.issuer.clone(),
+        });
+    }
+
+    for (i, current) in chain.iter().enumerate() {
+        let signature = current.signature.as_deref().ok_or(TokenError::Unsigned)?;
+        let verifying_key = verifying_key_from_did(&current.issuer)?;
+        let sig_bytes: [u8; 64] = hex::decode(signature)?
+            .try_into()
+            .map_err(|_| TokenError::Malformed(format!("signature {signature} is not 64 bytes")))?;
+        let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+        let body = canonical_bytes(current)?;
+        verifying_key
+            .verify(&body, &sig)
+            .map_err(|_| TokenError::InvalidSignature {
+                issuer: current.issuer.clone(),
+            })?;
+
+        if now < current.not_before || now >= current.expires_at {
+            return Err(TokenError::Expired {
+                issuer: current.issuer.clone(),
+                now,
+            });
+        }
+
+        if let Some(child) = chain.get(i + 1) {
+            if current.audience != child.issuer {
+                return Err(TokenError::AudienceMismatch {
+                    audience: current.audience.clone(),
+                    next_issuer: child.issuer.clone(),
+                });
+            }
+            for cap in &child.capabilities {
+                let covered = current.capabilities.iter().any(|parent_cap| cap.attenuates(parent_cap));
+                if !covered {
+                    return Err(TokenError::NotAttenuated {
+                        resource: cap.resource.clone(),
+                        ability: cap.ability.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    let leaf = chain.last().expect("chain always has at least the leaf");
+    if !leaf.capabilities.iter().any(|c| c.permits(resource, ability)) {
+        return Err(TokenError::NotGranted {
+            resource: resource.to_string(),
+            ability: ability.to_string(),
+        });
+    }
+
+    Ok(Grant {
+        chain_len: chain.len(),
+        root_issuer: root.issuer.clone(),
+        granted: leaf.capabilities.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cap(resource: &str, ability: &str) -> Cap {
+        Cap {
+            resource: resource.to_string(),
+            ability: ability.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_self_issued_root_verifies() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+        let steward_did = did_key_of(&steward_key.verifying_key());
+
+        let grant = verify_token(&root, "posture:L2", "op:FRAME", &steward_did, 500).unwrap();
+        assert_eq!(grant.chain_len, 1);
+        assert_eq!(grant.root_issuer, steward_did);
+    }
+
+    #[test]
+    fn test_delegation_chain_verifies() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+        let steward_did = did_key_of(&steward_key.verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+        let leaf = root.delegate(
+            &holder_key,
+            &leaf_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+
+        let grant = verify_token(&leaf, "posture:L2", "op:FRAME", &steward_did, 500).unwrap();
+        assert_eq!(grant.chain_len, 2);
+    }
+
+    #[test]
+    fn test_rejects_broadened_resource() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+        let steward_did = did_key_of(&steward_key.verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L1", "op:FRAME")],
+            0,
+            1_000,
+        );
+        // L2 is not a narrowing of L1 - not a valid attenuation.
+        let leaf = root.delegate(
+            &holder_key,
+            &leaf_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+
+        let err = verify_token(&leaf, "posture:L2", "op:FRAME", &steward_did, 500).unwrap_err();
+        assert!(matches!(err, TokenError::NotAttenuated { .. }));
+    }
+
+    #[test]
+    fn test_rejects_audience_mismatch() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let stranger_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+        let steward_did = did_key_of(&steward_key.verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+        // Signed by a stranger, not the `holder_key` the root delegated to.
+        let leaf = root.delegate(
+            &stranger_key,
+            &leaf_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+
+        let err = verify_token(&leaf, "posture:L2", "op:FRAME", &steward_did, 500).unwrap_err();
+        assert!(matches!(err, TokenError::AudienceMismatch { .. }));
+    }
+
+    #[test]
+    fn test_rejects_expired_token() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+        let steward_did = did_key_of(&steward_key.verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+
+        let err = verify_token(&root, "posture:L2", "op:FRAME", &steward_did, 1_000).unwrap_err();
+        assert!(matches!(err, TokenError::Expired { .. }));
+    }
+
+    #[test]
+    fn test_rejects_untrusted_steward() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let impostor_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L2", "op:FRAME")],
+            0,
+            1_000,
+        );
+
+        let err = verify_token(&root, "posture:L2", "op:FRAME", &impostor_did, 500).unwrap_err();
+        assert!(matches!(err, TokenError::UntrustedSteward { .. }));
+    }
+
+    #[test]
+    fn test_not_granted_rejected() {
+        let steward_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+        let steward_did = did_key_of(&steward_key.verifying_key());
+
+        let root = Token::issue_root(
+            &steward_key,
+            &holder_did,
+            vec![cap("posture:L1", "op:FRAME")],
+            0,
+            1_000,
+        );
+
+        let err = verify_token(&root, "posture:L2", "op:FRAME", &steward_did, 500).unwrap_err();
+        assert!(matches!(err, TokenError::NotGranted { .. }));
+    }
+}
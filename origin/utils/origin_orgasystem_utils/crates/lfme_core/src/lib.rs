@@ -5,12 +5,15 @@
 //! - Parser for YAML/JSON input
 //! - Validator enforcing seed invariants (fail-closed)
 //! - Canonical serialization (stable, deterministic)
+//! - Capability tokens gating Prism operators against posture ladder levels
 
 pub mod canonical;
+pub mod capability;
 pub mod denotum;
 pub mod parser;
 pub mod validator;
 
+pub use capability::{did_key_of, verify_token, Cap, Did, Grant, Token, TokenError};
 pub use denotum::{
     Axiom, Beam, BlockerRegistry, Denotum, GlossaryEntry, Lattice, Layer, PostureLadder, Prism,
 };
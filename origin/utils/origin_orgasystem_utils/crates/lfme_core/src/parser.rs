@@ -1,8 +1,20 @@
 //! Parser: load Denotum seeds from YAML or JSON.
+//!
+//! A seed file's body may also carry composition directives, processed
+//! line-by-line before the YAML is parsed:
+//! - `%include <relative-path>` recursively loads and merges another seed,
+//!   resolved against the including file's directory.
+//! - `%unset <SECTION.KEY>` removes an entry inherited from an include
+//!   (e.g. `%unset GLOSSARY.2I` or `%unset POSTURE_LADDER.levels.L2`).
+//!
+//! Merging is last-wins per map key across `GLOSSARY`, `AXIOMS`,
+//! `POSTURE_LADDER.levels`, and the layer list (keyed by layer name):
+//! includes are merged in document order, then the including file's own
+//! body is merged on top, so a child seed overrides its base(s).
 
 use crate::denotum::*;
 use std::collections::BTreeMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ParseError {
@@ -16,16 +28,223 @@ pub enum ParseError {
     UnsupportedFormat(String),
     #[error("missing required field: {0}")]
     MissingField(String),
+    #[error("include cycle detected at {0}")]
+    IncludeCycle(String),
+}
+
+/// Canonical layer ordering, used both to recognize layer sections in raw
+/// YAML and to lay out the merged `layers` vec deterministically.
+const LAYER_NAMES: [&str; 6] = ["OI", "SGS", "STANGRAPHICS", "GSI", "NSCE", "2I"];
+
+/// One seed's fields, gathered before merging with its includes.
+///
+/// Scalars are `Option` so that a seed which doesn't mention a field (e.g.
+/// a child seed that only overrides the glossary) doesn't clobber a value
+/// inherited from an include with a made-up default; defaults are applied
+/// once, at the end of the whole composition chain.
+#[derive(Default)]
+struct Accumulator {
+    version: Option<String>,
+    steward: Option<String>,
+    posture: Option<String>,
+    stop_wins: Option<bool>,
+    glossary: BTreeMap<String, GlossaryEntry>,
+    axioms: BTreeMap<String, Axiom>,
+    posture_levels: BTreeMap<String, String>,
+    degrade_rule: Option<String>,
+    layers: BTreeMap<String, Layer>,
+    blocker_registry: Option<BlockerRegistry>,
+}
+
+impl Accumulator {
+    /// Merge `other` on top of `self`: `other`'s scalars and map entries
+    /// win on conflicting keys, everything else is preserved.
+    fn merge(&mut self, other: Accumulator) {
+        if other.version.is_some() {
+            self.version = other.version;
+        }
+        if other.steward.is_some() {
+            self.steward = other.steward;
+        }
+        if other.posture.is_some() {
+            self.posture = other.posture;
+        }
+        if other.stop_wins.is_some() {
+            self.stop_wins = other.stop_wins;
+        }
+        self.glossary.extend(other.glossary);
+        self.axioms.extend(other.axioms);
+        self.posture_levels.extend(other.posture_levels);
+        if other.degrade_rule.is_some() {
+            self.degrade_rule = other.degrade_rule;
+        }
+        self.layers.extend(other.layers);
+        if other.blocker_registry.is_some() {
+            self.blocker_registry = other.blocker_registry;
+        }
+    }
+
+    /// Apply a `%unset SECTION.KEY` directive, removing an inherited entry.
+    fn apply_unset(&mut self, key: &str) {
+        let Some((section, rest)) = key.split_once('.') else {
+            return;
+        };
+        match section {
+            "GLOSSARY" => {
+                self.glossary.remove(rest);
+            }
+            "AXIOMS" => {
+                self.axioms.remove(rest);
+            }
+            "LAYERS" => {
+                self.layers.remove(rest);
+            }
+            "POSTURE_LADDER" => {
+                if let Some(level_key) = rest.strip_prefix("levels.") {
+                    self.posture_levels.remove(level_key);
+                } else if rest == "degrade_rule" {
+                    self.degrade_rule = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolve scalars to their defaults and produce the final typed seed.
+    fn finalize(self) -> Denotum {
+        Denotum {
+            version: self.version.unwrap_or_else(|| "v1.0".to_string()),
+            steward: self.steward.unwrap_or_else(|| "unknown".to_string()),
+            posture: self.posture.unwrap_or_else(|| "FAIL_CLOSED".to_string()),
+            stop_wins: self.stop_wins.unwrap_or(true),
+            glossary: self.glossary,
+            axioms: self.axioms,
+            posture_ladder: PostureLadder {
+                levels: self.posture_levels,
+                degrade_rule: self.degrade_rule.unwrap_or_default(),
+            },
+            layers: LAYER_NAMES
+                .iter()
+                .filter_map(|name| self.layers.get(*name).cloned())
+                .collect(),
+            beams: vec![],
+            lattices: vec![],
+            prisms: vec![],
+            blocker_registry: self.blocker_registry.unwrap_or_default(),
+        }
+    }
 }
 
 /// Parse a Denotum seed from a YAML string (the canonical 2I seed format).
 ///
 /// The raw YAML uses a non-standard layout; this parser maps it to the
 /// typed Denotum struct. Non-YAML header lines (like `DENOTUM::SEED::2I`)
-/// are stripped before parsing.
+/// are stripped before parsing. `%include`/`%unset` directives (see the
+/// module docs) are resolved relative to the current directory, since a
+/// bare string has no filesystem location of its own; use
+/// [`parse_seed_file`] so includes resolve against the seed's own directory.
 pub fn parse_seed(input: &str) -> Result<Denotum, ParseError> {
-    // Strip non-YAML header lines (e.g., "DENOTUM::SEED::2I")
-    let cleaned: String = input
+    let acc = parse_seed_composed(input, Path::new("."), &mut Vec::new())?;
+    Ok(acc.finalize())
+}
+
+/// Parse a Denotum seed from a file (YAML or JSON, detected by extension).
+///
+/// For YAML, `%include` directives resolve relative to `path`'s directory,
+/// recursively, with cycle detection across the whole include chain.
+pub fn parse_seed_file(path: &Path) -> Result<Denotum, ParseError> {
+    let content = std::fs::read_to_string(path)?;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    match ext {
+        "yaml" | "yml" => {
+            let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+            let mut visited = vec![path.canonicalize().unwrap_or_else(|_| path.to_path_buf())];
+            let acc = parse_seed_composed(&content, base_dir, &mut visited)?;
+            Ok(acc.finalize())
+        }
+        "json" => {
+            let d: Denotum = serde_json::from_str(&content)?;
+            Ok(d)
+        }
+        _ => Err(ParseError::UnsupportedFormat(ext.to_string())),
+    }
+}
+
+/// Parse one seed layer plus all of its (transitively included) bases,
+/// merging last-wins per map key, includes-then-own-body, in document
+/// order. `visited` tracks include paths already on the current chain so
+/// cycles are reported instead of recursing forever.
+fn parse_seed_composed(
+    input: &str,
+    base_dir: &Path,
+    visited: &mut Vec<PathBuf>,
+) -> Result<Accumulator, ParseError> {
+    let (body, includes, unsets) = extract_directives(input);
+    let own = parse_layer(&strip_header_lines(&body))?;
+
+    let mut acc = Accumulator::default();
+    for include_path in includes {
+        let resolved = base_dir.join(&include_path);
+        if !resolved.is_file() {
+            return Err(ParseError::MissingField(format!(
+                "include target not found: {}",
+                resolved.display()
+            )));
+        }
+        let canon = resolved.canonicalize().unwrap_or_else(|_| resolved.clone());
+        if visited.contains(&canon) {
+            return Err(ParseError::IncludeCycle(canon.display().to_string()));
+        }
+
+        let include_content = std::fs::read_to_string(&resolved)?;
+        let include_base_dir = resolved
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        visited.push(canon);
+        let included = parse_seed_composed(&include_content, &include_base_dir, visited)?;
+        visited.pop();
+
+        acc.merge(included);
+    }
+    acc.merge(own);
+
+    for key in unsets {
+        acc.apply_unset(&key);
+    }
+
+    Ok(acc)
+}
+
+/// Split `%include`/`%unset` directive lines out of a seed body, returning
+/// the remaining body text plus the include paths and unset keys in the
+/// order they appeared.
+fn extract_directives(input: &str) -> (String, Vec<String>, Vec<String>) {
+    let mut includes = Vec::new();
+    let mut unsets = Vec::new();
+    let mut body_lines = Vec::new();
+
+    for line in input.lines() {
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed.strip_prefix("%include ") {
+            includes.push(rest.trim().to_string());
+        } else if let Some(rest) = trimmed.strip_prefix("%unset ") {
+            unsets.push(rest.trim().to_string());
+        } else {
+            body_lines.push(line);
+        }
+    }
+
+    (body_lines.join("\n"), includes, unsets)
+}
+
+/// Strip non-YAML header lines (e.g., "DENOTUM::SEED::2I").
+fn strip_header_lines(input: &str) -> String {
+    input
         .lines()
         .filter(|line| {
             let trimmed = line.trim();
@@ -37,66 +256,52 @@ pub fn parse_seed(input: &str) -> Result<Denotum, ParseError> {
                 || trimmed.starts_with("  ")
         })
         .collect::<Vec<_>>()
-        .join("\n");
+        .join("\n")
+}
 
-    let raw: serde_yaml::Value = serde_yaml::from_str(&cleaned)?;
+/// Parse a single seed's own YAML body (directives and header already
+/// stripped) into an [`Accumulator`], without resolving any includes.
+fn parse_layer(cleaned: &str) -> Result<Accumulator, ParseError> {
+    let raw: serde_yaml::Value = serde_yaml::from_str(cleaned)?;
 
-    let version = extract_string(&raw, "VERSION").unwrap_or_else(|| "v1.0".to_string());
-    let steward = extract_string(&raw, "STEWARD").unwrap_or_else(|| "unknown".to_string());
-    let posture = extract_string(&raw, "POSTURE").unwrap_or_else(|| "FAIL_CLOSED".to_string());
-    let stop_wins = raw
-        .get("STOP_WINS")
-        .and_then(|v| v.as_bool())
-        .unwrap_or(true);
+    let version = extract_string(&raw, "VERSION");
+    let steward = extract_string(&raw, "STEWARD");
+    let posture = extract_string(&raw, "POSTURE");
+    let stop_wins = raw.get("STOP_WINS").and_then(|v| v.as_bool());
 
-    // Glossary
     let glossary = parse_glossary(&raw);
-
-    // Axioms
     let axioms = parse_axioms(&raw);
 
-    // Posture ladder
     let posture_ladder = parse_posture_ladder(&raw);
+    let degrade_rule = if posture_ladder.degrade_rule.is_empty() {
+        None
+    } else {
+        Some(posture_ladder.degrade_rule)
+    };
 
-    // Layers
-    let layers = parse_layers(&raw);
+    let layers = parse_layers(&raw)
+        .into_iter()
+        .map(|l| (l.name.clone(), l))
+        .collect();
 
-    // Blocker registry
-    let blocker_registry = parse_blocker_registry(&raw);
+    let blocker_registry = raw
+        .get("BLOCKER_REGISTRY")
+        .map(|_| parse_blocker_registry(&raw));
 
-    Ok(Denotum {
+    Ok(Accumulator {
         version,
         steward,
         posture,
         stop_wins,
         glossary,
         axioms,
-        posture_ladder,
+        posture_levels: posture_ladder.levels,
+        degrade_rule,
         layers,
-        beams: vec![],
-        lattices: vec![],
-        prisms: vec![],
         blocker_registry,
     })
 }
 
-/// Parse a Denotum seed from a file (YAML or JSON, detected by extension).
-pub fn parse_seed_file(path: &Path) -> Result<Denotum, ParseError> {
-    let content = std::fs::read_to_string(path)?;
-    let ext = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-    match ext {
-        "yaml" | "yml" => parse_seed(&content),
-        "json" => {
-            let d: Denotum = serde_json::from_str(&content)?;
-            Ok(d)
-        }
-        _ => Err(ParseError::UnsupportedFormat(ext.to_string())),
-    }
-}
-
 fn extract_string(val: &serde_yaml::Value, key: &str) -> Option<String> {
     val.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
 }
@@ -156,7 +361,7 @@ fn parse_posture_ladder(raw: &serde_yaml::Value) -> PostureLadder {
 }
 
 fn parse_layers(raw: &serde_yaml::Value) -> Vec<Layer> {
-    let layer_names = ["OI", "SGS", "STANGRAPHICS", "GSI", "NSCE", "2I"];
+    let layer_names = LAYER_NAMES;
     let mut layers = Vec::new();
 
     for name in &layer_names {
@@ -325,4 +530,119 @@ BLOCKER_REGISTRY:
             assert!(d.axioms.len() >= 7);
         }
     }
+
+    const BASE_SEED: &str = r#"
+VERSION: v1.0
+STEWARD: Ande
+POSTURE: FAIL_CLOSED
+STOP_WINS: true
+
+GLOSSARY:
+  2I: "Integrated Intelligence"
+  OI: "Ongoing Intelligence"
+
+AXIOMS:
+  A1_PeopleFirst: "People first, tools serve."
+  A2_StopWins: "Stop wins."
+
+POSTURE_LADDER:
+  L0: "READ_ONLY"
+  L1: "SUGGEST"
+  degrade_rule: "Drop one level on guard failure."
+"#;
+
+    #[test]
+    fn test_parse_seed_include_merges_base() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), BASE_SEED).unwrap();
+
+        let child = "%include base.yaml\n\nGLOSSARY:\n  3I: \"Triple Intelligence\"\n";
+        std::fs::write(dir.path().join("child.yaml"), child).unwrap();
+
+        let d = parse_seed_file(&dir.path().join("child.yaml")).unwrap();
+        assert_eq!(d.steward, "Ande");
+        assert_eq!(d.glossary.len(), 3);
+        assert!(d.glossary.contains_key("2I"));
+        assert!(d.glossary.contains_key("3I"));
+    }
+
+    #[test]
+    fn test_parse_seed_include_child_overrides_base() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), BASE_SEED).unwrap();
+
+        let child = "%include base.yaml\n\nSTEWARD: Nova\n";
+        std::fs::write(dir.path().join("child.yaml"), child).unwrap();
+
+        let d = parse_seed_file(&dir.path().join("child.yaml")).unwrap();
+        assert_eq!(d.steward, "Nova");
+        // Fields the child doesn't mention are still inherited from base.
+        assert_eq!(d.posture, "FAIL_CLOSED");
+    }
+
+    #[test]
+    fn test_parse_seed_unset_removes_inherited_entry() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), BASE_SEED).unwrap();
+
+        let child = "%include base.yaml\n%unset GLOSSARY.OI\n%unset POSTURE_LADDER.levels.L1\n";
+        std::fs::write(dir.path().join("child.yaml"), child).unwrap();
+
+        let d = parse_seed_file(&dir.path().join("child.yaml")).unwrap();
+        assert!(!d.glossary.contains_key("OI"));
+        assert!(d.glossary.contains_key("2I"));
+        assert!(!d.posture_ladder.levels.contains_key("L1"));
+        assert!(d.posture_ladder.levels.contains_key("L0"));
+    }
+
+    #[test]
+    fn test_parse_seed_include_cycle_detected() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.yaml"), "%include b.yaml\nSTEWARD: A\n").unwrap();
+        std::fs::write(dir.path().join("b.yaml"), "%include a.yaml\nSTEWARD: B\n").unwrap();
+
+        let err = parse_seed_file(&dir.path().join("a.yaml")).unwrap_err();
+        assert!(matches!(err, ParseError::IncludeCycle(_)));
+    }
+
+    #[test]
+    fn test_parse_seed_include_missing_target() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("child.yaml"),
+            "%include does-not-exist.yaml\nSTEWARD: A\n",
+        )
+        .unwrap();
+
+        let err = parse_seed_file(&dir.path().join("child.yaml")).unwrap_err();
+        assert!(matches!(err, ParseError::MissingField(_)));
+    }
+
+    #[test]
+    fn test_parse_seed_diamond_include_is_not_a_cycle() {
+        // base <- mid1, mid2 <- child: base is included twice via two
+        // different paths, which must not trip cycle detection.
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("base.yaml"), BASE_SEED).unwrap();
+        std::fs::write(
+            dir.path().join("mid1.yaml"),
+            "%include base.yaml\nGLOSSARY:\n  M1: \"Mid one\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("mid2.yaml"),
+            "%include base.yaml\nGLOSSARY:\n  M2: \"Mid two\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("child.yaml"),
+            "%include mid1.yaml\n%include mid2.yaml\n",
+        )
+        .unwrap();
+
+        let d = parse_seed_file(&dir.path().join("child.yaml")).unwrap();
+        assert!(d.glossary.contains_key("2I"));
+        assert!(d.glossary.contains_key("M1"));
+        assert!(d.glossary.contains_key("M2"));
+    }
 }
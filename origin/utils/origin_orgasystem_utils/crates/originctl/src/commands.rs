@@ -1,9 +1,24 @@
 //! Command implementations for originctl.
 
-use compress::{compress_dpack, decompress_cpack};
-use dpack_core::pack::{pack_repo, unfurl_pack, verify_pack};
+use compress::codec::parse_codec_name;
+use compress::frame::{CpackHeader, HEADER_SIZE, PAYLOAD_CHUNKED};
+use compress::{
+    chunk_dedup_stats, compress_dpack_chunked, compress_dpack_with_base, compress_dpack_with_codec,
+    decompress_cpack, decompress_cpack_chunked, decompress_cpack_delta,
+};
+use dpack_core::lock::{populate_dir_atomic, OutputLock};
+use dpack_core::manifest::DpackManifest;
+use dpack_core::pack::{
+    load_base_manifest, materialize_delta_pack, pack_repo, pack_repo_delta, unfurl_pack,
+    unfurl_pack_delta, verify_pack, verify_pack_delta,
+};
 use dpack_core::policy::Policy;
-use replication_core::replicate::{replicate_local, replicate_rootball, replicate_zip2repo_v1};
+use ed25519_dalek::SigningKey;
+use replication_core::replicate::{
+    materialize_chunk_delta_pack, replicate_delta, replicate_local, replicate_remote,
+    replicate_rootball, replicate_rootball_zip, replicate_zip2repo_v1,
+};
+use replication_core::Capability;
 use seed_core::Seed;
 use std::path::Path;
 
@@ -26,19 +41,139 @@ fn load_policy(policy_path: Option<&Path>) -> Result<Option<Policy>> {
     }
 }
 
+/// Load ed25519 signing keys for the `targets` role from `--sign-with`
+/// paths, each file holding a hex-encoded 32-byte private key seed.
+/// Returns `None` when no paths were given, so callers can pass it
+/// straight through as the opt-in `signing_keys` argument.
+fn load_signing_keys(sign_with: &[std::path::PathBuf]) -> Result<Option<Vec<SigningKey>>> {
+    if sign_with.is_empty() {
+        return Ok(None);
+    }
+    let mut keys = Vec::with_capacity(sign_with.len());
+    for path in sign_with {
+        let hex_seed = std::fs::read_to_string(path)?;
+        let seed_bytes: [u8; 32] = hex::decode(hex_seed.trim())
+            .map_err(|e| anyhow::anyhow!("invalid signing key in {}: {e}", path.display()))?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("signing key in {} is not 32 bytes", path.display()))?;
+        keys.push(SigningKey::from_bytes(&seed_bytes));
+    }
+    Ok(Some(keys))
+}
+
+/// Load a UCAN-style capability token for `--capability`, a JSON file
+/// holding a serialized [`Capability`]. Returns `None` when no path was
+/// given, so callers can pass it straight through as the opt-in
+/// `capability` argument; see `policy.trusted_capability_roots` for what
+/// makes the replication gates actually require one.
+fn load_capability(path: Option<&Path>) -> Result<Option<Capability>> {
+    match path {
+        Some(p) => {
+            let json = std::fs::read_to_string(p)?;
+            Ok(Some(serde_json::from_str(&json)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Resolve a `--base` reference to a `DpackManifest`: accepts a DPACK
+/// directory, a bare manifest.json, or a .cpack (decompressed to a scratch
+/// directory first).
+fn load_base_manifest_ref(base: &Path) -> Result<DpackManifest> {
+    if base.is_file() && base.extension().is_some_and(|e| e == "cpack") {
+        let tmp = tempfile::tempdir()?;
+        decompress_cpack(base, tmp.path())?;
+        Ok(load_base_manifest(tmp.path())?)
+    } else {
+        Ok(load_base_manifest(base)?)
+    }
+}
+
+/// Resolve a `--base` reference to a full DPACK directory on disk: a
+/// directory is used as-is, while a .cpack is decompressed to a scratch
+/// directory first. The returned `TempDir` (when present) must outlive the
+/// returned path.
+fn resolve_base_dir(base: &Path) -> Result<(std::path::PathBuf, Option<tempfile::TempDir>)> {
+    if base.is_dir() {
+        Ok((base.to_path_buf(), None))
+    } else {
+        let tmp = tempfile::tempdir()?;
+        decompress_cpack(base, tmp.path())?;
+        let path = tmp.path().to_path_buf();
+        Ok((path, Some(tmp)))
+    }
+}
+
+/// Stage a single-file write at `dest`'s own directory and rename it into
+/// place once `write` succeeds, so a concurrent reader never observes a
+/// partially written file.
+fn write_staged<E>(
+    dest: &Path,
+    write: impl FnOnce(&Path) -> std::result::Result<String, E>,
+) -> Result<String>
+where
+    anyhow::Error: From<E>,
+{
+    let parent = dest.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(parent)?;
+    let mut tmp_name = dest.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    tmp_name.push(".tmp");
+    let tmp_path = parent.join(tmp_name);
+    let hash = write(&tmp_path)?;
+    std::fs::rename(&tmp_path, dest)?;
+    Ok(hash)
+}
+
+/// Read just enough of `cpack_path` to learn its `payload_format` byte, so
+/// `run_decompress` can dispatch to the matching decoder without the caller
+/// having to say up front whether a pack was produced by
+/// `compress_dpack_chunked`.
+fn peek_payload_format(cpack_path: &Path) -> Result<u8> {
+    use std::io::Read;
+    let mut data = Vec::with_capacity(HEADER_SIZE);
+    std::fs::File::open(cpack_path)?
+        .take(HEADER_SIZE as u64)
+        .read_to_end(&mut data)?;
+    if data.len() < HEADER_SIZE {
+        anyhow::bail!("cpack file is too short to contain a header");
+    }
+    let header = CpackHeader::from_bytes(&data)?;
+    Ok(header.payload_format)
+}
+
 pub fn run_pack(
     repo_root: &Path,
     output: &Path,
     policy_path: Option<&Path>,
     seed_path: Option<&Path>,
+    allow_dirty: bool,
+    base: Option<&Path>,
+    no_lock: bool,
 ) -> Result<()> {
     let seed = load_seed(seed_path, Some(repo_root))?;
     let policy = load_policy(policy_path)?;
+    let base_manifest = base.map(load_base_manifest_ref).transpose()?;
 
     eprintln!("Packing {} -> {}", repo_root.display(), output.display());
     eprintln!("Seed fingerprint: {}", seed.fingerprint);
 
-    let receipt = pack_repo(repo_root, output, &seed, policy.as_ref())?;
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let mut receipt = None;
+    populate_dir_atomic(output, |staging| -> std::result::Result<(), dpack_core::pack::PackError> {
+        receipt = Some(match &base_manifest {
+            Some(base_manifest) => pack_repo_delta(
+                repo_root,
+                staging,
+                &seed,
+                policy.as_ref(),
+                allow_dirty,
+                base_manifest,
+            )?,
+            None => pack_repo(repo_root, staging, &seed, policy.as_ref(), allow_dirty)?,
+        });
+        Ok(())
+    })?;
+    let receipt = receipt.expect("populate_dir_atomic runs its closure exactly once on success");
 
     if receipt.passed {
         println!("PASS: pack complete");
@@ -57,14 +192,58 @@ pub fn run_pack(
     Ok(())
 }
 
-pub fn run_compress(dpack_dir: &Path, output: &Path) -> Result<()> {
+pub fn run_compress(
+    dpack_dir: &Path,
+    output: &Path,
+    codec: &str,
+    level: i32,
+    base: Option<&Path>,
+    chunked: bool,
+    no_lock: bool,
+) -> Result<()> {
     eprintln!(
-        "Compressing {} -> {}",
+        "Compressing {} -> {} (codec={}, level={}{})",
         dpack_dir.display(),
-        output.display()
+        output.display(),
+        codec,
+        level,
+        if chunked { ", chunked dedup" } else { "" }
     );
 
-    let payload_hash = compress_dpack(dpack_dir, output)?;
+    if chunked {
+        if base.is_some() {
+            anyhow::bail!(
+                "--chunked cannot be combined with --base; delta compress already dedups by diffing against the base"
+            );
+        }
+        let _lock = OutputLock::acquire(output, !no_lock)?;
+        let payload_hash = write_staged(output, |tmp| compress_dpack_chunked(dpack_dir, tmp))?;
+        println!("PASS: chunked compress complete");
+        println!("  payload_sha256: {}", payload_hash);
+        println!("  output: {}", output.display());
+
+        let meta = std::fs::metadata(output)?;
+        println!("  size: {} bytes", meta.len());
+
+        let stats = chunk_dedup_stats(dpack_dir)?;
+        println!(
+            "  dedup: {}/{} bytes unique ({:.1}% of original)",
+            stats.unique_bytes,
+            stats.total_bytes,
+            stats.ratio() * 100.0
+        );
+        return Ok(());
+    }
+
+    let codec_byte = parse_codec_name(codec)?;
+    let base_manifest = base.map(load_base_manifest_ref).transpose()?;
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let payload_hash = write_staged(output, |tmp| match &base_manifest {
+        Some(base_manifest) => {
+            compress_dpack_with_base(dpack_dir, tmp, codec_byte, level, base_manifest)
+        }
+        None => compress_dpack_with_codec(dpack_dir, tmp, codec_byte, level),
+    })?;
     println!("PASS: compress complete");
     println!("  payload_sha256: {}", payload_hash);
     println!("  output: {}", output.display());
@@ -74,21 +253,92 @@ pub fn run_compress(dpack_dir: &Path, output: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn run_decompress(cpack_path: &Path, output_dir: &Path) -> Result<()> {
+pub fn run_decompress(
+    cpack_path: &Path,
+    output_dir: &Path,
+    base: Option<&Path>,
+    no_lock: bool,
+) -> Result<()> {
     eprintln!(
         "Decompressing {} -> {}",
         cpack_path.display(),
         output_dir.display()
     );
 
-    let payload_hash = decompress_cpack(cpack_path, output_dir)?;
+    let _lock = OutputLock::acquire(output_dir, !no_lock)?;
+
+    if let Some(base) = base {
+        let (base_dir, _base_tmp) = resolve_base_dir(base)?;
+        let delta_tmp = tempfile::tempdir()?;
+        let payload_hash = decompress_cpack_delta(cpack_path, delta_tmp.path())?;
+        let mut full_manifest = None;
+        populate_dir_atomic(output_dir, |staging| -> std::result::Result<(), dpack_core::pack::PackError> {
+            full_manifest = Some(materialize_delta_pack(delta_tmp.path(), &base_dir, staging)?);
+            Ok(())
+        })?;
+        let full_manifest =
+            full_manifest.expect("populate_dir_atomic runs its closure exactly once on success");
+        println!("PASS: delta decompress complete");
+        println!("  payload_sha256: {}", payload_hash);
+        println!("  pack_hash: {}", full_manifest.pack_hash);
+        println!("  output: {}", output_dir.display());
+        return Ok(());
+    }
+
+    let chunked = peek_payload_format(cpack_path)? == PAYLOAD_CHUNKED;
+    let mut payload_hash = None;
+    populate_dir_atomic(output_dir, |staging| -> std::result::Result<(), compress::frame::FrameError> {
+        payload_hash = Some(if chunked {
+            decompress_cpack_chunked(cpack_path, staging)?
+        } else {
+            decompress_cpack(cpack_path, staging)?
+        });
+        Ok(())
+    })?;
+    let payload_hash = payload_hash.expect("populate_dir_atomic runs its closure exactly once on success");
     println!("PASS: decompress complete");
     println!("  payload_sha256: {}", payload_hash);
     println!("  output: {}", output_dir.display());
     Ok(())
 }
 
-pub fn run_verify(path: &Path, seed_path: Option<&Path>) -> Result<()> {
+pub fn run_verify(path: &Path, seed_path: Option<&Path>, base: Option<&Path>) -> Result<()> {
+    if let Some(base) = base {
+        // Delta pack: resolve the base manifest and verify the diff without
+        // needing to materialize the full tree.
+        let base_manifest = load_base_manifest_ref(base)?;
+
+        let (delta_dir, _delta_tmp) = if path.is_file() {
+            eprintln!("Verifying delta CPACK file: {}", path.display());
+            let tmp = tempfile::tempdir()?;
+            decompress_cpack_delta(path, tmp.path())?;
+            (tmp.path().to_path_buf(), Some(tmp))
+        } else {
+            eprintln!("Verifying delta DPACK at {}", path.display());
+            (path.to_path_buf(), None)
+        };
+
+        let seed = if let Some(sp) = seed_path {
+            Seed::load(sp)?
+        } else {
+            anyhow::bail!("no seed path provided; use --seed when verifying a delta pack")
+        };
+
+        let receipt = verify_pack_delta(&delta_dir, &base_manifest, &seed)?;
+        if receipt.passed {
+            println!("PASS: delta verification complete");
+        } else {
+            println!("FAIL: delta verification failed");
+        }
+        for g in &receipt.gates {
+            println!("  [{}] {:?}: {}", g.gate, g.status, g.detail);
+        }
+        if !receipt.passed {
+            anyhow::bail!("verification failed");
+        }
+        return Ok(());
+    }
+
     // Detect whether this is a cpack file or dpack directory
     if path.is_file() {
         // Assume .cpack file: decompress to temp, then verify
@@ -153,21 +403,41 @@ pub fn run_verify(path: &Path, seed_path: Option<&Path>) -> Result<()> {
     Ok(())
 }
 
-pub fn run_unfurl(pack_dir: &Path, output: &Path, seed_path: Option<&Path>) -> Result<()> {
+pub fn run_unfurl(
+    pack_dir: &Path,
+    output: &Path,
+    seed_path: Option<&Path>,
+    base: Option<&Path>,
+    no_lock: bool,
+) -> Result<()> {
     let seed = if let Some(sp) = seed_path {
         Seed::load(sp)?
     } else {
-        let candidate = pack_dir.join("data/spec/seed/denotum.seed.2i.yaml");
-        if candidate.exists() {
-            Seed::load(&candidate)?
+        let pack_candidate = pack_dir.join("data/spec/seed/denotum.seed.2i.yaml");
+        let base_candidate = base.map(|b| b.join("data/spec/seed/denotum.seed.2i.yaml"));
+        if pack_candidate.exists() {
+            Seed::load(&pack_candidate)?
+        } else if let Some(base_candidate) = base_candidate.filter(|c| c.exists()) {
+            Seed::load(&base_candidate)?
         } else {
-            anyhow::bail!("no seed path provided; use --seed or ensure seed is in pack data")
+            anyhow::bail!("no seed path provided; use --seed or ensure seed is in pack or base data")
         }
     };
 
     eprintln!("Unfurling {} -> {}", pack_dir.display(), output.display());
 
-    let receipt = unfurl_pack(pack_dir, output, &seed)?;
+    let resolved_base = base.map(resolve_base_dir).transpose()?;
+
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let mut receipt = None;
+    populate_dir_atomic(output, |staging| -> std::result::Result<(), dpack_core::pack::PackError> {
+        receipt = Some(match &resolved_base {
+            Some((base_dir, _base_tmp)) => unfurl_pack_delta(pack_dir, base_dir, staging, &seed)?,
+            None => unfurl_pack(pack_dir, staging, &seed)?,
+        });
+        Ok(())
+    })?;
+    let receipt = receipt.expect("populate_dir_atomic runs its closure exactly once on success");
 
     if receipt.passed {
         println!("PASS: unfurl complete");
@@ -223,9 +493,14 @@ pub fn run_replicate_local(
     output: &Path,
     policy_path: Option<&Path>,
     seed_path: Option<&Path>,
+    sign_with: &[std::path::PathBuf],
+    capability_path: Option<&Path>,
+    no_lock: bool,
 ) -> Result<()> {
     let seed = load_seed(seed_path, Some(repo_root))?;
     let policy = load_policy(policy_path)?;
+    let signing_keys = load_signing_keys(sign_with)?;
+    let capability = load_capability(capability_path)?;
 
     eprintln!(
         "Replicating (local) {} -> {}",
@@ -233,7 +508,20 @@ pub fn run_replicate_local(
         output.display()
     );
 
-    let receipt = replicate_local(repo_root, output, &seed, policy.as_ref())?;
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let mut receipt = None;
+    populate_dir_atomic(output, |staging| -> std::result::Result<(), replication_core::replicate::ReplicationError> {
+        receipt = Some(replicate_local(
+            repo_root,
+            staging,
+            &seed,
+            policy.as_ref(),
+            signing_keys.as_deref(),
+            capability.as_ref(),
+        )?);
+        Ok(())
+    })?;
+    let receipt = receipt.expect("populate_dir_atomic runs its closure exactly once on success");
 
     if receipt.passed {
         println!("PASS: local replication complete");
@@ -248,6 +536,9 @@ pub fn run_replicate_local(
         for g in &receipt.gates {
             println!("  [{}] {:?}: {}", g.gate, g.status, g.detail);
         }
+        if !receipt.signatures.is_empty() {
+            println!("  signatures: {}", receipt.signatures.len());
+        }
     } else {
         eprintln!("FAIL: replication failed");
         anyhow::bail!("replication failed");
@@ -260,9 +551,14 @@ pub fn run_replicate_rootball(
     output: &Path,
     policy_path: Option<&Path>,
     seed_path: Option<&Path>,
+    sign_with: &[std::path::PathBuf],
+    capability_path: Option<&Path>,
+    no_lock: bool,
 ) -> Result<()> {
     let seed = load_seed(seed_path, Some(repo_root))?;
     let policy = load_policy(policy_path)?;
+    let signing_keys = load_signing_keys(sign_with)?;
+    let capability = load_capability(capability_path)?;
 
     eprintln!(
         "Creating rootball {} -> {}",
@@ -270,13 +566,29 @@ pub fn run_replicate_rootball(
         output.display()
     );
 
-    let receipt = replicate_rootball(repo_root, output, &seed, policy.as_ref())?;
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let mut receipt = None;
+    populate_dir_atomic(output, |staging| -> std::result::Result<(), replication_core::replicate::ReplicationError> {
+        receipt = Some(replicate_rootball(
+            repo_root,
+            staging,
+            &seed,
+            policy.as_ref(),
+            signing_keys.as_deref(),
+            capability.as_ref(),
+        )?);
+        Ok(())
+    })?;
+    let receipt = receipt.expect("populate_dir_atomic runs its closure exactly once on success");
 
     if receipt.passed {
         println!("PASS: rootball created");
         for g in &receipt.gates {
             println!("  [{}] {:?}: {}", g.gate, g.status, g.detail);
         }
+        if !receipt.signatures.is_empty() {
+            println!("  signatures: {}", receipt.signatures.len());
+        }
     } else {
         eprintln!("FAIL: rootball creation failed");
         anyhow::bail!("rootball creation failed");
@@ -284,15 +596,72 @@ pub fn run_replicate_rootball(
     Ok(())
 }
 
+pub fn run_replicate_rootball_zip(
+    repo_root: &Path,
+    output: &Path,
+    policy_path: Option<&Path>,
+    seed_path: Option<&Path>,
+    sign_with: &[std::path::PathBuf],
+    capability_path: Option<&Path>,
+    no_lock: bool,
+) -> Result<()> {
+    let seed = load_seed(seed_path, Some(repo_root))?;
+    let policy = load_policy(policy_path)?;
+    let signing_keys = load_signing_keys(sign_with)?;
+    let capability = load_capability(capability_path)?;
+
+    eprintln!(
+        "Creating rootball zip {} -> {}",
+        repo_root.display(),
+        output.display()
+    );
+
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let mut receipt = None;
+    write_staged(output, |tmp| -> std::result::Result<String, replication_core::replicate::ReplicationError> {
+        let r = replicate_rootball_zip(
+            repo_root,
+            tmp,
+            &seed,
+            policy.as_ref(),
+            signing_keys.as_deref(),
+            capability.as_ref(),
+        )?;
+        let hash = r.source_pack_hash.clone().unwrap_or_default();
+        receipt = Some(r);
+        Ok(hash)
+    })?;
+    let receipt = receipt.expect("write_staged runs its closure exactly once on success");
+
+    if receipt.passed {
+        println!("PASS: rootball zip created");
+        for g in &receipt.gates {
+            println!("  [{}] {:?}: {}", g.gate, g.status, g.detail);
+        }
+        if !receipt.signatures.is_empty() {
+            println!("  signatures: {}", receipt.signatures.len());
+        }
+    } else {
+        eprintln!("FAIL: rootball zip creation failed");
+        anyhow::bail!("rootball zip creation failed");
+    }
+    Ok(())
+}
+
 pub fn run_replicate_zip2repo_v1(
     source: &Path,
     out_dir: &Path,
     init_git: bool,
     policy_path: Option<&Path>,
     seed_path: Option<&Path>,
+    sign_with: &[std::path::PathBuf],
+    capability_path: Option<&Path>,
+    no_lock: bool,
 ) -> Result<()> {
     let seed = load_seed(seed_path, Some(source))?;
     let policy = load_policy(policy_path)?;
+    let signing_keys = load_signing_keys(sign_with)?;
+    let capability = load_capability(capability_path)?;
 
     eprintln!(
         "Replicating (zip2repo_v1) {} -> {}",
@@ -300,13 +669,30 @@ pub fn run_replicate_zip2repo_v1(
         out_dir.display()
     );
 
-    let receipt = replicate_zip2repo_v1(source, out_dir, &seed, init_git, policy.as_ref())?;
+    let _lock = OutputLock::acquire(out_dir, !no_lock)?;
+    let mut receipt = None;
+    populate_dir_atomic(out_dir, |staging| -> std::result::Result<(), replication_core::replicate::ReplicationError> {
+        receipt = Some(replicate_zip2repo_v1(
+            source,
+            staging,
+            &seed,
+            init_git,
+            policy.as_ref(),
+            signing_keys.as_deref(),
+            capability.as_ref(),
+        )?);
+        Ok(())
+    })?;
+    let receipt = receipt.expect("populate_dir_atomic runs its closure exactly once on success");
 
     if receipt.passed {
         println!("PASS: zip2repo_v1 replication complete");
         for g in &receipt.gates {
             println!("  [{}] {:?}: {}", g.gate, g.status, g.detail);
         }
+        if !receipt.signatures.is_empty() {
+            println!("  signatures: {}", receipt.signatures.len());
+        }
     } else {
         eprintln!("FAIL: replication failed");
         anyhow::bail!("replication failed");
@@ -314,22 +700,76 @@ pub fn run_replicate_zip2repo_v1(
     Ok(())
 }
 
-/// End-to-end pipeline: pack -> compress -> decompress -> verify round-trip.
+pub fn run_replicate_remote(
+    url: &str,
+    output: &Path,
+    expected_hash: Option<&str>,
+    policy_path: Option<&Path>,
+    seed_path: Option<&Path>,
+    max_fetch_bytes: Option<u64>,
+    capability_path: Option<&Path>,
+    no_lock: bool,
+) -> Result<()> {
+    let seed_path =
+        seed_path.ok_or_else(|| anyhow::anyhow!("--seed is required for remote replication"))?;
+    let seed = Seed::load(seed_path)?;
+    let policy = load_policy(policy_path)?;
+    let capability = load_capability(capability_path)?;
+
+    eprintln!("Replicating (remote) {} -> {}", url, output.display());
+
+    let _lock = OutputLock::acquire(output, !no_lock)?;
+    let mut receipt = None;
+    populate_dir_atomic(output, |staging| -> std::result::Result<(), replication_core::replicate::ReplicationError> {
+        receipt = Some(replicate_remote(url, staging, &seed, expected_hash, policy.as_ref(), max_fetch_bytes, capability.as_ref())?);
+        Ok(())
+    })?;
+    let receipt = receipt.expect("populate_dir_atomic runs its closure exactly once on success");
+
+    if receipt.passed {
+        println!("PASS: remote replication complete");
+        println!(
+            "  source_url: {}",
+            receipt.source_url.as_deref().unwrap_or_default()
+        );
+        println!(
+            "  source_pack_hash: {}",
+            receipt.source_pack_hash.unwrap_or_default()
+        );
+        println!(
+            "  target_pack_hash: {}",
+            receipt.target_pack_hash.unwrap_or_default()
+        );
+        for g in &receipt.gates {
+            println!("  [{}] {:?}: {}", g.gate, g.status, g.detail);
+        }
+    } else {
+        eprintln!("FAIL: remote replication failed");
+        anyhow::bail!("replication failed");
+    }
+    Ok(())
+}
+
+/// End-to-end pipeline: pack -> compress -> decompress -> verify round-trip,
+/// plus a chunk-level delta replication round-trip against the step-1 pack.
 pub fn run_e2e(
     repo_root: &Path,
     seed_path: Option<&Path>,
     policy_path: Option<&Path>,
+    codec: &str,
+    level: i32,
 ) -> Result<()> {
     let seed = load_seed(seed_path, Some(repo_root))?;
     let policy = load_policy(policy_path)?;
+    let codec_byte = parse_codec_name(codec)?;
 
     println!("=== ORIGIN E2E PIPELINE ===");
     println!();
 
     // Step 1: Pack
-    println!("[1/6] Packing repository...");
+    println!("[1/7] Packing repository...");
     let dpack_dir = tempfile::tempdir()?;
-    let pack_receipt = pack_repo(repo_root, dpack_dir.path(), &seed, policy.as_ref())?;
+    let pack_receipt = pack_repo(repo_root, dpack_dir.path(), &seed, policy.as_ref(), false)?;
     if !pack_receipt.passed {
         anyhow::bail!("E2E FAIL at step 1 (pack): gates did not pass");
     }
@@ -338,16 +778,17 @@ pub fn run_e2e(
     println!("  pack_hash: {}", &pack_hash[..16]);
 
     // Step 2: Compress
-    println!("[2/6] Compressing to CPACK...");
+    println!("[2/7] Compressing to CPACK...");
     let cpack_dir = tempfile::tempdir()?;
     let cpack_path = cpack_dir.path().join("origin.cpack");
-    let payload_hash = compress_dpack(dpack_dir.path(), &cpack_path)?;
+    let payload_hash =
+        compress_dpack_with_codec(dpack_dir.path(), &cpack_path, codec_byte, level)?;
     let cpack_size = std::fs::metadata(&cpack_path)?.len();
     println!("  PASS: compressed to {} bytes", cpack_size);
     println!("  payload_sha256: {}", &payload_hash[..16]);
 
     // Step 3: Decompress
-    println!("[3/6] Decompressing CPACK...");
+    println!("[3/7] Decompressing CPACK...");
     let restored_dir = tempfile::tempdir()?;
     let restored_hash = decompress_cpack(&cpack_path, restored_dir.path())?;
     println!("  PASS: decompressed");
@@ -358,7 +799,7 @@ pub fn run_e2e(
     println!("  payload_sha256 matches: YES");
 
     // Step 4: Verify restored dpack
-    println!("[4/6] Verifying restored DPACK...");
+    println!("[4/7] Verifying restored DPACK...");
     let verify_receipt = verify_pack(restored_dir.path(), &seed)?;
     if !verify_receipt.passed {
         anyhow::bail!("E2E FAIL at step 4 (verify): restored dpack verification failed");
@@ -369,7 +810,7 @@ pub fn run_e2e(
     }
 
     // Step 5: Round-trip integrity check (compare pack hashes)
-    println!("[5/6] Checking round-trip integrity...");
+    println!("[5/7] Checking round-trip integrity...");
     let restored_manifest_bytes = std::fs::read(restored_dir.path().join("manifest.json"))?;
     let restored_manifest: dpack_core::DpackManifest =
         serde_json::from_slice(&restored_manifest_bytes)?;
@@ -383,10 +824,11 @@ pub fn run_e2e(
     println!("  PASS: pack_hash matches original");
 
     // Step 6: Compress determinism check
-    println!("[6/6] Checking compress determinism...");
+    println!("[6/7] Checking compress determinism...");
     let cpack2_dir = tempfile::tempdir()?;
     let cpack2_path = cpack2_dir.path().join("origin2.cpack");
-    let payload_hash2 = compress_dpack(dpack_dir.path(), &cpack2_path)?;
+    let payload_hash2 =
+        compress_dpack_with_codec(dpack_dir.path(), &cpack2_path, codec_byte, level)?;
     let cpack1_bytes = std::fs::read(&cpack_path)?;
     let cpack2_bytes = std::fs::read(&cpack2_path)?;
     if cpack1_bytes != cpack2_bytes {
@@ -395,6 +837,45 @@ pub fn run_e2e(
     assert_eq!(payload_hash, payload_hash2);
     println!("  PASS: compress is deterministic (byte-identical)");
 
+    // Step 7: Delta replication round-trip against the pack taken in step 1
+    // as the base. The "changed" source is the pack's own materialized
+    // `data/` directory (already a complete, disposable copy of
+    // repo_root's tracked content) with one extra file dropped in, so this
+    // never touches repo_root itself. Exercises replicate_delta end-to-end:
+    // chunking, the RG2_DELTA_RECONSTRUCTION gate, and
+    // materialize_chunk_delta_pack.
+    println!("[7/7] Checking delta replication round-trip...");
+    let delta_source_dir = dpack_dir.path().join("data");
+    std::fs::write(
+        delta_source_dir.join(".origin_e2e_delta_probe"),
+        b"e2e delta round-trip probe",
+    )?;
+    let delta_dir = tempfile::tempdir()?;
+    let delta_receipt = replicate_delta(
+        &delta_source_dir,
+        dpack_dir.path(),
+        delta_dir.path(),
+        &seed,
+        policy.as_ref(),
+        None,
+        None,
+    )?;
+    if !delta_receipt.passed {
+        anyhow::bail!("E2E FAIL at step 7: delta replication gates did not pass");
+    }
+    let reconstructed_dir = tempfile::tempdir()?;
+    let reconstructed =
+        materialize_chunk_delta_pack(delta_dir.path(), dpack_dir.path(), reconstructed_dir.path())?;
+    let expected_target_hash = delta_receipt.target_pack_hash.clone().unwrap_or_default();
+    if reconstructed.pack_hash != expected_target_hash {
+        anyhow::bail!(
+            "E2E FAIL at step 7: reconstructed pack_hash {} does not match delta's target {}",
+            &reconstructed.pack_hash[..16],
+            &expected_target_hash[..16.min(expected_target_hash.len())]
+        );
+    }
+    println!("  PASS: delta replication reconstructs the exact post-change pack_hash");
+
     println!();
     println!("=== E2E PIPELINE PASSED ===");
     println!("  pack_hash:       {}", pack_hash);
@@ -402,5 +883,6 @@ pub fn run_e2e(
     println!("  cpack_size:      {} bytes", cpack_size);
     println!("  round_trip:      VERIFIED");
     println!("  determinism:     VERIFIED");
+    println!("  delta_round_trip: VERIFIED");
     Ok(())
 }
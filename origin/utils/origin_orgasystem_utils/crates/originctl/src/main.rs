@@ -33,6 +33,18 @@ enum Commands {
         /// Path to the seed file (defaults to spec/seed/denotum.seed.2i.yaml in repo_root).
         #[arg(long)]
         seed: Option<PathBuf>,
+        /// Allow packing a git checkout with uncommitted or untracked changes.
+        #[arg(long)]
+        allow_dirty: bool,
+        /// Base manifest (DPACK directory or manifest.json) to diff against.
+        /// When set, produces a delta pack with only the files that changed
+        /// since the base, instead of a full snapshot.
+        #[arg(long)]
+        base: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
     /// Compress a DPACK directory into a .cpack file.
     Compress {
@@ -41,6 +53,28 @@ enum Commands {
         /// Output .cpack file path.
         #[arg(short, long)]
         output: PathBuf,
+        /// Compression codec: store, zstd, or xz (reserved, not yet implemented).
+        #[arg(long, default_value = "zstd")]
+        codec: String,
+        /// Compression level (zstd: 1-19; ignored for store/xz).
+        #[arg(long, default_value_t = compress::codec::DEFAULT_ZSTD_LEVEL)]
+        level: i32,
+        /// Base manifest (DPACK directory, manifest.json, or .cpack) to diff
+        /// against. When set, produces a delta .cpack with only the files
+        /// that changed since the base.
+        #[arg(long)]
+        base: Option<PathBuf>,
+        /// Split files into content-defined chunks and dedup identical
+        /// chunks across the whole pack before compressing. Not supported
+        /// together with `--base` (delta compress already dedups by diffing
+        /// against the base). Ignores `--codec`/`--level` (chunked payloads
+        /// are always zstd-compressed).
+        #[arg(long)]
+        chunked: bool,
+        /// Skip advisory locking of the output file (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
     /// Decompress a .cpack file back into a DPACK directory.
     Decompress {
@@ -49,6 +83,15 @@ enum Commands {
         /// Output directory for the DPACK.
         #[arg(short, long)]
         output: PathBuf,
+        /// Base DPACK directory or .cpack to merge a delta .cpack's contents
+        /// onto. When set, `cpack` is treated as a delta produced by
+        /// `compress --base` and the output is the reconstructed full DPACK.
+        #[arg(long)]
+        base: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
     /// Verify a DPACK or CPACK (hashes, schema, invariants).
     Verify {
@@ -57,6 +100,11 @@ enum Commands {
         /// Path to the seed file.
         #[arg(long)]
         seed: Option<PathBuf>,
+        /// Base manifest (DPACK directory, manifest.json, or .cpack) that
+        /// `path` is a delta against. When set, `path` is verified as a
+        /// delta pack instead of a full one.
+        #[arg(long)]
+        base: Option<PathBuf>,
     },
     /// Unfurl (restore) a DPACK snapshot to a target directory.
     Unfurl {
@@ -68,6 +116,14 @@ enum Commands {
         /// Path to the seed file.
         #[arg(long)]
         seed: Option<PathBuf>,
+        /// Base DPACK directory or .cpack to merge a delta pack onto. When
+        /// set, `pack` is treated as a delta produced by `pack --base`.
+        #[arg(long)]
+        base: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
     /// Audit a DPACK snapshot and output gate results.
     Audit {
@@ -96,6 +152,12 @@ enum Commands {
         /// Optional policy YAML file.
         #[arg(long)]
         policy: Option<PathBuf>,
+        /// Compression codec: store, zstd, or xz (reserved, not yet implemented).
+        #[arg(long, default_value = "zstd")]
+        codec: String,
+        /// Compression level (zstd: 1-19; ignored for store/xz).
+        #[arg(long, default_value_t = compress::codec::DEFAULT_ZSTD_LEVEL)]
+        level: i32,
     },
 }
 
@@ -115,6 +177,22 @@ enum ReplicateMode {
         /// Path to the seed file.
         #[arg(long)]
         seed: Option<PathBuf>,
+        /// Sign the replication receipt as the `targets` role with this
+        /// ed25519 signing key (a file holding a hex-encoded 32-byte seed).
+        /// May be repeated to collect signatures toward a threshold; see
+        /// `policy.trusted_root` for the `root.json` a receipt is verified
+        /// against.
+        #[arg(long)]
+        sign_with: Vec<PathBuf>,
+        /// Path to a UCAN-style capability token (JSON), authorizing this
+        /// replication under `policy.trusted_capability_roots`. Required
+        /// only when that policy knob is configured; ignored otherwise.
+        #[arg(long)]
+        capability: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
     /// R1: Produce a DPACK rootball for transport.
     Rootball {
@@ -130,10 +208,55 @@ enum ReplicateMode {
         /// Path to the seed file.
         #[arg(long)]
         seed: Option<PathBuf>,
+        /// Sign the replication receipt as the `targets` role with this
+        /// ed25519 signing key (a file holding a hex-encoded 32-byte seed).
+        /// May be repeated to collect signatures toward a threshold.
+        #[arg(long)]
+        sign_with: Vec<PathBuf>,
+        /// Path to a UCAN-style capability token (JSON), authorizing this
+        /// replication under `policy.trusted_capability_roots`. Required
+        /// only when that policy knob is configured; ignored otherwise.
+        #[arg(long)]
+        capability: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
-    /// R2: Unfurl from a source directory into a fresh repo tree (v1).
+    /// R1: Produce a DPACK rootball, serialized as a single deterministic `.zip`.
+    RootballZip {
+        /// Path to the repository root.
+        #[arg(long)]
+        repo_root: PathBuf,
+        /// Output .zip file path.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Optional policy YAML file.
+        #[arg(long)]
+        policy: Option<PathBuf>,
+        /// Path to the seed file.
+        #[arg(long)]
+        seed: Option<PathBuf>,
+        /// Sign the replication receipt as the `targets` role with this
+        /// ed25519 signing key (a file holding a hex-encoded 32-byte seed).
+        /// May be repeated to collect signatures toward a threshold.
+        #[arg(long)]
+        sign_with: Vec<PathBuf>,
+        /// Path to a UCAN-style capability token (JSON), authorizing this
+        /// replication under `policy.trusted_capability_roots`. Required
+        /// only when that policy knob is configured; ignored otherwise.
+        #[arg(long)]
+        capability: Option<PathBuf>,
+        /// Skip advisory locking of the output file (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
+    },
+    /// R2: Unfurl from a source directory or zip file into a fresh repo tree (v1).
     Zip2repoV1 {
-        /// Source directory (simulating extracted zip).
+        /// Source directory (simulating extracted zip), or an actual `.zip`
+        /// file path, which is stream-extracted with path-traversal and
+        /// symlink protection.
         #[arg(long)]
         source: PathBuf,
         /// Output directory.
@@ -148,6 +271,53 @@ enum ReplicateMode {
         /// Path to the seed file.
         #[arg(long)]
         seed: Option<PathBuf>,
+        /// Sign the replication receipt as the `targets` role with this
+        /// ed25519 signing key (a file holding a hex-encoded 32-byte seed).
+        /// May be repeated to collect signatures toward a threshold.
+        #[arg(long)]
+        sign_with: Vec<PathBuf>,
+        /// Path to a UCAN-style capability token (JSON), authorizing this
+        /// replication under `policy.trusted_capability_roots`. Required
+        /// only when that policy knob is configured; ignored otherwise.
+        #[arg(long)]
+        capability: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
+    },
+    /// R3: Fetch a .cpack (or zip/tar.gz archive) from an http(s) URL and replicate it.
+    Remote {
+        /// http(s) URL of the .cpack, .zip, .tar.gz, or .tgz to fetch.
+        #[arg(long)]
+        url: String,
+        /// Target directory.
+        #[arg(short, long)]
+        output: PathBuf,
+        /// Expected payload_sha256 of the fetched .cpack, compared against the
+        /// header's payload_sha256 only (a manifest pack_hash does not match).
+        /// Ignored for archive (.zip/.tar.gz/.tgz) sources.
+        #[arg(long)]
+        expected_hash: Option<String>,
+        /// Optional policy YAML file.
+        #[arg(long)]
+        policy: Option<PathBuf>,
+        /// Path to the seed file.
+        #[arg(long)]
+        seed: Option<PathBuf>,
+        /// Cap in bytes on the downloaded artifact (defaults to
+        /// `replication_core::replicate::DEFAULT_MAX_FETCH_BYTES`).
+        #[arg(long)]
+        max_fetch_bytes: Option<u64>,
+        /// Path to a UCAN-style capability token (JSON), authorizing this
+        /// replication under `policy.trusted_capability_roots`. Required
+        /// only when that policy knob is configured; ignored otherwise.
+        #[arg(long)]
+        capability: Option<PathBuf>,
+        /// Skip advisory locking of the output directory (for read-only or
+        /// lock-incapable network filesystems).
+        #[arg(long)]
+        no_lock: bool,
     },
 }
 
@@ -159,13 +329,51 @@ fn main() {
             output,
             policy,
             seed,
-        } => commands::run_pack(&repo_root, &output, policy.as_deref(), seed.as_deref()),
-        Commands::Compress { dpack, output } => commands::run_compress(&dpack, &output),
-        Commands::Decompress { cpack, output } => commands::run_decompress(&cpack, &output),
-        Commands::Verify { path, seed } => commands::run_verify(&path, seed.as_deref()),
-        Commands::Unfurl { pack, output, seed } => {
-            commands::run_unfurl(&pack, &output, seed.as_deref())
+            allow_dirty,
+            base,
+            no_lock,
+        } => commands::run_pack(
+            &repo_root,
+            &output,
+            policy.as_deref(),
+            seed.as_deref(),
+            allow_dirty,
+            base.as_deref(),
+            no_lock,
+        ),
+        Commands::Compress {
+            dpack,
+            output,
+            codec,
+            level,
+            base,
+            chunked,
+            no_lock,
+        } => commands::run_compress(
+            &dpack,
+            &output,
+            &codec,
+            level,
+            base.as_deref(),
+            chunked,
+            no_lock,
+        ),
+        Commands::Decompress {
+            cpack,
+            output,
+            base,
+            no_lock,
+        } => commands::run_decompress(&cpack, &output, base.as_deref(), no_lock),
+        Commands::Verify { path, seed, base } => {
+            commands::run_verify(&path, seed.as_deref(), base.as_deref())
         }
+        Commands::Unfurl {
+            pack,
+            output,
+            seed,
+            base,
+            no_lock,
+        } => commands::run_unfurl(&pack, &output, seed.as_deref(), base.as_deref(), no_lock),
         Commands::Audit { pack, json, seed } => commands::run_audit(&pack, json, seed.as_deref()),
         Commands::Replicate { mode } => match mode {
             ReplicateMode::Local {
@@ -173,22 +381,51 @@ fn main() {
                 output,
                 policy,
                 seed,
+                sign_with,
+                capability,
+                no_lock,
             } => commands::run_replicate_local(
                 &repo_root,
                 &output,
                 policy.as_deref(),
                 seed.as_deref(),
+                &sign_with,
+                capability.as_deref(),
+                no_lock,
             ),
             ReplicateMode::Rootball {
                 repo_root,
                 output,
                 policy,
                 seed,
+                sign_with,
+                capability,
+                no_lock,
             } => commands::run_replicate_rootball(
                 &repo_root,
                 &output,
                 policy.as_deref(),
                 seed.as_deref(),
+                &sign_with,
+                capability.as_deref(),
+                no_lock,
+            ),
+            ReplicateMode::RootballZip {
+                repo_root,
+                output,
+                policy,
+                seed,
+                sign_with,
+                capability,
+                no_lock,
+            } => commands::run_replicate_rootball_zip(
+                &repo_root,
+                &output,
+                policy.as_deref(),
+                seed.as_deref(),
+                &sign_with,
+                capability.as_deref(),
+                no_lock,
             ),
             ReplicateMode::Zip2repoV1 {
                 source,
@@ -196,19 +433,46 @@ fn main() {
                 init_git,
                 policy,
                 seed,
+                sign_with,
+                capability,
+                no_lock,
             } => commands::run_replicate_zip2repo_v1(
                 &source,
                 &out_dir,
                 init_git,
                 policy.as_deref(),
                 seed.as_deref(),
+                &sign_with,
+                capability.as_deref(),
+                no_lock,
+            ),
+            ReplicateMode::Remote {
+                url,
+                output,
+                expected_hash,
+                policy,
+                seed,
+                max_fetch_bytes,
+                capability,
+                no_lock,
+            } => commands::run_replicate_remote(
+                &url,
+                &output,
+                expected_hash.as_deref(),
+                policy.as_deref(),
+                seed.as_deref(),
+                max_fetch_bytes,
+                capability.as_deref(),
+                no_lock,
             ),
         },
         Commands::E2e {
             repo_root,
             seed,
             policy,
-        } => commands::run_e2e(&repo_root, seed.as_deref(), policy.as_deref()),
+            codec,
+            level,
+        } => commands::run_e2e(&repo_root, seed.as_deref(), policy.as_deref(), &codec, level),
     };
 
     if let Err(e) = result {
@@ -39,8 +39,8 @@ fn golden_pack_hash_is_deterministic() {
     let out1 = TempDir::new().unwrap();
     let out2 = TempDir::new().unwrap();
 
-    let r1 = pack_repo(&sample_tree(), out1.path(), &seed, None).unwrap();
-    let r2 = pack_repo(&sample_tree(), out2.path(), &seed, None).unwrap();
+    let r1 = pack_repo(&sample_tree(), out1.path(), &seed, None, false).unwrap();
+    let r2 = pack_repo(&sample_tree(), out2.path(), &seed, None, false).unwrap();
 
     assert!(r1.passed);
     assert!(r2.passed);
@@ -55,7 +55,7 @@ fn golden_pack_hash_is_deterministic() {
 fn golden_manifest_file_hashes_stable() {
     let seed = seed_from_fixture();
     let out = TempDir::new().unwrap();
-    pack_repo(&sample_tree(), out.path(), &seed, None).unwrap();
+    pack_repo(&sample_tree(), out.path(), &seed, None, false).unwrap();
 
     let manifest_bytes = std::fs::read(out.path().join("manifest.json")).unwrap();
     let manifest: DpackManifest = serde_json::from_slice(&manifest_bytes).unwrap();
@@ -81,7 +81,7 @@ fn golden_manifest_file_hashes_stable() {
 fn golden_verify_accepts_clean_pack() {
     let seed = seed_from_fixture();
     let out = TempDir::new().unwrap();
-    pack_repo(&sample_tree(), out.path(), &seed, None).unwrap();
+    pack_repo(&sample_tree(), out.path(), &seed, None, false).unwrap();
 
     let receipt = verify_pack(out.path(), &seed).unwrap();
     assert!(
@@ -95,7 +95,7 @@ fn golden_verify_accepts_clean_pack() {
 fn golden_compress_decompress_roundtrip() {
     let seed = seed_from_fixture();
     let dpack = TempDir::new().unwrap();
-    pack_repo(&sample_tree(), dpack.path(), &seed, None).unwrap();
+    pack_repo(&sample_tree(), dpack.path(), &seed, None, false).unwrap();
 
     // Read original manifest
     let orig_manifest_bytes = std::fs::read(dpack.path().join("manifest.json")).unwrap();
@@ -123,7 +123,7 @@ fn golden_compress_decompress_roundtrip() {
 fn golden_compress_is_bytewise_deterministic() {
     let seed = seed_from_fixture();
     let dpack = TempDir::new().unwrap();
-    pack_repo(&sample_tree(), dpack.path(), &seed, None).unwrap();
+    pack_repo(&sample_tree(), dpack.path(), &seed, None, false).unwrap();
 
     let cp1 = TempDir::new().unwrap();
     let cp2 = TempDir::new().unwrap();
@@ -166,8 +166,9 @@ fn golden_lfme_canonical_fingerprint_stable() {
     let content = std::fs::read_to_string(&seed_path).unwrap();
     let denotum = lfme_core::parse_seed(&content).unwrap();
 
-    let fp1 = lfme_core::canonical::canonical_fingerprint(&denotum).unwrap();
-    let fp2 = lfme_core::canonical::canonical_fingerprint(&denotum).unwrap();
+    let scheme = seed_core::hash::HashScheme::default();
+    let fp1 = lfme_core::canonical::canonical_fingerprint(&denotum, &scheme).unwrap();
+    let fp2 = lfme_core::canonical::canonical_fingerprint(&denotum, &scheme).unwrap();
     assert_eq!(fp1, fp2, "canonical fingerprint must be stable");
     assert_eq!(fp1.len(), 64);
 }
@@ -183,8 +184,20 @@ fn golden_rag_deterministic_retrieval() {
     index.add_document("code", "fn main() { println!(\"hello\"); }", 100);
     index.add_document("config", "name: origin\nversion: 1.0.0", 100);
 
-    let r1 = rag_deterministic::retrieve(&index, "origin intelligence", 2);
-    let r2 = rag_deterministic::retrieve(&index, "origin intelligence", 2);
+    let r1 = rag_deterministic::retrieve(
+        &index,
+        "origin intelligence",
+        2,
+        rag_deterministic::ScoringMode::Embedding,
+        false,
+    );
+    let r2 = rag_deterministic::retrieve(
+        &index,
+        "origin intelligence",
+        2,
+        rag_deterministic::ScoringMode::Embedding,
+        false,
+    );
 
     assert_eq!(r1.len(), r2.len());
     for (a, b) in r1.iter().zip(r2.iter()) {
@@ -21,14 +21,12 @@ fn make_synthetic_dpack(dir: &std::path::Path, files: &[(String, Vec<u8>)], seed
         std::fs::write(&dest, content).unwrap();
         manifest_files.insert(
             rel_path.clone(),
-            FileEntry {
-                sha256: compute_sha256(content),
-                size: content.len() as u64,
-            },
+            FileEntry::new(compute_sha256(content), content.len() as u64),
         );
     }
 
-    let pack_hash = DpackManifest::compute_pack_hash(&manifest_files);
+    let hash_scheme = seed_core::hash::HashScheme::default();
+    let pack_hash = DpackManifest::compute_pack_hash(&manifest_files, &hash_scheme);
     let manifest = DpackManifest {
         schema_version: "1.0".to_string(),
         root_2i_seed_fingerprint: seed_fp.to_string(),
@@ -36,6 +34,8 @@ fn make_synthetic_dpack(dir: &std::path::Path, files: &[(String, Vec<u8>)], seed
         source_root: "/synthetic".to_string(),
         files: manifest_files,
         pack_hash,
+        vcs: None,
+        hash_scheme,
     };
     let json = serde_json::to_string_pretty(&manifest).unwrap();
     std::fs::write(dir.join("manifest.json"), json).unwrap();
@@ -3,8 +3,17 @@
 //! Uses SHA-256-based deterministic "embeddings" — no external model calls.
 //! The embedding is a fixed-dimensional vector derived from the content hash.
 //! This is a toy implementation that still provides deterministic retrieval.
+//!
+//! [`Embedder`] is the extension point for a real backend: implement it and
+//! pass it to [`crate::index::DeterministicIndex::with_embedder`].
+//! [`AsyncEmbedder`] is its async counterpart for remote/model-backed
+//! backends; `DeterministicIndex` and `retrieve` only accept an [`Embedder`].
+//! [`DeterministicEmbedder`] - this module's SHA-256 stub - is the default,
+//! so existing callers and determinism tests are unaffected.
 
 use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 
 /// Embedding dimension (number of f64 values).
 pub const EMBED_DIM: usize = 32;
@@ -16,6 +25,83 @@ pub struct Embedding {
     pub vector: Vec<f64>,
 }
 
+/// A synchronous text-to-vector backend.
+///
+/// `Debug` is a supertrait so types holding an `Embedder` (e.g.
+/// [`crate::index::DeterministicIndex`]) can keep deriving `Debug`.
+pub trait Embedder: std::fmt::Debug {
+    /// Embed `text`. Implementations need not be deterministic, but
+    /// [`DeterministicIndex::query`](crate::index::DeterministicIndex::query)
+    /// only reproduces identical results across runs if this does.
+    fn embed(&self, text: &str) -> Embedding;
+
+    /// The dimension of vectors this embedder returns.
+    fn dim(&self) -> usize;
+}
+
+/// The default [`Embedder`]: this module's SHA-256 stub (see [`embed_chunk`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DeterministicEmbedder;
+
+impl Embedder for DeterministicEmbedder {
+    fn embed(&self, text: &str) -> Embedding {
+        embed_chunk(text)
+    }
+
+    fn dim(&self) -> usize {
+        EMBED_DIM
+    }
+}
+
+/// An async text-to-vector backend, for remote or model-backed embedding
+/// (batched HTTP calls, local model inference) that cannot produce a
+/// vector synchronously. There is no built-in implementation - callers
+/// needing a real semantic embedder implement this directly.
+pub trait AsyncEmbedder {
+    /// Embed `text`.
+    fn embed(&self, text: &str) -> impl std::future::Future<Output = Embedding> + Send;
+
+    /// The dimension of vectors this embedder returns.
+    fn dim(&self) -> usize;
+}
+
+/// Wraps an [`Embedder`], memoizing by exact input text so indexing
+/// repeated or near-duplicate chunk text only embeds each distinct string
+/// once. Preserves the wrapped embedder's determinism: the same text
+/// always returns the same (now cached) value.
+#[derive(Debug, Clone)]
+pub struct CachingEmbedder<E: Embedder> {
+    inner: E,
+    cache: RefCell<BTreeMap<String, Embedding>>,
+}
+
+impl<E: Embedder> CachingEmbedder<E> {
+    /// Wrap `inner` with an empty cache.
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            cache: RefCell::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl<E: Embedder> Embedder for CachingEmbedder<E> {
+    fn embed(&self, text: &str) -> Embedding {
+        if let Some(cached) = self.cache.borrow().get(text) {
+            return cached.clone();
+        }
+        let embedding = self.inner.embed(text);
+        self.cache
+            .borrow_mut()
+            .insert(text.to_string(), embedding.clone());
+        embedding
+    }
+
+    fn dim(&self) -> usize {
+        self.inner.dim()
+    }
+}
+
 /// Compute a deterministic "embedding" from text.
 ///
 /// This is a hash-based stub: it hashes the text and maps bytes to [0, 1] floats.
@@ -105,4 +191,35 @@ mod tests {
         let s2 = cosine_similarity(&a, &b);
         assert_eq!(s1, s2);
     }
+
+    #[test]
+    fn test_deterministic_embedder_matches_embed_chunk() {
+        let embedder = DeterministicEmbedder;
+        assert_eq!(embedder.embed("hello").vector, embed_chunk("hello").vector);
+        assert_eq!(embedder.dim(), EMBED_DIM);
+    }
+
+    #[test]
+    fn test_caching_embedder_matches_inner() {
+        let cached = CachingEmbedder::new(DeterministicEmbedder);
+        assert_eq!(cached.embed("hello").vector, embed_chunk("hello").vector);
+        assert_eq!(cached.dim(), EMBED_DIM);
+    }
+
+    #[test]
+    fn test_caching_embedder_reuses_cached_value() {
+        let cached = CachingEmbedder::new(DeterministicEmbedder);
+        let a = cached.embed("repeated text");
+        let b = cached.embed("repeated text");
+        assert_eq!(a.vector, b.vector);
+        assert_eq!(cached.cache.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_caching_embedder_distinct_inputs_cache_separately() {
+        let cached = CachingEmbedder::new(DeterministicEmbedder);
+        cached.embed("one");
+        cached.embed("two");
+        assert_eq!(cached.cache.borrow().len(), 2);
+    }
 }
@@ -1,56 +1,257 @@
 //! Deterministic local index: BTreeMap-based document index with embedding lookup.
 
 use crate::chunk::Chunk;
-use crate::embed::{cosine_similarity, embed_chunk, Embedding};
+use crate::embed::{cosine_similarity, DeterministicEmbedder, Embedder, Embedding};
+use sha2::{Digest, Sha256};
 use std::collections::BTreeMap;
 
-/// An indexed chunk: chunk metadata + precomputed embedding.
+/// Number of leading bytes of `chunk.text` hashed for the cheap first-stage
+/// duplicate check in [`DeterministicIndex::add_chunk`].
+const PARTIAL_HASH_PREFIX_BYTES: usize = 4096;
+
+/// SHA-256 of the first [`PARTIAL_HASH_PREFIX_BYTES`] bytes of `text`, hex
+/// encoded - a cheap identity check before falling back to hashing the
+/// whole text.
+fn partial_content_hash(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let prefix = &bytes[..bytes.len().min(PARTIAL_HASH_PREFIX_BYTES)];
+    hex_sha256(prefix)
+}
+
+/// SHA-256 of the whole `text`, hex encoded.
+fn full_content_hash(text: &str) -> String {
+    hex_sha256(text.as_bytes())
+}
+
+fn hex_sha256(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hex::encode(hasher.finalize())
+}
+
+/// Dedup stats returned by [`DeterministicIndex::add_document`] and
+/// [`DeterministicIndex::add_chunk`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DedupStats {
+    /// Chunks that became new, distinct index entries.
+    pub chunks_added: usize,
+    /// Chunks whose content exactly matched an already-indexed chunk and
+    /// were recorded as an alias instead of a new index entry.
+    pub duplicates_collapsed: usize,
+    /// `embed_chunk` calls skipped thanks to dedup (one per collapsed
+    /// duplicate, since its embedding is reused from the canonical entry).
+    pub embeddings_saved: usize,
+}
+
+impl std::ops::AddAssign for DedupStats {
+    fn add_assign(&mut self, other: Self) {
+        self.chunks_added += other.chunks_added;
+        self.duplicates_collapsed += other.duplicates_collapsed;
+        self.embeddings_saved += other.embeddings_saved;
+    }
+}
+
+/// BM25 term-frequency saturation parameter.
+const BM25_K1: f64 = 1.2;
+/// BM25 chunk-length normalization parameter.
+const BM25_B: f64 = 0.75;
+
+/// Tokenize for BM25: lowercase, split on non-alphanumerics, drop empties.
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_lowercase())
+        .collect()
+}
+
+/// Length-scaled edit-distance budget for fuzzy term matching: short tokens
+/// must match exactly, longer ones tolerate a growing number of typos.
+fn fuzzy_budget(len: usize) -> usize {
+    if len <= 3 {
+        0
+    } else if len <= 7 {
+        1
+    } else {
+        2
+    }
+}
+
+/// Classic O(m*n) dynamic-programming Levenshtein edit distance.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (m, n) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0usize; n + 1]; m + 1];
+    for (i, row) in dp.iter_mut().enumerate().take(m + 1) {
+        row[0] = i;
+    }
+    for j in 0..=n {
+        dp[0][j] = j;
+    }
+    for i in 1..=m {
+        for j in 1..=n {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            dp[i][j] = (dp[i - 1][j] + 1)
+                .min(dp[i][j - 1] + 1)
+                .min(dp[i - 1][j - 1] + cost);
+        }
+    }
+    dp[m][n]
+}
+
+/// An indexed chunk: chunk metadata + precomputed embedding + BM25 term stats.
 #[derive(Debug, Clone)]
 pub struct IndexedChunk {
     pub chunk: Chunk,
     pub embedding: Embedding,
+    /// Term frequency within this chunk, keyed by lowercased token.
+    pub term_freqs: BTreeMap<String, u32>,
+    /// Token count of this chunk (BM25's `|D|`).
+    pub length: usize,
 }
 
 /// Deterministic in-memory index for RAG retrieval.
 ///
-/// Uses BTreeMap keyed by chunk ID for stable iteration order.
+/// Uses BTreeMap keyed by chunk ID for stable iteration order. Generic over
+/// the [`Embedder`] backend used for [`Self::query`]'s cosine-similarity
+/// scoring; defaults to [`DeterministicEmbedder`] (this crate's SHA-256
+/// stub), so `DeterministicIndex::new()` keeps its original exact behavior.
+/// BM25 scoring ([`Self::bm25_query`]) never embeds, so it is unaffected by
+/// the choice of `E`.
 #[derive(Debug, Clone)]
-pub struct DeterministicIndex {
-    /// Chunks indexed by their stable ID.
+pub struct DeterministicIndex<E: Embedder = DeterministicEmbedder> {
+    /// Chunks indexed by their stable ID. Does not include duplicate chunks
+    /// collapsed into an existing entry - see `aliases`.
     chunks: BTreeMap<String, IndexedChunk>,
+    /// Number of chunks each term appears in at least once (BM25's `n(t)`).
+    doc_freq: BTreeMap<String, usize>,
+    /// Sum of all chunk token counts, for BM25's `avgdl`.
+    total_tokens: usize,
+    /// Backend used to embed chunk and query text.
+    embedder: E,
+    /// Partial content hash (see [`partial_content_hash`]) -> canonical
+    /// chunk IDs sharing that partial hash, for the cheap first stage of
+    /// duplicate detection in [`Self::add_chunk`].
+    partial_hash_index: BTreeMap<String, Vec<String>>,
+    /// Chunk ID -> canonical chunk ID, for chunks whose content exactly
+    /// duplicated an already-indexed chunk and were collapsed into it
+    /// instead of getting their own index entry.
+    aliases: BTreeMap<String, String>,
 }
 
-impl DeterministicIndex {
-    /// Create a new empty index.
+impl DeterministicIndex<DeterministicEmbedder> {
+    /// Create a new empty index using the default [`DeterministicEmbedder`].
     pub fn new() -> Self {
+        Self::with_embedder(DeterministicEmbedder)
+    }
+}
+
+impl<E: Embedder> DeterministicIndex<E> {
+    /// Create a new empty index using `embedder` in place of the default.
+    pub fn with_embedder(embedder: E) -> Self {
         Self {
             chunks: BTreeMap::new(),
+            doc_freq: BTreeMap::new(),
+            total_tokens: 0,
+            embedder,
+            partial_hash_index: BTreeMap::new(),
+            aliases: BTreeMap::new(),
         }
     }
 
-    /// Index a document: chunk it and add all chunks to the index.
+    /// Index a document: chunk it and add all chunks to the index,
+    /// collapsing any chunk whose content exactly duplicates an
+    /// already-indexed chunk (see [`Self::add_chunk`]).
     ///
-    /// Returns the number of chunks added.
-    pub fn add_document(&mut self, source_id: &str, text: &str, max_chunk_chars: usize) -> usize {
+    /// Returns dedup stats for the chunks just added.
+    pub fn add_document(
+        &mut self,
+        source_id: &str,
+        text: &str,
+        max_chunk_chars: usize,
+    ) -> DedupStats {
         let chunks = crate::chunk::chunk_text(source_id, text, max_chunk_chars);
-        let count = chunks.len();
+        let mut stats = DedupStats::default();
         for chunk in chunks {
-            let embedding = embed_chunk(&chunk.text);
-            self.chunks.insert(
-                chunk.id.clone(),
-                IndexedChunk { chunk, embedding },
-            );
+            stats += self.add_chunk(chunk);
         }
-        count
+        stats
     }
 
     /// Add a single pre-chunked entry.
-    pub fn add_chunk(&mut self, chunk: Chunk) {
-        let embedding = embed_chunk(&chunk.text);
+    ///
+    /// Before embedding, checks whether `chunk.text` exactly duplicates an
+    /// already-indexed chunk's text: first a cheap partial hash over the
+    /// first [`PARTIAL_HASH_PREFIX_BYTES`] bytes, and only on a partial-hash
+    /// collision a full-content hash to confirm a true duplicate (the same
+    /// two-stage scheme duplicate-file detectors use to avoid hashing whole
+    /// files on every comparison). On a confirmed duplicate, `chunk.id` is
+    /// recorded as an alias of the canonical entry and its embedding is
+    /// reused instead of calling `embed` again; `query`/`bm25_query` keep
+    /// scoring only the canonical entry.
+    pub fn add_chunk(&mut self, chunk: Chunk) -> DedupStats {
+        let partial_hash = partial_content_hash(&chunk.text);
+        if let Some(candidates) = self.partial_hash_index.get(&partial_hash) {
+            let full_hash = full_content_hash(&chunk.text);
+            for candidate_id in candidates {
+                let candidate = &self.chunks[candidate_id];
+                if full_content_hash(&candidate.chunk.text) == full_hash {
+                    self.aliases.insert(chunk.id, candidate_id.clone());
+                    return DedupStats {
+                        chunks_added: 0,
+                        duplicates_collapsed: 1,
+                        embeddings_saved: 1,
+                    };
+                }
+            }
+        }
+
+        let embedding = self.embedder.embed(&chunk.text);
+        let terms = tokenize(&chunk.text);
+        let length = terms.len();
+
+        let mut term_freqs: BTreeMap<String, u32> = BTreeMap::new();
+        for term in terms {
+            *term_freqs.entry(term).or_insert(0) += 1;
+        }
+        for term in term_freqs.keys() {
+            *self.doc_freq.entry(term.clone()).or_insert(0) += 1;
+        }
+        self.total_tokens += length;
+
+        self.partial_hash_index
+            .entry(partial_hash)
+            .or_default()
+            .push(chunk.id.clone());
         self.chunks.insert(
             chunk.id.clone(),
-            IndexedChunk { chunk, embedding },
+            IndexedChunk {
+                chunk,
+                embedding,
+                term_freqs,
+                length,
+            },
         );
+
+        DedupStats {
+            chunks_added: 1,
+            duplicates_collapsed: 0,
+            embeddings_saved: 0,
+        }
+    }
+
+    /// Resolve `id` to its canonical chunk ID: `id` itself unless it's a
+    /// duplicate alias recorded by [`Self::add_chunk`], in which case the
+    /// canonical entry's ID.
+    pub fn resolve_alias<'a>(&'a self, id: &'a str) -> &'a str {
+        self.aliases.get(id).map(String::as_str).unwrap_or(id)
+    }
+
+    /// The alias map: duplicate chunk ID -> canonical chunk ID, for every
+    /// chunk collapsed by [`Self::add_chunk`]'s dedup check.
+    pub fn aliases(&self) -> &BTreeMap<String, String> {
+        &self.aliases
     }
 
     /// Number of indexed chunks.
@@ -68,7 +269,7 @@ impl DeterministicIndex {
     /// Returns results sorted by similarity (descending), with deterministic
     /// tie-breaking by chunk ID (lexicographic ascending).
     pub fn query(&self, query_text: &str, top_k: usize) -> Vec<(f64, &IndexedChunk)> {
-        let query_embedding = embed_chunk(query_text);
+        let query_embedding = self.embedder.embed(query_text);
 
         // Compute similarities
         let mut scored: Vec<(f64, &str, &IndexedChunk)> = self
@@ -94,9 +295,127 @@ impl DeterministicIndex {
             .collect()
     }
 
-    /// Get a chunk by ID.
+    /// Retrieve the top-k chunks for a query by Okapi BM25 lexical score
+    /// (k1=1.2, b=0.75), rather than cosine similarity over embeddings.
+    ///
+    /// When `fuzzy` is true, a query token with no exact match in a chunk
+    /// may still match a vocabulary token present in that chunk within a
+    /// length-scaled Levenshtein budget (see [`fuzzy_budget`]), contributing
+    /// at `score * 0.5^distance` so exact hits still rank above near-misses.
+    ///
+    /// Returns results sorted by score (descending), with deterministic
+    /// tie-breaking by chunk ID (lexicographic ascending).
+    pub fn bm25_query(
+        &self,
+        query_text: &str,
+        top_k: usize,
+        fuzzy: bool,
+    ) -> Vec<(f64, &IndexedChunk)> {
+        if self.chunks.is_empty() {
+            return Vec::new();
+        }
+
+        let query_terms = tokenize(query_text);
+        let n = self.chunks.len() as f64;
+        let avgdl = self.total_tokens as f64 / n;
+
+        let mut scored: Vec<(f64, &str, &IndexedChunk)> = self
+            .chunks
+            .iter()
+            .map(|(id, ic)| {
+                let score = self.bm25_score(&query_terms, ic, n, avgdl, fuzzy);
+                (score, id.as_str(), ic)
+            })
+            .collect();
+
+        scored.sort_by(|a, b| {
+            b.0.partial_cmp(&a.0)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.1.cmp(b.1))
+        });
+
+        scored
+            .into_iter()
+            .take(top_k)
+            .map(|(score, _, ic)| (score, ic))
+            .collect()
+    }
+
+    /// BM25 score of a single chunk against an already-tokenized query.
+    fn bm25_score(
+        &self,
+        query_terms: &[String],
+        ic: &IndexedChunk,
+        n: f64,
+        avgdl: f64,
+        fuzzy: bool,
+    ) -> f64 {
+        let dl = ic.length as f64;
+        let mut score = 0.0;
+
+        for term in query_terms {
+            if let Some(&f) = ic.term_freqs.get(term) {
+                score += self.bm25_term_contribution(term, f as f64, dl, n, avgdl);
+                continue;
+            }
+
+            if !fuzzy {
+                continue;
+            }
+
+            let budget = fuzzy_budget(term.len());
+            if budget == 0 {
+                continue;
+            }
+
+            // Candidates are this chunk's own vocabulary, iterated in
+            // ascending order (BTreeMap), so the first minimal-distance
+            // match found is the lexicographically smallest - a
+            // deterministic tie-break with no extra bookkeeping.
+            let mut best: Option<(usize, &str)> = None;
+            for candidate in ic.term_freqs.keys() {
+                let distance = levenshtein(term, candidate);
+                if distance > budget {
+                    continue;
+                }
+                let improves = match best {
+                    Some((best_dist, _)) => distance < best_dist,
+                    None => true,
+                };
+                if improves {
+                    best = Some((distance, candidate.as_str()));
+                }
+            }
+
+            if let Some((distance, matched)) = best {
+                let f = ic.term_freqs[matched] as f64;
+                let base = self.bm25_term_contribution(matched, f, dl, n, avgdl);
+                score += base * 0.5f64.powi(distance as i32);
+            }
+        }
+
+        score
+    }
+
+    /// A single query term's BM25 contribution against one chunk, given its
+    /// term frequency `f` in that chunk and the chunk's token count `dl`.
+    fn bm25_term_contribution(&self, term: &str, f: f64, dl: f64, n: f64, avgdl: f64) -> f64 {
+        let n_t = *self.doc_freq.get(term).unwrap_or(&0) as f64;
+        let idf = (1.0 + (n - n_t + 0.5) / (n_t + 0.5)).ln();
+        let denom = f + BM25_K1 * (1.0 - BM25_B + BM25_B * dl / avgdl);
+        idf * (f * (BM25_K1 + 1.0)) / denom
+    }
+
+    /// The full indexed vocabulary (every distinct token seen across all
+    /// chunks), in sorted order.
+    pub fn vocabulary(&self) -> impl Iterator<Item = &str> {
+        self.doc_freq.keys().map(String::as_str)
+    }
+
+    /// Get a chunk by ID, following a duplicate alias to its canonical
+    /// entry first (see [`Self::resolve_alias`]).
     pub fn get(&self, id: &str) -> Option<&IndexedChunk> {
-        self.chunks.get(id)
+        self.chunks.get(self.resolve_alias(id))
     }
 
     /// Iterate over all indexed chunks in stable (sorted by ID) order.
@@ -105,7 +424,7 @@ impl DeterministicIndex {
     }
 }
 
-impl Default for DeterministicIndex {
+impl Default for DeterministicIndex<DeterministicEmbedder> {
     fn default() -> Self {
         Self::new()
     }
@@ -118,9 +437,64 @@ mod tests {
     #[test]
     fn test_index_add_document() {
         let mut idx = DeterministicIndex::new();
-        let count = idx.add_document("doc1", "Hello world.\n\nSecond paragraph.", 100);
-        assert!(count > 0);
-        assert_eq!(idx.len(), count);
+        let stats = idx.add_document("doc1", "Hello world.\n\nSecond paragraph.", 100);
+        assert!(stats.chunks_added > 0);
+        assert_eq!(stats.duplicates_collapsed, 0);
+        assert_eq!(idx.len(), stats.chunks_added);
+    }
+
+    #[test]
+    fn test_add_chunk_collapses_exact_duplicate_content() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "Shared boilerplate paragraph.", 100);
+        let stats = idx.add_document("doc2", "Shared boilerplate paragraph.", 100);
+
+        assert_eq!(stats.chunks_added, 0);
+        assert_eq!(stats.duplicates_collapsed, 1);
+        assert_eq!(stats.embeddings_saved, 1);
+        assert_eq!(idx.len(), 1, "duplicate content must not get its own index entry");
+        assert_eq!(idx.aliases().len(), 1);
+    }
+
+    #[test]
+    fn test_add_chunk_distinct_content_is_not_collapsed() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "First unique paragraph.", 100);
+        let stats = idx.add_document("doc2", "Second unique paragraph.", 100);
+
+        assert_eq!(stats.chunks_added, 1);
+        assert_eq!(stats.duplicates_collapsed, 0);
+        assert!(idx.aliases().is_empty());
+    }
+
+    #[test]
+    fn test_alias_resolves_to_canonical_chunk() {
+        let mut idx = DeterministicIndex::new();
+        let first = idx.add_document("doc1", "Duplicated paragraph text.", 100);
+        assert_eq!(first.chunks_added, 1);
+        idx.add_document("doc2", "Duplicated paragraph text.", 100);
+
+        let canonical_id = idx.iter().next().unwrap().0.clone();
+        let (dup_id, canonical) = idx.aliases().iter().next().unwrap();
+        assert_eq!(canonical, &canonical_id);
+        assert!(idx.get(dup_id).is_some());
+        assert_eq!(idx.get(dup_id).unwrap().chunk.id, canonical_id);
+        assert_eq!(idx.resolve_alias(dup_id), canonical_id);
+    }
+
+    #[test]
+    fn test_query_does_not_double_count_duplicate_chunks() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "Rust programming language overview.", 100);
+        idx.add_document("doc2", "Rust programming language overview.", 100);
+        idx.add_document("doc3", "Completely different weather report.", 100);
+
+        let results = idx.query("rust programming", 10);
+        assert_eq!(
+            results.len(),
+            2,
+            "a collapsed duplicate must not appear as a second query result"
+        );
     }
 
     #[test]
@@ -173,4 +547,149 @@ mod tests {
             assert!(ids[i - 1] <= ids[i], "iteration must be sorted by ID");
         }
     }
+
+    #[test]
+    fn test_bm25_query_deterministic() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+        idx.add_document("doc2", "Python is interpreted.", 100);
+
+        let r1 = idx.bm25_query("fast programming", 2, false);
+        let r2 = idx.bm25_query("fast programming", 2, false);
+
+        assert_eq!(r1.len(), r2.len());
+        for (a, b) in r1.iter().zip(r2.iter()) {
+            assert_eq!(a.0, b.0);
+            assert_eq!(a.1.chunk.id, b.1.chunk.id);
+        }
+    }
+
+    #[test]
+    fn test_bm25_query_prefers_lexical_match() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+        idx.add_document("doc2", "Python is interpreted and slow by comparison.", 100);
+
+        let results = idx.bm25_query("fast programming", 1, false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1.chunk.source_id, "doc1");
+        assert!(results[0].0 > 0.0);
+    }
+
+    #[test]
+    fn test_bm25_query_empty_index() {
+        let idx = DeterministicIndex::new();
+        let results = idx.bm25_query("anything", 5, false);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_bm25_no_term_overlap_scores_zero() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "Apples and oranges.", 100);
+
+        let results = idx.bm25_query("zebra quasar", 1, false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, 0.0);
+    }
+
+    #[test]
+    fn test_levenshtein_basic() {
+        assert_eq!(levenshtein("programming", "programing"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_fuzzy_budget_scales_with_length() {
+        assert_eq!(fuzzy_budget(3), 0);
+        assert_eq!(fuzzy_budget(7), 1);
+        assert_eq!(fuzzy_budget(8), 2);
+    }
+
+    #[test]
+    fn test_bm25_fuzzy_matches_typo() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+
+        let exact = idx.bm25_query("programing", 1, false);
+        assert_eq!(exact[0].0, 0.0, "no fuzzy matching: typo scores zero");
+
+        let fuzzy = idx.bm25_query("programing", 1, true);
+        assert!(fuzzy[0].0 > 0.0, "fuzzy matching: typo should still score");
+    }
+
+    #[test]
+    fn test_bm25_fuzzy_down_weights_vs_exact() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "programming programming", 100);
+        idx.add_document("doc2", "programing programing", 100);
+
+        let exact_score = idx.bm25_query("programming", 2, true);
+        let doc1_score = exact_score
+            .iter()
+            .find(|(_, ic)| ic.chunk.source_id == "doc1")
+            .unwrap()
+            .0;
+        let doc2_score = exact_score
+            .iter()
+            .find(|(_, ic)| ic.chunk.source_id == "doc2")
+            .unwrap()
+            .0;
+        assert!(
+            doc1_score > doc2_score,
+            "exact match must outrank a fuzzy match of the same term"
+        );
+    }
+
+    #[test]
+    fn test_bm25_fuzzy_deterministic() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+        idx.add_document("doc2", "Python is interpreted.", 100);
+
+        let r1 = idx.bm25_query("programing", 2, true);
+        let r2 = idx.bm25_query("programing", 2, true);
+        assert_eq!(r1.len(), r2.len());
+        for (a, b) in r1.iter().zip(r2.iter()) {
+            assert_eq!(a.0, b.0);
+            assert_eq!(a.1.chunk.id, b.1.chunk.id);
+        }
+    }
+
+    #[test]
+    fn test_vocabulary_sorted_and_complete() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "zebra apple mango", 100);
+
+        let vocab: Vec<&str> = idx.vocabulary().collect();
+        assert_eq!(vocab, vec!["apple", "mango", "zebra"]);
+    }
+
+    #[test]
+    fn test_with_embedder_matches_default_for_deterministic_backend() {
+        use crate::embed::DeterministicEmbedder;
+
+        let mut default_idx = DeterministicIndex::new();
+        default_idx.add_document("doc1", "Rust programming language.", 100);
+
+        let mut custom_idx = DeterministicIndex::with_embedder(DeterministicEmbedder);
+        custom_idx.add_document("doc1", "Rust programming language.", 100);
+
+        let r1 = default_idx.query("rust", 1);
+        let r2 = custom_idx.query("rust", 1);
+        assert_eq!(r1[0].0, r2[0].0);
+    }
+
+    #[test]
+    fn test_with_caching_embedder_query_still_works() {
+        use crate::embed::{CachingEmbedder, DeterministicEmbedder};
+
+        let mut idx = DeterministicIndex::with_embedder(CachingEmbedder::new(DeterministicEmbedder));
+        idx.add_document("doc1", "Rust is a systems programming language.", 100);
+        idx.add_document("doc2", "Python is great for scripting.", 100);
+
+        let results = idx.query("systems programming", 1);
+        assert_eq!(results.len(), 1);
+    }
 }
@@ -8,7 +8,7 @@ pub mod embed;
 pub mod index;
 pub mod retrieve;
 
-pub use chunk::{chunk_text, Chunk};
-pub use embed::{embed_chunk, Embedding};
-pub use index::DeterministicIndex;
-pub use retrieve::{retrieve, RetrievalResult};
+pub use chunk::{chunk_text, chunk_text_cdc, Chunk};
+pub use embed::{embed_chunk, AsyncEmbedder, CachingEmbedder, DeterministicEmbedder, Embedder, Embedding};
+pub use index::{DedupStats, DeterministicIndex};
+pub use retrieve::{retrieve, RetrievalResult, ScoringMode};
@@ -1,5 +1,6 @@
 //! Deterministic retrieval: query the index and return structured results.
 
+use crate::embed::Embedder;
 use crate::index::{DeterministicIndex, IndexedChunk};
 use serde::{Deserialize, Serialize};
 
@@ -18,12 +19,41 @@ pub struct RetrievalResult {
     pub text: String,
 }
 
-/// Retrieve top-k results from the index for a query.
+/// Ranking strategy used by [`retrieve`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringMode {
+    /// Cosine similarity over hash-based embeddings (see `embed::cosine_similarity`).
+    Embedding,
+    /// Okapi BM25 lexical ranking (see `DeterministicIndex::bm25_query`).
+    Bm25,
+}
+
+/// Retrieve top-k results from the index for a query, ranked by `mode`.
+///
+/// `fuzzy` enables length-scaled Levenshtein term matching (see
+/// [`DeterministicIndex::bm25_query`]) and is only meaningful under
+/// [`ScoringMode::Bm25`]; it's ignored for [`ScoringMode::Embedding`], which
+/// has no notion of individual query terms.
+///
+/// Generic over `index`'s [`Embedder`] backend `E`, so swapping
+/// [`DeterministicIndex::with_embedder`] for a real model doesn't require a
+/// different retrieval function.
 ///
-/// Deterministic: same index + same query = same results, always.
-pub fn retrieve(index: &DeterministicIndex, query: &str, top_k: usize) -> Vec<RetrievalResult> {
-    index
-        .query(query, top_k)
+/// Deterministic under the default embedder: same index + same query + same
+/// mode + same fuzzy toggle = same results, always.
+pub fn retrieve<E: Embedder>(
+    index: &DeterministicIndex<E>,
+    query: &str,
+    top_k: usize,
+    mode: ScoringMode,
+    fuzzy: bool,
+) -> Vec<RetrievalResult> {
+    let scored = match mode {
+        ScoringMode::Embedding => index.query(query, top_k),
+        ScoringMode::Bm25 => index.bm25_query(query, top_k, fuzzy),
+    };
+
+    scored
         .into_iter()
         .map(|(score, ic): (f64, &IndexedChunk)| RetrievalResult {
             chunk_id: ic.chunk.id.clone(),
@@ -46,8 +76,8 @@ mod tests {
         idx.add_document("doc1", "The Rust programming language is fast.", 100);
         idx.add_document("doc2", "Python is interpreted.", 100);
 
-        let r1 = retrieve(&idx, "fast programming", 2);
-        let r2 = retrieve(&idx, "fast programming", 2);
+        let r1 = retrieve(&idx, "fast programming", 2, ScoringMode::Embedding, false);
+        let r2 = retrieve(&idx, "fast programming", 2, ScoringMode::Embedding, false);
 
         assert_eq!(r1.len(), r2.len());
         for (a, b) in r1.iter().zip(r2.iter()) {
@@ -61,10 +91,49 @@ mod tests {
     fn test_retrieve_serializable() {
         let mut idx = DeterministicIndex::new();
         idx.add_document("doc1", "Test content.", 100);
-        let results = retrieve(&idx, "test", 1);
+        let results = retrieve(&idx, "test", 1, ScoringMode::Embedding, false);
         let json = serde_json::to_string(&results).unwrap();
         let parsed: Vec<RetrievalResult> = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.len(), results.len());
         assert_eq!(parsed[0].chunk_id, results[0].chunk_id);
     }
+
+    #[test]
+    fn test_retrieve_bm25_prefers_lexical_match() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+        idx.add_document("doc2", "Python is interpreted and slow by comparison.", 100);
+
+        let results = retrieve(&idx, "fast programming", 1, ScoringMode::Bm25, false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].source_id, "doc1");
+    }
+
+    #[test]
+    fn test_retrieve_bm25_deterministic() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+        idx.add_document("doc2", "Python is interpreted.", 100);
+
+        let r1 = retrieve(&idx, "fast programming", 2, ScoringMode::Bm25, false);
+        let r2 = retrieve(&idx, "fast programming", 2, ScoringMode::Bm25, false);
+
+        assert_eq!(r1.len(), r2.len());
+        for (a, b) in r1.iter().zip(r2.iter()) {
+            assert_eq!(a.chunk_id, b.chunk_id);
+            assert_eq!(a.score, b.score);
+        }
+    }
+
+    #[test]
+    fn test_retrieve_fuzzy_toggle_matches_typo() {
+        let mut idx = DeterministicIndex::new();
+        idx.add_document("doc1", "The Rust programming language is fast.", 100);
+
+        let exact_only = retrieve(&idx, "programing", 1, ScoringMode::Bm25, false);
+        assert_eq!(exact_only[0].score, 0.0);
+
+        let with_fuzzy = retrieve(&idx, "programing", 1, ScoringMode::Bm25, true);
+        assert!(with_fuzzy[0].score > 0.0);
+    }
 }
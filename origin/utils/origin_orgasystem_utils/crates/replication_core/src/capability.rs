@@ -0,0 +1,515 @@
+//! Capability-token authorization for replication, modeled on UCAN
+//! (<https://github.com/ucan-wg/spec>).
+//!
+//! A [`Capability`] is a bearer token a root steward can delegate, scoped
+//! and time-bounded, without sharing the seed key: `issuer` attenuates a
+//! set of `capabilities` to `audience`, who may present the token (plus its
+//! `proof` chain back to the root) to authorize a replicate call. Unlike
+//! [`crate::trust`]'s TUF-style roles (fixed keys signing artifacts),
+//! capability delegation is a chain any holder can re-delegate further,
+//! narrowing scope at each hop.
+//!
+//! `issuer`/`audience` are `did:key` identifiers. This repo's `did:key` is
+//! `did:key:<hex ed25519 pubkey>` - the real spec's multicodec/multibase
+//! varint encoding buys nothing for verification here and would add a
+//! dependency, so it's skipped in favor of the same hex encoding
+//! [`crate::trust`] already uses for keyids.
+//!
+//! Verification ([`verify_capability`]) walks the `proof` chain root-to-leaf
+//! and fails closed on the first problem:
+//!   1. every token's signature verifies against its issuer's `did:key`
+//!   2. each delegation's audience equals the next token's issuer
+//!   3. each delegation only attenuates - never broadens - its parent's
+//!      resource scope or ability
+//!   4. the current time is within every token's `[not_before, expires_at)`
+//!   5. the root issuer is listed as trusted in [`dpack_core::policy::Policy`]
+
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Prefix for this repo's simplified `did:key` identifiers.
+pub const DID_KEY_PREFIX: &str = "did:key:";
+
+/// An action a [`Capability`] authorizes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ability {
+    ReplicateLocal,
+    ReplicateRootball,
+    ReplicateRemote,
+    ReplicateDelta,
+}
+
+/// One granted capability: an ability over a resource scope.
+///
+/// `resource` is a seed-fingerprint scope, e.g. `seed:<fp>` (exact) or
+/// `seed:<prefix>` (a fingerprint prefix, granting every fingerprint it
+/// matches).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityClaim {
+    pub resource: String,
+    pub ability: Ability,
+}
+
+impl CapabilityClaim {
+    /// True if `self` authorizes exactly `resource`/`ability` - the same
+    /// ability, and `self.resource` is a prefix of (or equal to) `resource`.
+    fn permits(&self, resource: &str, ability: Ability) -> bool {
+        self.ability == ability && resource.starts_with(&self.resource)
+    }
+
+    /// True if `self` is at least as narrow as `parent`: never a broader
+    /// resource scope or a different ability.
+    fn attenuates(&self, parent: &CapabilityClaim) -> bool {
+        self.ability == parent.ability && self.resource.starts_with(&parent.resource)
+    }
+}
+
+/// A UCAN-style bearer token: `issuer` delegates `capabilities` to
+/// `audience`, valid within `[not_before, expires_at)`, attested by `proof`
+/// (the parent token this one was delegated from - empty for a
+/// self-issued root) and `signature` (hex ed25519, over this token's
+/// canonical bytes with `signature` itself cleared).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capability {
+    pub issuer: String,
+    pub audience: String,
+    pub capabilities: Vec<CapabilityClaim>,
+    /// Unix timestamp (seconds); the token is invalid before this.
+    pub not_before: i64,
+    /// Unix timestamp (seconds); the token is invalid at or after this.
+    pub expires_at: i64,
+    #[serde(default)]
+    pub proof: Vec<Capability>,
+    #[serde(default)]
+    pub signature: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CapabilityError {
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("hex decode error: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("token is unsigned")]
+    Unsigned,
+    #[error("malformed did:key or signature: {0}")]
+    Malformed(String),
+    #[error("signature by issuer {issuer} failed to verify")]
+    InvalidSignature { issuer: String },
+    #[error("token audience {audience} does not match next issuer {next_issuer}")]
+    AudienceMismatch { audience: String, next_issuer: String },
+    #[error("capability {resource}/{ability:?} is not attenuated from its proof")]
+    NotAttenuated { resource: String, ability: Ability },
+    #[error("token issued by {issuer} is outside its validity window at {now}")]
+    Expired { issuer: String, now: i64 },
+    #[error("root issuer {issuer} is not in policy.trusted_capability_roots")]
+    UntrustedRoot { issuer: String },
+    #[error("proof chain has more than one parent at {issuer}; only linear delegation is supported")]
+    AmbiguousProof { issuer: String },
+    #[error("no capability in the token grants {resource}/{ability:?}")]
+    NotGranted { resource: String, ability: Ability },
+}
+
+/// This repo's `did:key` for an ed25519 public key: `did:key:<hex bytes>`.
+pub fn did_key_of(verifying_key: &VerifyingKey) -> String {
+    format!("{DID_KEY_PREFIX}{}", hex::encode(verifying_key.as_bytes()))
+}
+
+pub(crate) fn verifying_key_from_did(did: &str) -> Result<VerifyingKey, CapabilityError> {
+    let hex_key = did
+        .strip_prefix(DID_KEY_PREFIX)
+        .ok_or_else(|| CapabilityError::Malformed(format!("{did} is not a did:key")))?;
+    let key_bytes: [u8; 32] = hex::decode(hex_key)?
+        .try_into()
+        .map_err(|_| CapabilityError::Malformed(format!("did:key {did} is not a 32-byte ed25519 key")))?;
+    VerifyingKey::from_bytes(&key_bytes).map_err(|e| CapabilityError::Malformed(e.to_string()))
+}
+
+/// Canonical bytes signed for a token: itself (including its `proof`, whose
+/// signatures are already fixed) with its own `signature` field cleared.
+fn canonical_bytes(cap: &Capability) -> Result<Vec<u8>, CapabilityError> {
+    let mut unsigned = cap.clone();
+    unsigned.signature = None;
+    Ok(serde_json::to_vec(&unsigned)?)
+}
+
+impl Capability {
+    /// Issue a self-issued root token: `issuer` grants `capabilities` to
+    /// `audience` directly, with no proof chain.
+    pub fn issue_root(
+        issuer_key: &SigningKey,
+        audience: &str,
+        capabilities: Vec<CapabilityClaim>,
+        not_before: i64,
+        expires_at: i64,
+    ) -> Capability {
+        let mut cap = Capability {
+            issuer: did_key_of(&issuer_key.verifying_key()),
+            audience: audience.to_string(),
+            capabilities,
+            not_before,
+            expires_at,
+            proof: Vec::new(),
+            signature: None,
+        };
+        cap.sign(issuer_key);
+        cap
+    }
+
+    /// Delegate from `self` (the holder re-delegating as `issuer_key`, which
+    /// must match `self.audience`) to `audience`, narrowing to
+    /// `capabilities`. The caller is responsible for ensuring `capabilities`
+    /// attenuates `self`'s grants - [`verify_capability`] checks this on
+    /// the receiving end regardless.
+    pub fn delegate(
+        &self,
+        issuer_key: &SigningKey,
+        audience: &str,
+        capabilities: Vec<CapabilityClaim>,
+        not_before: i64,
+        expires_at: i64,
+    ) -> Capability {
+        let mut cap = Capability {
+            issuer: did_key_of(&issuer_key.verifying_key()),
+            audience: audience.to_string(),
+            capabilities,
+            not_before,
+            expires_at,
+            proof: vec![self.clone()],
+            signature: None,
+        };
+        cap.sign(issuer_key);
+        cap
+    }
+
+    fn sign(&mut self, issuer_key: &SigningKey) {
+        self.signature = None;
+        let body = serde_json::to_vec(self).expect("Capability always serializes");
+        self.signature = Some(hex::encode(issuer_key.sign(&body).to_bytes()));
+    }
+}
+
+/// The result of a successful [`verify_capability`] call, recorded in the
+/// `RG1_SEED_BINDING` gate detail.
+pub struct Delegation {
+    pub chain_len: usize,
+    pub root_issuer: String,
+}
+
+/// Verify that `capability` grants `ability` over `resource`, per the
+/// module-level checks. Fails closed: any problem anywhere in the chain
+/// rejects the whole token, not just the offending link.
+pub fn verify_capability(
+    capability: &Capability,
+    resource: &str,
+    ability: Ability,
+    trusted_roots: &[String],
+    now: i64,
+) -> Result<Delegation, CapabilityError> {
+    // Walk from the leaf (the token presented) down through `proof` to the
+    // root (the self-issued token with no proof), then verify root-to-leaf.
+    let mut chain = vec![capability];
+    loop {
+        let current = *chain.last().expect("chain always has at least the leaf");
+        match current.proof.len() {
+            0 => break,
+            1 => chain.push(&current.proof[0]),
+            _ => {
+                return Err(CapabilityError::AmbiguousProof {
+                    issuer: current.issuer.clone(),
+                })
+            }
+        }
+    }
+    chain.reverse(); // root ..= leaf
+
+    let root = chain[0];
+    if !trusted_roots.iter().any(|trusted| trusted == &root.issuer) {
+        return Err(CapabilityError::UntrustedRoot {
+            issuer: root.issuer.clone(),
+        });
+    }
+
+    for (i, token) in chain.iter().enumerate() {
+        let signature = token.signature.as_deref().ok_or(CapabilityError::Unsigned)?;
+        let verifying_key = verifying_key_from_did(&token.issuer)?;
+        let sig_bytes: [u8; 64] = hex::decode(signature)?
+            .try_into()
+            .map_err(|_| CapabilityError::Malformed(format!("signature {signature} is not 64 bytes")))?;
+        let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+        let body = canonical_bytes(token)?;
+        verifying_key
+            .verify(&body, &sig)
+            .map_err(|_| CapabilityError::InvalidSignature {
+                issuer: token.issuer.clone(),
+            })?;
+
+        if now < token.not_before || now >= token.expires_at {
+            return Err(CapabilityError::Expired {
+                issuer: token.issuer.clone(),
+                now,
+            });
+        }
+
+        if let Some(child) = chain.get(i + 1) {
+            if token.audience != child.issuer {
+                return Err(CapabilityError::AudienceMismatch {
+                    audience: token.audience.clone(),
+                    next_issuer: child.issuer.clone(),
+                });
+            }
+            for claim in &child.capabilities {
+                let covered = token.capabilities.iter().any(|parent_claim| claim.attenuates(parent_claim));
+                if !covered {
+                    return Err(CapabilityError::NotAttenuated {
+                        resource: claim.resource.clone(),
+                        ability: claim.ability,
+                    });
+                }
+            }
+        }
+    }
+
+    let leaf = chain.last().expect("chain always has at least the leaf");
+    if !leaf.capabilities.iter().any(|c| c.permits(resource, ability)) {
+        return Err(CapabilityError::NotGranted {
+            resource: resource.to_string(),
+            ability,
+        });
+    }
+
+    Ok(Delegation {
+        chain_len: chain.len(),
+        root_issuer: root.issuer.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(resource: &str, ability: Ability) -> CapabilityClaim {
+        CapabilityClaim {
+            resource: resource.to_string(),
+            ability,
+        }
+    }
+
+    #[test]
+    fn test_self_issued_root_verifies() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let delegation =
+            verify_capability(&root_cap, "seed:abc", Ability::ReplicateLocal, &trusted, 500).unwrap();
+        assert_eq!(delegation.chain_len, 1);
+        assert_eq!(delegation.root_issuer, did_key_of(&root_key.verifying_key()));
+    }
+
+    #[test]
+    fn test_delegation_chain_verifies() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let leaf_cap = root_cap.delegate(
+            &holder_key,
+            &leaf_did,
+            vec![claim("seed:abc123", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let delegation =
+            verify_capability(&leaf_cap, "seed:abc123", Ability::ReplicateLocal, &trusted, 500).unwrap();
+        assert_eq!(delegation.chain_len, 2);
+    }
+
+    #[test]
+    fn test_rejects_broadened_resource_scope() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc123", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        // Broader than the parent's "seed:abc123" - not a valid attenuation.
+        let leaf_cap = root_cap.delegate(
+            &holder_key,
+            &leaf_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let err = verify_capability(&leaf_cap, "seed:abc", Ability::ReplicateLocal, &trusted, 500)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::NotAttenuated { .. }));
+    }
+
+    #[test]
+    fn test_rejects_broadened_ability() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let leaf_cap = root_cap.delegate(
+            &holder_key,
+            &leaf_did,
+            vec![claim("seed:abc", Ability::ReplicateRemote)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let err = verify_capability(&leaf_cap, "seed:abc", Ability::ReplicateRemote, &trusted, 500)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::NotAttenuated { .. }));
+    }
+
+    #[test]
+    fn test_rejects_audience_mismatch() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_key = SigningKey::generate(&mut rand_core::OsRng);
+        let stranger_key = SigningKey::generate(&mut rand_core::OsRng);
+        let leaf_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&holder_key.verifying_key());
+        let leaf_did = did_key_of(&leaf_key.verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        // Signed by a stranger, not the `holder_key` the root delegated to.
+        let leaf_cap = root_cap.delegate(
+            &stranger_key,
+            &leaf_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let err = verify_capability(&leaf_cap, "seed:abc", Ability::ReplicateLocal, &trusted, 500)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::AudienceMismatch { .. }));
+    }
+
+    #[test]
+    fn test_rejects_expired_token() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let err = verify_capability(&root_cap, "seed:abc", Ability::ReplicateLocal, &trusted, 1_000)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::Expired { .. }));
+    }
+
+    #[test]
+    fn test_rejects_untrusted_root() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+
+        let err = verify_capability(&root_cap, "seed:abc", Ability::ReplicateLocal, &[], 500)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::UntrustedRoot { .. }));
+    }
+
+    #[test]
+    fn test_rejects_tampered_capability() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let mut root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        // Widen the granted scope post-signature, without re-signing.
+        root_cap.capabilities[0].resource = "seed:".to_string();
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let err = verify_capability(&root_cap, "seed:", Ability::ReplicateLocal, &trusted, 500)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::InvalidSignature { .. }));
+    }
+
+    #[test]
+    fn test_not_granted_rejected() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let holder_did = did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let root_cap = Capability::issue_root(
+            &root_key,
+            &holder_did,
+            vec![claim("seed:abc", Ability::ReplicateLocal)],
+            0,
+            1_000,
+        );
+        let trusted = vec![did_key_of(&root_key.verifying_key())];
+
+        let err = verify_capability(&root_cap, "seed:xyz", Ability::ReplicateLocal, &trusted, 500)
+            .unwrap_err();
+        assert!(matches!(err, CapabilityError::NotGranted { .. }));
+    }
+}
@@ -1,5 +1,9 @@
 //! Replication gates: checks that must pass before/after replication.
 
+use crate::capability::{self, Ability, Capability};
+use crate::trust::Signature;
+use dpack_core::CanonicalWriter;
+use ed25519_dalek::{Signer, SigningKey, Verifier};
 use serde::{Deserialize, Serialize};
 
 /// Status of a replication gate.
@@ -26,8 +30,32 @@ pub struct ReplicationReceipt {
     pub root_2i_seed_fingerprint: String,
     pub source_pack_hash: Option<String>,
     pub target_pack_hash: Option<String>,
+    /// The URL a `R3_REMOTE_FETCH` replication pulled its artifact from.
+    /// `None` for every other mode, which all operate on local paths.
+    #[serde(default)]
+    pub source_url: Option<String>,
     pub gates: Vec<ReplicationGateResult>,
     pub passed: bool,
+    /// TUF-style signatures over this receipt's [`Self::canonical_bytes`]
+    /// (with this field cleared), appended by [`crate::trust::sign_receipt`]. Empty
+    /// for an unsigned receipt.
+    #[serde(default)]
+    pub signatures: Vec<Signature>,
+    /// `did:key` of the issuer that authorized this replication by calling
+    /// [`Self::sign`], so the receipt proves who ran it without a
+    /// separate out-of-band channel. `None` for an unsigned receipt.
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Hex ed25519 signature by `issuer` over this receipt's canonical
+    /// bytes (with `signature` itself cleared), set by [`Self::sign`].
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// The capability token authorizing `issuer` to replicate
+    /// `root_2i_seed_fingerprint`, whose `proof` chain traces back to a
+    /// trusted root (see [`crate::capability`]). `None` when `issuer` is
+    /// itself the trusted root and needs no delegation.
+    #[serde(default)]
+    pub delegation_chain: Option<Capability>,
 }
 
 impl ReplicationReceipt {
@@ -47,14 +75,220 @@ impl ReplicationReceipt {
             root_2i_seed_fingerprint: root_2i_seed_fingerprint.to_string(),
             source_pack_hash: source_pack_hash.map(|s| s.to_string()),
             target_pack_hash: target_pack_hash.map(|s| s.to_string()),
+            source_url: None,
             gates,
             passed,
+            signatures: Vec::new(),
+            issuer: None,
+            signature: None,
+            delegation_chain: None,
         }
     }
 
+    /// Attach the remote URL a `R3_REMOTE_FETCH` receipt's artifact was
+    /// fetched from. Builder-style so [`Self::new`] doesn't need a source-url
+    /// parameter every other mode would just pass `None` for.
+    pub fn with_source_url(mut self, url: &str) -> Self {
+        self.source_url = Some(url.to_string());
+        self
+    }
+
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
+
+    /// Canonical byte encoding of this receipt (see [`dpack_core::canonical`]),
+    /// independent of serde/JSON formatting. `signatures` is included as-is;
+    /// callers that need the bytes a signature is computed over (i.e. with
+    /// `signatures` cleared first) should clone and clear it before calling.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut w = CanonicalWriter::new();
+        w.field("operation", |w| {
+            w.string(&self.operation);
+        });
+        w.field("mode", |w| {
+            w.string(&self.mode);
+        });
+        w.field("timestamp", |w| {
+            w.string(&self.timestamp);
+        });
+        w.field("root_2i_seed_fingerprint", |w| {
+            w.string(&self.root_2i_seed_fingerprint);
+        });
+        w.field("source_pack_hash", |w| {
+            w.option_string(self.source_pack_hash.as_deref());
+        });
+        w.field("target_pack_hash", |w| {
+            w.option_string(self.target_pack_hash.as_deref());
+        });
+        w.field("source_url", |w| {
+            w.option_string(self.source_url.as_deref());
+        });
+        w.field("gates", |w| {
+            w.seq(&self.gates, |w, gate| {
+                w.string(&gate.gate);
+                w.string(match gate.status {
+                    RGateStatus::Pass => "pass",
+                    RGateStatus::Fail => "fail",
+                });
+                w.string(&gate.detail);
+            });
+        });
+        w.field("passed", |w| {
+            w.bool(self.passed);
+        });
+        w.field("signatures", |w| {
+            w.seq(&self.signatures, |w, sig| {
+                w.string(&sig.keyid);
+                w.string(&sig.sig);
+            });
+        });
+        w.field("issuer", |w| {
+            w.option_string(self.issuer.as_deref());
+        });
+        w.field("signature", |w| {
+            w.option_string(self.signature.as_deref());
+        });
+        w.field("delegation_chain", |w| match &self.delegation_chain {
+            Some(cap) => {
+                w.bool(true);
+                w.string(&serde_json::to_string(cap).expect("Capability always serializes"));
+            }
+            None => {
+                w.bool(false);
+            }
+        });
+        w.into_bytes()
+    }
+
+    /// Content-addressed ID for this receipt: `SHA-256(canonical_bytes())`.
+    /// Two semantically equal receipts (down to the `signatures` block)
+    /// hash identically across machines and serializer versions.
+    pub fn content_id(&self) -> String {
+        seed_core::compute_sha256(&self.canonical_bytes())
+    }
+
+    /// Bytes authenticated by [`Self::sign`]/[`Self::verify`]: this
+    /// receipt's canonical bytes with `signature` cleared, so the
+    /// signature binds `issuer` and `delegation_chain` but not itself.
+    fn canonical_bytes_for_signature(&self) -> Vec<u8> {
+        let mut unsigned = self.clone();
+        unsigned.signature = None;
+        unsigned.canonical_bytes()
+    }
+
+    /// Attach the capability token authorizing this replication, to be
+    /// included under `issuer`'s signature. Builder-style, like
+    /// [`Self::with_source_url`]; call before [`Self::sign`] so the
+    /// signature covers it.
+    pub fn with_delegation_chain(mut self, delegation_chain: Capability) -> Self {
+        self.delegation_chain = Some(delegation_chain);
+        self
+    }
+
+    /// Sign this receipt as `issuer_key`'s holder: sets `issuer` to its
+    /// `did:key` and `signature` to an ed25519 signature over
+    /// [`Self::canonical_bytes_for_signature`]. Attach a
+    /// [`Self::with_delegation_chain`] first if `issuer_key` is not itself
+    /// a trusted root for `root_2i_seed_fingerprint`.
+    pub fn sign(&self, issuer_key: &SigningKey) -> ReplicationReceipt {
+        let mut signed = self.clone();
+        signed.issuer = Some(capability::did_key_of(&issuer_key.verifying_key()));
+        signed.signature = None;
+        let body = signed.canonical_bytes_for_signature();
+        signed.signature = Some(hex::encode(issuer_key.sign(&body).to_bytes()));
+        signed
+    }
+
+    /// Verify that this receipt was signed by an issuer whose authority to
+    /// replicate `root_2i_seed_fingerprint` traces back to `trusted_root_key`
+    /// (a `did:key`): (1) `signature` verifies against `issuer`, (2) if
+    /// `delegation_chain` is present, its proof chain verifies and roots in
+    /// `trusted_root_key` and its leaf's `audience` is `issuer`, or (3) if
+    /// absent, `issuer` itself must equal `trusted_root_key`. Expiry on
+    /// every token in the chain is checked against this receipt's own
+    /// `timestamp`, not wall-clock time, so a receipt is either valid or
+    /// not regardless of when it is later inspected.
+    pub fn verify(&self, trusted_root_key: &str) -> Result<(), VerifyError> {
+        let issuer = self.issuer.as_deref().ok_or(VerifyError::Unsigned)?;
+        let signature = self.signature.as_deref().ok_or(VerifyError::Unsigned)?;
+
+        let verifying_key = capability::verifying_key_from_did(issuer)
+            .map_err(|e| VerifyError::Malformed(e.to_string()))?;
+        let sig_bytes: [u8; 64] = hex::decode(signature)
+            .map_err(|e| VerifyError::Malformed(e.to_string()))?
+            .try_into()
+            .map_err(|_| VerifyError::Malformed(format!("signature {signature} is not 64 bytes")))?;
+        let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+        let body = self.canonical_bytes_for_signature();
+        verifying_key
+            .verify(&body, &sig)
+            .map_err(|_| VerifyError::InvalidSignature {
+                issuer: issuer.to_string(),
+            })?;
+
+        match &self.delegation_chain {
+            Some(chain) => {
+                if chain.audience != issuer {
+                    return Err(VerifyError::ChainAudienceMismatch {
+                        audience: chain.audience.clone(),
+                        issuer: issuer.to_string(),
+                    });
+                }
+                let ability = ability_for_mode(&self.mode)?;
+                let resource = format!("seed:{}", self.root_2i_seed_fingerprint);
+                let now = chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                    .map_err(|_| VerifyError::InvalidTimestamp(self.timestamp.clone()))?
+                    .timestamp();
+                capability::verify_capability(
+                    chain,
+                    &resource,
+                    ability,
+                    std::slice::from_ref(&trusted_root_key.to_string()),
+                    now,
+                )?;
+            }
+            None if issuer == trusted_root_key => {}
+            None => {
+                return Err(VerifyError::NotAuthorized {
+                    issuer: issuer.to_string(),
+                })
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The ability a receipt's `mode` requires, for [`ReplicationReceipt::verify`].
+fn ability_for_mode(mode: &str) -> Result<Ability, VerifyError> {
+    match mode {
+        "R0_LOCAL_CLONE" | "R2_ZIP_TO_FRESH_REPO_V1" => Ok(Ability::ReplicateLocal),
+        "R1_ROOTBALL_SEED" => Ok(Ability::ReplicateRootball),
+        "R3_REMOTE_FETCH" => Ok(Ability::ReplicateRemote),
+        "R4_DELTA_REPLICATION" => Ok(Ability::ReplicateDelta),
+        other => Err(VerifyError::UnknownMode(other.to_string())),
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    #[error("receipt is not signed")]
+    Unsigned,
+    #[error("malformed issuer did:key or signature: {0}")]
+    Malformed(String),
+    #[error("receipt signature by issuer {issuer} failed to verify")]
+    InvalidSignature { issuer: String },
+    #[error("receipt timestamp {0} is not a valid RFC 3339 timestamp")]
+    InvalidTimestamp(String),
+    #[error("no ability is defined for replication mode {0}")]
+    UnknownMode(String),
+    #[error("delegation chain's leaf audience {audience} does not match issuer {issuer}")]
+    ChainAudienceMismatch { audience: String, issuer: String },
+    #[error("issuer {issuer} is not the trusted root and presented no delegation chain")]
+    NotAuthorized { issuer: String },
+    #[error(transparent)]
+    Capability(#[from] crate::capability::CapabilityError),
 }
 
 #[cfg(test)]
@@ -86,6 +320,16 @@ mod tests {
         assert!(receipt.passed);
     }
 
+    #[test]
+    fn test_receipt_with_source_url() {
+        let receipt = ReplicationReceipt::new("replicate", "R3_REMOTE_FETCH", "fp", None, None, vec![])
+            .with_source_url("https://example.com/snapshot.cpack");
+        assert_eq!(
+            receipt.source_url.as_deref(),
+            Some("https://example.com/snapshot.cpack")
+        );
+    }
+
     #[test]
     fn test_receipt_gate_fail() {
         let gates = vec![ReplicationGateResult {
@@ -97,4 +341,116 @@ mod tests {
             ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, gates);
         assert!(!receipt.passed);
     }
+
+    #[test]
+    fn test_canonical_bytes_deterministic() {
+        let receipt =
+            ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", Some("a"), Some("b"), vec![]);
+        assert_eq!(receipt.canonical_bytes(), receipt.canonical_bytes());
+    }
+
+    #[test]
+    fn test_canonical_bytes_changes_with_content() {
+        let a = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![]);
+        let b = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp2", None, None, vec![]);
+        assert_ne!(a.canonical_bytes(), b.canonical_bytes());
+    }
+
+    #[test]
+    fn test_content_id_sha256_shaped_and_stable() {
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![]);
+        let id1 = receipt.content_id();
+        let id2 = receipt.content_id();
+        assert_eq!(id1, id2);
+        assert_eq!(id1.len(), 64);
+    }
+
+    #[test]
+    fn test_sign_and_verify_as_trusted_root() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let root_did = capability::did_key_of(&root_key.verifying_key());
+
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![]);
+        let signed = receipt.sign(&root_key);
+
+        signed.verify(&root_did).unwrap();
+    }
+
+    #[test]
+    fn test_verify_walks_delegation_chain_to_trusted_root() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let issuer_key = SigningKey::generate(&mut rand_core::OsRng);
+        let root_did = capability::did_key_of(&root_key.verifying_key());
+        let issuer_did = capability::did_key_of(&issuer_key.verifying_key());
+
+        let delegation = Capability::issue_root(
+            &root_key,
+            &issuer_did,
+            vec![capability::CapabilityClaim {
+                resource: "seed:fp".to_string(),
+                ability: Ability::ReplicateLocal,
+            }],
+            0,
+            i64::MAX,
+        );
+
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![])
+            .with_delegation_chain(delegation);
+        let signed = receipt.sign(&issuer_key);
+
+        signed.verify(&root_did).unwrap();
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_receipt() {
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let root_did = capability::did_key_of(&root_key.verifying_key());
+
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![]);
+        let mut signed = receipt.sign(&root_key);
+        signed.root_2i_seed_fingerprint = "tampered".to_string();
+
+        let err = signed.verify(&root_did).unwrap_err();
+        assert!(matches!(err, VerifyError::InvalidSignature { .. }));
+    }
+
+    #[test]
+    fn test_verify_rejects_untrusted_issuer_with_no_chain() {
+        let issuer_key = SigningKey::generate(&mut rand_core::OsRng);
+        let some_other_root_did =
+            capability::did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![]);
+        let signed = receipt.sign(&issuer_key);
+
+        let err = signed.verify(&some_other_root_did).unwrap_err();
+        assert!(matches!(err, VerifyError::NotAuthorized { .. }));
+    }
+
+    #[test]
+    fn test_verify_rejects_chain_not_rooted_in_trusted_key() {
+        let unrelated_root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let issuer_key = SigningKey::generate(&mut rand_core::OsRng);
+        let issuer_did = capability::did_key_of(&issuer_key.verifying_key());
+        let real_trusted_root_did =
+            capability::did_key_of(&SigningKey::generate(&mut rand_core::OsRng).verifying_key());
+
+        let delegation = Capability::issue_root(
+            &unrelated_root_key,
+            &issuer_did,
+            vec![capability::CapabilityClaim {
+                resource: "seed:fp".to_string(),
+                ability: Ability::ReplicateLocal,
+            }],
+            0,
+            i64::MAX,
+        );
+
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", "fp", None, None, vec![])
+            .with_delegation_chain(delegation);
+        let signed = receipt.sign(&issuer_key);
+
+        let err = signed.verify(&real_trusted_root_did).unwrap_err();
+        assert!(matches!(err, VerifyError::Capability(_)));
+    }
 }
@@ -8,9 +8,21 @@
 //! - R0_LOCAL_CLONE: pack + unfurl into a new directory (offline)
 //! - R1_ROOTBALL_SEED: produce a DPACK rootball for transport
 //! - R2_ZIP_TO_FRESH_REPO_V1: unfurl from zip into new repo tree
+//! - R3_REMOTE_FETCH: download a cpack/archive over HTTP(S) and replicate it
+//! - R4_DELTA_REPLICATION: chunk-level delta replication against a base pack
 
+pub mod capability;
 pub mod gate;
 pub mod replicate;
+pub mod trust;
 
-pub use gate::{ReplicationGateResult, ReplicationReceipt};
-pub use replicate::{replicate_local, replicate_rootball, replicate_zip2repo_v1};
+pub use capability::{Ability, Capability, CapabilityClaim, CapabilityError};
+pub use gate::{ReplicationGateResult, ReplicationReceipt, VerifyError};
+pub use replicate::{
+    materialize_chunk_delta_pack, replicate_delta, replicate_local, replicate_remote,
+    replicate_rootball, replicate_zip2repo_v1, ChunkDeltaManifest, ChunkRecipe,
+};
+pub use trust::{
+    sign_receipt, verify_receipt, verify_root_rotation, RoleDelegation, RoleName, RootDelegation,
+    Signature, SignedRoot, TrustError,
+};
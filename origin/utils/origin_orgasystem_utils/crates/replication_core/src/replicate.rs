@@ -1,12 +1,42 @@
 //! Core replication operations.
 
+use crate::capability::{self, Ability, Capability, CapabilityError};
 use crate::gate::{RGateStatus, ReplicationGateResult, ReplicationReceipt};
+use crate::trust::{self, RoleName, SignedRoot, TrustError};
+use compress::chunk::chunk_content;
+use compress::decompress_cpack;
+use compress::frame::{CpackHeader, FrameError};
+use dpack_core::delta::{apply_delta, diff_manifests, DeltaManifest};
 use dpack_core::manifest::DpackManifest;
-use dpack_core::pack::{pack_repo, unfurl_pack, verify_shape_equivalence, PackError};
+use dpack_core::pack::{
+    load_base_manifest, pack_repo, unfurl_pack, verify_shape_equivalence,
+    verify_shape_equivalence_ignoring, PackError,
+};
 use dpack_core::policy::Policy;
+use ed25519_dalek::SigningKey;
 use seed_core::Seed;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
+use url::Url;
+use walkdir::WalkDir;
+
+/// Default cap on a `R3_REMOTE_FETCH` download when the caller doesn't pick
+/// one, large enough for any real rootball while still bounding a
+/// misbehaving or malicious server.
+pub const DEFAULT_MAX_FETCH_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Maximum number of redirect hops `R3_REMOTE_FETCH` will follow before
+/// giving up - a backstop against redirect loops, not a normal case.
+const MAX_REDIRECT_HOPS: u32 = 5;
+
+/// Bookkeeping files `replicate_local` stamps directly into `target_dir`
+/// (the incremental manifest it diffs future calls against, and the
+/// replication receipt). `repo_root` never has these, so the RG2
+/// shape-equivalence gate must ignore them rather than treat them as drift.
+const LOCAL_CLONE_HOUSEKEEPING_FILES: &[&str] = &["manifest.json", "replication_receipt.json"];
 
 #[derive(Error, Debug)]
 pub enum ReplicationError {
@@ -22,6 +52,146 @@ pub enum ReplicationError {
     Failed { reason: String },
     #[error("gate failed (fail-closed): {gate}")]
     GateFailed { gate: String },
+    #[error("cpack frame error: {0}")]
+    Frame(#[from] FrameError),
+    #[error("fetch of {url} failed: {reason}")]
+    Fetch { url: String, reason: String },
+    #[error("expected_hash mismatch: expected {expected}, got {actual}")]
+    HashMismatch { expected: String, actual: String },
+    #[error("unrecognized remote artifact (expected .cpack, .tar.gz, .tgz, or .zip): {0}")]
+    UnrecognizedArtifact(String),
+    #[error("download of {url} exceeded the {max_bytes}-byte cap")]
+    FetchTooLarge { url: String, max_bytes: u64 },
+    #[error("signature trust error: {0}")]
+    Trust(#[from] TrustError),
+    #[error("capability authorization error: {0}")]
+    Capability(#[from] CapabilityError),
+}
+
+/// Evaluate the `RG4_SIGNATURE` gate for a replicate mode: sign a
+/// provisional receipt (built from `gates_so_far`, the gates accumulated
+/// before this one) as the `targets` role with `signing_keys`, then check
+/// the result against `policy`'s `trusted_root`, if configured. Opt-in:
+/// with neither signing keys nor a trusted root configured, the gate
+/// passes as skipped rather than failing.
+///
+/// The provisional receipt exists only to have something to sign and
+/// verify; the caller re-signs the real, final receipt (whose `gates`
+/// also includes this gate's own result) once it is fully built, so the
+/// signature actually shipped covers exactly what is written to disk.
+#[allow(clippy::too_many_arguments)]
+fn gate_signature(
+    mode: &str,
+    seed_fingerprint: &str,
+    source_pack_hash: Option<&str>,
+    target_pack_hash: Option<&str>,
+    gates_so_far: &[ReplicationGateResult],
+    policy: Option<&Policy>,
+    signing_keys: Option<&[SigningKey]>,
+) -> Result<ReplicationGateResult, ReplicationError> {
+    let trusted_root = policy.and_then(|p| p.trusted_root.as_deref());
+
+    if signing_keys.is_none() && trusted_root.is_none() {
+        return Ok(ReplicationGateResult {
+            gate: "RG4_SIGNATURE".to_string(),
+            status: RGateStatus::Pass,
+            detail: "signing not configured, skipped".to_string(),
+        });
+    }
+
+    let Some(keys) = signing_keys else {
+        return Ok(ReplicationGateResult {
+            gate: "RG4_SIGNATURE".to_string(),
+            status: RGateStatus::Fail,
+            detail: "trusted_root configured but no signing keys supplied".to_string(),
+        });
+    };
+
+    let provisional = ReplicationReceipt::new(
+        "replicate",
+        mode,
+        seed_fingerprint,
+        source_pack_hash,
+        target_pack_hash,
+        gates_so_far.to_vec(),
+    );
+    let signed = trust::sign_receipt(&provisional, keys, RoleName::Targets)?;
+
+    let Some(root_path) = trusted_root else {
+        return Ok(ReplicationGateResult {
+            gate: "RG4_SIGNATURE".to_string(),
+            status: RGateStatus::Pass,
+            detail: format!("signed by {} key(s), no trusted_root to verify against", keys.len()),
+        });
+    };
+
+    let signed_root = SignedRoot::load(root_path)?;
+    match trust::verify_receipt(&signed, &signed_root.root, RoleName::Targets) {
+        Ok(()) => Ok(ReplicationGateResult {
+            gate: "RG4_SIGNATURE".to_string(),
+            status: RGateStatus::Pass,
+            detail: "targets signature threshold met".to_string(),
+        }),
+        Err(e) => Ok(ReplicationGateResult {
+            gate: "RG4_SIGNATURE".to_string(),
+            status: RGateStatus::Fail,
+            detail: format!("signature verification failed: {e}"),
+        }),
+    }
+}
+
+/// Evaluate the `RG1_SEED_BINDING` gate: record the seed binding, and - when
+/// `policy.trusted_capability_roots` is non-empty - also authorize `ability`
+/// over `seed:<seed_fingerprint>` against `capability`'s UCAN-style
+/// delegation chain (see [`crate::capability`]). Opt-in like
+/// [`gate_signature`]: with no trusted roots configured, the gate passes
+/// without requiring a capability at all, so existing local/offline
+/// workflows are unaffected.
+fn gate_capability(
+    ability: Ability,
+    seed_fingerprint: &str,
+    capability: Option<&Capability>,
+    policy: Option<&Policy>,
+) -> ReplicationGateResult {
+    let short_fp = &seed_fingerprint[..16.min(seed_fingerprint.len())];
+    let trusted_roots: &[String] = policy
+        .map(|p| p.trusted_capability_roots.as_slice())
+        .unwrap_or(&[]);
+
+    if trusted_roots.is_empty() {
+        return ReplicationGateResult {
+            gate: "RG1_SEED_BINDING".to_string(),
+            status: RGateStatus::Pass,
+            detail: format!("seed_fp={short_fp}"),
+        };
+    }
+
+    let Some(cap) = capability else {
+        return ReplicationGateResult {
+            gate: "RG1_SEED_BINDING".to_string(),
+            status: RGateStatus::Fail,
+            detail: "trusted_capability_roots configured but no capability token supplied"
+                .to_string(),
+        };
+    };
+
+    let resource = format!("seed:{seed_fingerprint}");
+    let now = chrono::Utc::now().timestamp();
+    match capability::verify_capability(cap, &resource, ability, trusted_roots, now) {
+        Ok(delegation) => ReplicationGateResult {
+            gate: "RG1_SEED_BINDING".to_string(),
+            status: RGateStatus::Pass,
+            detail: format!(
+                "seed_fp={short_fp}, capability chain_len={} root={}",
+                delegation.chain_len, delegation.root_issuer
+            ),
+        },
+        Err(e) => ReplicationGateResult {
+            gate: "RG1_SEED_BINDING".to_string(),
+            status: RGateStatus::Fail,
+            detail: format!("capability authorization failed: {e}"),
+        },
+    }
 }
 
 /// R0_LOCAL_CLONE: Pack the repo, then unfurl into a target directory.
@@ -31,6 +201,8 @@ pub fn replicate_local(
     target_dir: &Path,
     seed: &Seed,
     policy: Option<&Policy>,
+    signing_keys: Option<&[SigningKey]>,
+    capability: Option<&Capability>,
 ) -> Result<ReplicationReceipt, ReplicationError> {
     let mut gates = Vec::new();
 
@@ -41,33 +213,99 @@ pub fn replicate_local(
         detail: "policy applied".to_string(),
     });
 
-    // RG1: Seed binding
-    gates.push(ReplicationGateResult {
-        gate: "RG1_SEED_BINDING".to_string(),
-        status: RGateStatus::Pass,
-        detail: format!("seed_fp={}", &seed.fingerprint[..16]),
-    });
+    // RG1: Seed binding (+ capability authorization; see gate_capability)
+    let capability_gate = gate_capability(Ability::ReplicateLocal, &seed.fingerprint, capability, policy);
+    let capability_ok = capability_gate.status == RGateStatus::Pass;
+    gates.push(capability_gate);
+    if !capability_ok {
+        let receipt = ReplicationReceipt::new("replicate", "R0_LOCAL_CLONE", &seed.fingerprint, None, None, gates);
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
 
     // Pack to a temp dir
     let pack_temp = tempfile::tempdir()?;
-    let pack_receipt = pack_repo(repo_root, pack_temp.path(), seed, policy)?;
+    let pack_receipt = pack_repo(repo_root, pack_temp.path(), seed, policy, false)?;
     if !pack_receipt.passed {
         return Err(ReplicationError::GateFailed {
             gate: "pack".to_string(),
         });
     }
     let source_pack_hash = pack_receipt.pack_hash.clone();
+    let source_manifest = load_base_manifest(pack_temp.path())?;
 
-    // Unfurl to target
-    let unfurl_receipt = unfurl_pack(pack_temp.path(), target_dir, seed)?;
-    if !unfurl_receipt.passed {
-        return Err(ReplicationError::GateFailed {
-            gate: "unfurl".to_string(),
-        });
-    }
+    // Unfurl to target. If target_dir already holds the manifest.json this
+    // function stamped on a prior call, diff against it and only touch
+    // files that actually changed instead of rewriting (and later
+    // re-hashing) the whole tree - the common case once a target has been
+    // replicated to once.
+    let prior_manifest_path = target_dir.join("manifest.json");
+    let prior_manifest = if prior_manifest_path.is_file() {
+        load_base_manifest(target_dir)
+            .ok()
+            .filter(|m| m.root_2i_seed_fingerprint == seed.fingerprint)
+    } else {
+        None
+    };
+
+    let (target_manifest, added, changed, removed, reused) = match prior_manifest {
+        Some(base_manifest) => {
+            let delta = diff_manifests(&base_manifest, &source_manifest);
+            let data_dir = pack_temp.path().join("data");
+            let mut added = 0u64;
+            let mut changed = 0u64;
+
+            for (rel_path, entry) in &delta.added_or_changed {
+                let src = data_dir.join(rel_path);
+                let dst = target_dir.join(rel_path);
+                if let Some(parent) = dst.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let content = std::fs::read(&src)?;
+                if source_manifest.hash_scheme.digest(&content) != entry.sha256 {
+                    return Err(ReplicationError::GateFailed {
+                        gate: "unfurl".to_string(),
+                    });
+                }
+                std::fs::write(&dst, &content)?;
+                if base_manifest.files.contains_key(rel_path) {
+                    changed += 1;
+                } else {
+                    added += 1;
+                }
+            }
+            for rel_path in &delta.removed {
+                let path = target_dir.join(rel_path);
+                if path.is_file() {
+                    std::fs::remove_file(&path)?;
+                }
+            }
+
+            let removed = delta.removed.len() as u64;
+            let reused = source_manifest.files.len() as u64 - added - changed;
+            let merged = apply_delta(&base_manifest, &delta)?;
+            (merged, added, changed, removed, reused)
+        }
+        None => {
+            let unfurl_receipt = unfurl_pack(pack_temp.path(), target_dir, seed)?;
+            if !unfurl_receipt.passed {
+                return Err(ReplicationError::GateFailed {
+                    gate: "unfurl".to_string(),
+                });
+            }
+            let added = source_manifest.files.len() as u64;
+            (source_manifest.clone(), added, 0, 0, 0)
+        }
+    };
 
-    // RG2: Shape equivalence
-    let shape_eq = verify_shape_equivalence(repo_root, target_dir)?;
+    // RG2: Shape equivalence (ignoring the bookkeeping files this function
+    // itself stamps into target_dir; see LOCAL_CLONE_HOUSEKEEPING_FILES)
+    let shape_eq = verify_shape_equivalence_ignoring(
+        repo_root,
+        target_dir,
+        LOCAL_CLONE_HOUSEKEEPING_FILES,
+    )?;
     gates.push(ReplicationGateResult {
         gate: "RG2_SHAPE_EQUIVALENCE".to_string(),
         status: if shape_eq {
@@ -96,11 +334,11 @@ pub fn replicate_local(
         });
     }
 
-    // RG3: Content equivalence - re-pack the target and compare hashes
-    let verify_temp = tempfile::tempdir()?;
-    let target_pack_receipt = pack_repo(target_dir, verify_temp.path(), seed, policy)?;
-    let target_pack_hash = target_pack_receipt.pack_hash.clone();
-
+    // RG3: Content equivalence - the target's pack_hash is computed
+    // incrementally from the merged FileEntry map (reusing the unchanged
+    // entries from the prior manifest), not by re-walking and re-hashing
+    // every file in target_dir.
+    let target_pack_hash = Some(target_manifest.pack_hash.clone());
     let content_eq = source_pack_hash.as_deref() == target_pack_hash.as_deref();
     gates.push(ReplicationGateResult {
         gate: "RG3_CONTENT_EQUIVALENCE".to_string(),
@@ -110,12 +348,41 @@ pub fn replicate_local(
             RGateStatus::Fail
         },
         detail: if content_eq {
-            "content hashes identical".to_string()
+            format!(
+                "content hashes identical ({added} added, {changed} changed, {removed} removed, {reused} reused)"
+            )
         } else {
             "content hash mismatch".to_string()
         },
     });
 
+    // RG4: Signature (opt-in; see gate_signature)
+    let signature_gate = gate_signature(
+        "R0_LOCAL_CLONE",
+        &seed.fingerprint,
+        source_pack_hash.as_deref(),
+        target_pack_hash.as_deref(),
+        &gates,
+        policy,
+        signing_keys,
+    )?;
+    let signature_ok = signature_gate.status == RGateStatus::Pass;
+    gates.push(signature_gate);
+
+    if !signature_ok {
+        let receipt = ReplicationReceipt::new(
+            "replicate",
+            "R0_LOCAL_CLONE",
+            &seed.fingerprint,
+            source_pack_hash.as_deref(),
+            target_pack_hash.as_deref(),
+            gates,
+        );
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
     // RG5: Receipt
     gates.push(ReplicationGateResult {
         gate: "RG5_RECEIPT".to_string(),
@@ -123,7 +390,7 @@ pub fn replicate_local(
         detail: "replication receipt emitted".to_string(),
     });
 
-    let receipt = ReplicationReceipt::new(
+    let mut receipt = ReplicationReceipt::new(
         "replicate",
         "R0_LOCAL_CLONE",
         &seed.fingerprint,
@@ -131,9 +398,17 @@ pub fn replicate_local(
         target_pack_hash.as_deref(),
         gates,
     );
+    if let Some(keys) = signing_keys {
+        receipt = trust::sign_receipt(&receipt, keys, RoleName::Targets)?;
+    }
     let receipt_json = receipt.to_json()?;
     std::fs::write(target_dir.join("replication_receipt.json"), &receipt_json)?;
 
+    // Stamp the manifest the next replicate_local call against this same
+    // target_dir will diff against, so it can skip unchanged files.
+    let target_manifest_json = serde_json::to_string_pretty(&target_manifest)?;
+    std::fs::write(target_dir.join("manifest.json"), &target_manifest_json)?;
+
     Ok(receipt)
 }
 
@@ -144,6 +419,8 @@ pub fn replicate_rootball(
     output_dir: &Path,
     seed: &Seed,
     policy: Option<&Policy>,
+    signing_keys: Option<&[SigningKey]>,
+    capability: Option<&Capability>,
 ) -> Result<ReplicationReceipt, ReplicationError> {
     let mut gates = Vec::new();
 
@@ -154,21 +431,52 @@ pub fn replicate_rootball(
         detail: "policy applied".to_string(),
     });
 
-    // RG1: Seed binding
-    gates.push(ReplicationGateResult {
-        gate: "RG1_SEED_BINDING".to_string(),
-        status: RGateStatus::Pass,
-        detail: format!("seed_fp={}", &seed.fingerprint[..16]),
-    });
+    // RG1: Seed binding (+ capability authorization; see gate_capability)
+    let capability_gate = gate_capability(Ability::ReplicateRootball, &seed.fingerprint, capability, policy);
+    let capability_ok = capability_gate.status == RGateStatus::Pass;
+    gates.push(capability_gate);
+    if !capability_ok {
+        let receipt = ReplicationReceipt::new("replicate", "R1_ROOTBALL_SEED", &seed.fingerprint, None, None, gates);
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
 
     // Pack
-    let pack_receipt = pack_repo(repo_root, output_dir, seed, policy)?;
+    let pack_receipt = pack_repo(repo_root, output_dir, seed, policy, false)?;
     if !pack_receipt.passed {
         return Err(ReplicationError::GateFailed {
             gate: "pack".to_string(),
         });
     }
 
+    // RG4: Signature (opt-in; see gate_signature)
+    let signature_gate = gate_signature(
+        "R1_ROOTBALL_SEED",
+        &seed.fingerprint,
+        pack_receipt.pack_hash.as_deref(),
+        None,
+        &gates,
+        policy,
+        signing_keys,
+    )?;
+    let signature_ok = signature_gate.status == RGateStatus::Pass;
+    gates.push(signature_gate);
+
+    if !signature_ok {
+        let receipt = ReplicationReceipt::new(
+            "replicate",
+            "R1_ROOTBALL_SEED",
+            &seed.fingerprint,
+            pack_receipt.pack_hash.as_deref(),
+            None,
+            gates,
+        );
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
     // RG5: Receipt
     gates.push(ReplicationGateResult {
         gate: "RG5_RECEIPT".to_string(),
@@ -176,7 +484,7 @@ pub fn replicate_rootball(
         detail: format!("rootball created at {}", output_dir.display()),
     });
 
-    let receipt = ReplicationReceipt::new(
+    let mut receipt = ReplicationReceipt::new(
         "replicate",
         "R1_ROOTBALL_SEED",
         &seed.fingerprint,
@@ -184,24 +492,57 @@ pub fn replicate_rootball(
         None,
         gates,
     );
+    if let Some(keys) = signing_keys {
+        receipt = trust::sign_receipt(&receipt, keys, RoleName::Targets)?;
+    }
     let receipt_json = receipt.to_json()?;
     std::fs::write(output_dir.join("replication_receipt.json"), &receipt_json)?;
 
     Ok(receipt)
 }
 
+/// R1_ROOTBALL_SEED, serialized as a single deterministic `.zip`.
+///
+/// Packs `repo_root` into a rootball in a temp directory via
+/// [`replicate_rootball`] (so the gates, signing, and
+/// `replication_receipt.json` it writes are unchanged and travel inside the
+/// archive), then zips that directory's contents with
+/// [`write_deterministic_zip`] to `output_zip` - fixed timestamps, sorted
+/// entries, and fixed mode bits, so the archive hash depends only on the
+/// packed content, never on when or where it was produced.
+/// `policy.zip_store_only` selects store-vs-deflate (see [`Policy`]).
+pub fn replicate_rootball_zip(
+    repo_root: &Path,
+    output_zip: &Path,
+    seed: &Seed,
+    policy: Option<&Policy>,
+    signing_keys: Option<&[SigningKey]>,
+    capability: Option<&Capability>,
+) -> Result<ReplicationReceipt, ReplicationError> {
+    let staging = tempfile::tempdir()?;
+    let receipt = replicate_rootball(repo_root, staging.path(), seed, policy, signing_keys, capability)?;
+
+    let store_only = policy.map(|p| p.zip_store_only).unwrap_or(false);
+    write_deterministic_zip(staging.path(), output_zip, store_only)?;
+
+    Ok(receipt)
+}
+
 /// R2_ZIP_TO_FRESH_REPO_V1: Extract a zip and set up as a fresh repo.
 /// In v1, no merge with existing history - clean extraction only.
 ///
-/// Note: This is a simplified v1 implementation that works with a directory
-/// source (simulating zip extraction). Full zip support would add a zip
-/// dependency.
+/// `source` is either a directory (extracted in place, as in the original
+/// v1 implementation) or an actual `.zip` file, which is stream-extracted
+/// via [`extract_zip_secure`] - rejecting `..` components, absolute paths,
+/// and symlink entries - before packing.
 pub fn replicate_zip2repo_v1(
-    source_dir: &Path,
+    source: &Path,
     out_dir: &Path,
     seed: &Seed,
     init_git: bool,
     policy: Option<&Policy>,
+    signing_keys: Option<&[SigningKey]>,
+    capability: Option<&Capability>,
 ) -> Result<ReplicationReceipt, ReplicationError> {
     let mut gates = Vec::new();
 
@@ -212,16 +553,29 @@ pub fn replicate_zip2repo_v1(
         detail: "policy applied".to_string(),
     });
 
-    // RG1: Seed binding
-    gates.push(ReplicationGateResult {
-        gate: "RG1_SEED_BINDING".to_string(),
-        status: RGateStatus::Pass,
-        detail: format!("seed_fp={}", &seed.fingerprint[..16]),
-    });
+    // RG1: Seed binding (+ capability authorization; see gate_capability)
+    let capability_gate = gate_capability(Ability::ReplicateLocal, &seed.fingerprint, capability, policy);
+    let capability_ok = capability_gate.status == RGateStatus::Pass;
+    gates.push(capability_gate);
+    if !capability_ok {
+        let receipt = ReplicationReceipt::new("replicate", "R2_ZIP_TO_FRESH_REPO_V1", &seed.fingerprint, None, None, gates);
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
+    let zip_staging;
+    let source_dir: &Path = if source.is_file() {
+        zip_staging = tempfile::tempdir()?;
+        extract_zip_secure(source, zip_staging.path())?;
+        zip_staging.path()
+    } else {
+        source
+    };
 
     // Pack source, then unfurl to output
     let pack_temp = tempfile::tempdir()?;
-    let pack_receipt = pack_repo(source_dir, pack_temp.path(), seed, policy)?;
+    let pack_receipt = pack_repo(source_dir, pack_temp.path(), seed, policy, false)?;
     if !pack_receipt.passed {
         return Err(ReplicationError::GateFailed {
             gate: "pack".to_string(),
@@ -276,6 +630,33 @@ pub fn replicate_zip2repo_v1(
         }
     }
 
+    // RG4: Signature (opt-in; see gate_signature)
+    let signature_gate = gate_signature(
+        "R2_ZIP_TO_FRESH_REPO_V1",
+        &seed.fingerprint,
+        pack_receipt.pack_hash.as_deref(),
+        None,
+        &gates,
+        policy,
+        signing_keys,
+    )?;
+    let signature_ok = signature_gate.status == RGateStatus::Pass;
+    gates.push(signature_gate);
+
+    if !signature_ok {
+        let receipt = ReplicationReceipt::new(
+            "replicate",
+            "R2_ZIP_TO_FRESH_REPO_V1",
+            &seed.fingerprint,
+            pack_receipt.pack_hash.as_deref(),
+            None,
+            gates,
+        );
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
     // RG5: Receipt
     gates.push(ReplicationGateResult {
         gate: "RG5_RECEIPT".to_string(),
@@ -283,7 +664,7 @@ pub fn replicate_zip2repo_v1(
         detail: "replication receipt emitted".to_string(),
     });
 
-    let receipt = ReplicationReceipt::new(
+    let mut receipt = ReplicationReceipt::new(
         "replicate",
         "R2_ZIP_TO_FRESH_REPO_V1",
         &seed.fingerprint,
@@ -291,111 +672,1456 @@ pub fn replicate_zip2repo_v1(
         None,
         gates,
     );
+    if let Some(keys) = signing_keys {
+        receipt = trust::sign_receipt(&receipt, keys, RoleName::Targets)?;
+    }
     let receipt_json = receipt.to_json()?;
     std::fs::write(out_dir.join("replication_receipt.json"), &receipt_json)?;
 
     Ok(receipt)
 }
 
-/// Read a manifest from a pack directory.
-pub fn read_manifest(pack_dir: &Path) -> Result<DpackManifest, ReplicationError> {
-    let manifest_str = std::fs::read_to_string(pack_dir.join("manifest.json"))?;
-    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
-    Ok(manifest)
-}
+/// R3_REMOTE_FETCH: Download a `.cpack` (or a `.zip`/`.tar.gz`/`.tgz` archive)
+/// from an `http://`/`https://` URL and replicate it into `output_dir`.
+///
+/// The download is streamed to a temp file first, capped at `max_fetch_bytes`
+/// (defaults to [`DEFAULT_MAX_FETCH_BYTES`] when `None`) so a misbehaving or
+/// malicious server can't exhaust disk. Redirects are followed manually
+/// (rather than via ureq's automatic redirect handling) so each hop's host
+/// can be checked: a redirect to a different host is rejected unless that
+/// host appears in `policy.allowed_redirect_hosts`. For a `.cpack`, the fixed
+/// header is parsed and `payload_sha256` is checked against `expected_hash`
+/// (when given) before a single byte is decompressed, exactly mirroring the
+/// fail-closed order `run_verify` uses on a local file. For an archive, the
+/// bytes are extracted into a staging directory and handed to
+/// [`replicate_zip2repo_v1`] so remote and local zip/tar replication share
+/// one code path. Nothing is written under `output_dir` until the artifact
+/// has verified.
+pub fn replicate_remote(
+    url: &str,
+    output_dir: &Path,
+    seed: &Seed,
+    expected_hash: Option<&str>,
+    policy: Option<&Policy>,
+    max_fetch_bytes: Option<u64>,
+    capability: Option<&Capability>,
+) -> Result<ReplicationReceipt, ReplicationError> {
+    let mut gates = Vec::new();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use tempfile::TempDir;
+    // RG0: Policy
+    gates.push(ReplicationGateResult {
+        gate: "RG0_POLICY".to_string(),
+        status: RGateStatus::Pass,
+        detail: "policy applied".to_string(),
+    });
 
-    fn make_test_repo(dir: &Path) -> Seed {
-        std::fs::create_dir_all(dir.join("src")).unwrap();
-        std::fs::write(dir.join("README.md"), "# Test").unwrap();
-        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
-        let seed_dir = dir.join("spec/seed");
-        std::fs::create_dir_all(&seed_dir).unwrap();
-        std::fs::write(seed_dir.join("denotum.seed.2i.yaml"), "test seed").unwrap();
-        Seed::load_from_workspace(dir).unwrap()
+    // RG1: Seed binding (+ capability authorization; see gate_capability)
+    let capability_gate = gate_capability(Ability::ReplicateRemote, &seed.fingerprint, capability, policy);
+    let capability_ok = capability_gate.status == RGateStatus::Pass;
+    gates.push(capability_gate);
+    if !capability_ok {
+        let receipt = ReplicationReceipt::new("replicate", "R3_REMOTE_FETCH", &seed.fingerprint, None, None, gates);
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
     }
 
-    #[test]
-    fn test_replicate_local() {
-        let repo = TempDir::new().unwrap();
-        let target = TempDir::new().unwrap();
-        let seed = make_test_repo(repo.path());
+    // Already authorized above; the delegated zip2repo_v1 call below (for
+    // archive sources) must not re-gate under Ability::ReplicateLocal with
+    // the same token, so it's handed a policy with capability auth cleared.
+    let delegate_policy = policy.map(|p| Policy {
+        trusted_capability_roots: vec![],
+        ..p.clone()
+    });
 
-        let receipt = replicate_local(repo.path(), target.path(), &seed, None).unwrap();
-        assert!(receipt.passed);
-        assert_eq!(receipt.mode, "R0_LOCAL_CLONE");
+    let max_fetch_bytes = max_fetch_bytes.unwrap_or(DEFAULT_MAX_FETCH_BYTES);
+    let allowed_redirect_hosts: &[String] = policy
+        .map(|p| p.allowed_redirect_hosts.as_slice())
+        .unwrap_or(&[]);
 
-        // Verify the target has the same files
-        assert!(target.path().join("README.md").exists());
-        assert!(target.path().join("src/main.rs").exists());
+    let staging = tempfile::tempdir()?;
+    let downloaded = fetch_to_temp(url, staging.path(), max_fetch_bytes, allowed_redirect_hosts)?;
 
-        // Shape equivalence is verified internally by the RG2 gate.
-        // After replication, target also contains replication_receipt.json,
-        // so a raw shape comparison would differ by that one file.
-        assert!(target.path().join("replication_receipt.json").exists());
-    }
+    // RG4: Remote integrity - verify the downloaded bytes before touching output_dir.
+    if is_archive_url(url) {
+        let extracted = staging.path().join("extracted");
+        extract_archive(url, &downloaded, &extracted)?;
 
-    #[test]
-    fn test_replicate_rootball() {
-        let repo = TempDir::new().unwrap();
-        let rootball = TempDir::new().unwrap();
-        let seed = make_test_repo(repo.path());
+        gates.push(ReplicationGateResult {
+            gate: "RG4_REMOTE_INTEGRITY".to_string(),
+            status: RGateStatus::Pass,
+            detail: format!("fetched archive from {url}"),
+        });
 
-        let receipt = replicate_rootball(repo.path(), rootball.path(), &seed, None).unwrap();
-        assert!(receipt.passed);
-        assert_eq!(receipt.mode, "R1_ROOTBALL_SEED");
-        assert!(rootball.path().join("manifest.json").exists());
-        assert!(rootball.path().join("data").exists());
+        // From here on, an extracted archive replicates exactly like a local
+        // zip2repo_v1 source tree. Re-stamp the receipt it already wrote to
+        // `output_dir` with the fetch URL, so the on-disk artifact matches
+        // what's returned here.
+        let receipt = replicate_zip2repo_v1(&extracted, output_dir, seed, false, delegate_policy.as_ref(), None, None)?
+            .with_source_url(url);
+        std::fs::write(
+            output_dir.join("replication_receipt.json"),
+            receipt.to_json()?,
+        )?;
+        return Ok(receipt);
     }
 
-    #[test]
-    fn test_replicate_zip2repo_v1() {
-        let source = TempDir::new().unwrap();
-        let out = TempDir::new().unwrap();
-        let seed = make_test_repo(source.path());
+    let cpack_bytes = std::fs::read(&downloaded)?;
+    let header = CpackHeader::from_bytes(&cpack_bytes)?;
+    let payload_hash = hex::encode(header.payload_sha256);
 
-        let receipt = replicate_zip2repo_v1(source.path(), out.path(), &seed, false, None).unwrap();
-        assert!(receipt.passed);
-        assert_eq!(receipt.mode, "R2_ZIP_TO_FRESH_REPO_V1");
-        assert!(out.path().join("README.md").exists());
+    if let Some(expected) = expected_hash {
+        if expected != payload_hash {
+            gates.push(ReplicationGateResult {
+                gate: "RG4_REMOTE_INTEGRITY".to_string(),
+                status: RGateStatus::Fail,
+                detail: format!("expected_hash mismatch for {url}"),
+            });
+            return Err(ReplicationError::HashMismatch {
+                expected: expected.to_string(),
+                actual: payload_hash,
+            });
+        }
     }
 
-    #[test]
-    fn test_replicate_zip2repo_v1_with_git_init() {
-        let source = TempDir::new().unwrap();
-        let out = TempDir::new().unwrap();
-        let seed = make_test_repo(source.path());
+    gates.push(ReplicationGateResult {
+        gate: "RG4_REMOTE_INTEGRITY".to_string(),
+        status: RGateStatus::Pass,
+        detail: format!("payload_sha256={}", &payload_hash[..16]),
+    });
 
-        let receipt = replicate_zip2repo_v1(source.path(), out.path(), &seed, true, None).unwrap();
-        assert!(receipt.passed);
-        assert!(out.path().join(".git/HEAD").exists());
+    // Decompress (this re-checks payload_sha256 against the compressed bytes)
+    // into a temp DPACK, then verify schema/invariants before unfurling.
+    let dpack_temp = tempfile::tempdir()?;
+    decompress_cpack(&downloaded, dpack_temp.path())?;
+    let manifest = read_manifest(dpack_temp.path())?;
+
+    let unfurl_receipt = unfurl_pack(dpack_temp.path(), output_dir, seed)?;
+    if !unfurl_receipt.passed {
+        return Err(ReplicationError::GateFailed {
+            gate: "unfurl".to_string(),
+        });
     }
 
-    #[test]
-    fn test_replicate_local_preserves_seed_binding() {
-        let repo = TempDir::new().unwrap();
-        let target = TempDir::new().unwrap();
-        let seed = make_test_repo(repo.path());
+    // RG3: Content equivalence - re-pack the unfurled output and compare pack_hash.
+    let verify_temp = tempfile::tempdir()?;
+    let target_pack_receipt = pack_repo(output_dir, verify_temp.path(), seed, policy, false)?;
+    let target_pack_hash = target_pack_receipt.pack_hash.clone();
+    let content_eq = Some(manifest.pack_hash.as_str()) == target_pack_hash.as_deref();
+    gates.push(ReplicationGateResult {
+        gate: "RG3_CONTENT_EQUIVALENCE".to_string(),
+        status: if content_eq {
+            RGateStatus::Pass
+        } else {
+            RGateStatus::Fail
+        },
+        detail: if content_eq {
+            "content hashes identical".to_string()
+        } else {
+            "content hash mismatch".to_string()
+        },
+    });
 
-        let receipt = replicate_local(repo.path(), target.path(), &seed, None).unwrap();
-        assert_eq!(receipt.root_2i_seed_fingerprint, seed.fingerprint);
+    if !content_eq {
+        let receipt = ReplicationReceipt::new(
+            "replicate",
+            "R3_REMOTE_FETCH",
+            &seed.fingerprint,
+            Some(&manifest.pack_hash),
+            target_pack_hash.as_deref(),
+            gates,
+        )
+        .with_source_url(url);
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
     }
 
-    #[test]
-    fn test_replicate_local_content_equivalence() {
-        let repo = TempDir::new().unwrap();
-        let target = TempDir::new().unwrap();
-        let seed = make_test_repo(repo.path());
-
-        let receipt = replicate_local(repo.path(), target.path(), &seed, None).unwrap();
+    // RG5: Receipt
+    gates.push(ReplicationGateResult {
+        gate: "RG5_RECEIPT".to_string(),
+        status: RGateStatus::Pass,
+        detail: format!("fetched from {url}"),
+    });
 
-        // Check that source and target pack hashes match
-        assert!(receipt.source_pack_hash.is_some());
-        assert!(receipt.target_pack_hash.is_some());
+    let receipt = ReplicationReceipt::new(
+        "replicate",
+        "R3_REMOTE_FETCH",
+        &seed.fingerprint,
+        Some(&manifest.pack_hash),
+        target_pack_hash.as_deref(),
+        gates,
+    )
+    .with_source_url(url);
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(output_dir.join("replication_receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// The ordered chunk hashes that reconstruct one file's content: each hash
+/// is either a new chunk shipped under `chunks/` in the same delta, or a
+/// chunk reused byte-for-byte from re-chunking the base pack's own copy of
+/// the same path (see [`replicate_delta`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkRecipe {
+    pub chunk_hashes: Vec<String>,
+}
+
+/// Chunk-granularity refinement of a [`DeltaManifest`]: the same file-level
+/// diff, but with each added/changed file's content further split by the
+/// rolling-hash CDC [`compress::chunk`] uses for chunked cpacks, so a file
+/// that only changed in one place ships just the differing chunks instead
+/// of its entire new content. `recipes` has one entry per path in
+/// `delta.added_or_changed`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkDeltaManifest {
+    pub delta: DeltaManifest,
+    pub recipes: BTreeMap<String, ChunkRecipe>,
+    pub new_chunk_count: u64,
+    pub new_chunk_bytes: u64,
+    pub reused_chunk_count: u64,
+    pub reused_chunk_bytes: u64,
+}
+
+/// R4_DELTA_REPLICATION: Chunk-level delta replication against a base pack.
+///
+/// Packs `repo_root` in full (to get its manifest and content), diffs it
+/// against `base_manifest` with [`diff_manifests`] to find the
+/// added/changed paths, then CDC-chunks each of those paths' new content.
+/// A chunk is written to `output_dir/chunks/<hash>` only if it doesn't
+/// already occur in the base pack's own chunking of that same path (read
+/// from `base_pack_dir`'s `data/` directory, when `base_pack_dir` is a
+/// pack directory rather than a bare `manifest.json`); everything else is
+/// reconstructed on the far side by re-chunking the base file, which is
+/// deterministic and so reproduces byte-identical chunks at the same cut
+/// points. The `RG2_DELTA_RECONSTRUCTION` gate runs [`apply_delta`] against
+/// the produced [`DeltaManifest`] as a fail-closed check that the delta
+/// really does reconstruct the full pack's exact `pack_hash`; see
+/// [`materialize_chunk_delta_pack`] for the matching content-level
+/// reconstruction path.
+pub fn replicate_delta(
+    repo_root: &Path,
+    base_pack_dir: &Path,
+    output_dir: &Path,
+    seed: &Seed,
+    policy: Option<&Policy>,
+    signing_keys: Option<&[SigningKey]>,
+    capability: Option<&Capability>,
+) -> Result<ReplicationReceipt, ReplicationError> {
+    let mut gates = Vec::new();
+
+    // RG0: Policy
+    gates.push(ReplicationGateResult {
+        gate: "RG0_POLICY".to_string(),
+        status: RGateStatus::Pass,
+        detail: "policy applied".to_string(),
+    });
+
+    // RG1: Seed binding (+ capability authorization; see gate_capability)
+    let capability_gate = gate_capability(Ability::ReplicateDelta, &seed.fingerprint, capability, policy);
+    let capability_ok = capability_gate.status == RGateStatus::Pass;
+    gates.push(capability_gate);
+    if !capability_ok {
+        let receipt = ReplicationReceipt::new("replicate", "R4_DELTA_REPLICATION", &seed.fingerprint, None, None, gates);
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
+    // Pack the full repo to a temp dir to get its manifest and content.
+    let pack_temp = tempfile::tempdir()?;
+    let pack_receipt = pack_repo(repo_root, pack_temp.path(), seed, policy, false)?;
+    if !pack_receipt.passed {
+        return Err(ReplicationError::GateFailed {
+            gate: "pack".to_string(),
+        });
+    }
+    let full_manifest = load_base_manifest(pack_temp.path())?;
+    let full_pack_hash = full_manifest.pack_hash.clone();
+    let source_data_dir = pack_temp.path().join("data");
+
+    let base_manifest = load_base_manifest(base_pack_dir)?;
+    let delta = diff_manifests(&base_manifest, &full_manifest);
+
+    // Base content is only available for re-chunking when base_pack_dir is
+    // a real pack directory (not a bare manifest.json); without it, every
+    // chunk of a changed file is simply treated as new.
+    let base_data_dir = base_pack_dir.is_dir().then(|| base_pack_dir.join("data"));
+
+    let chunks_dir = output_dir.join("chunks");
+    std::fs::create_dir_all(&chunks_dir)?;
+
+    let mut recipes: BTreeMap<String, ChunkRecipe> = BTreeMap::new();
+    let mut new_chunk_store: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    let mut reused_chunk_count = 0u64;
+    let mut reused_chunk_bytes = 0u64;
+
+    for rel_path in delta.added_or_changed.keys() {
+        let new_content = std::fs::read(source_data_dir.join(rel_path))?;
+
+        let base_chunk_hashes: std::collections::BTreeSet<String> = match &base_data_dir {
+            Some(dir) if base_manifest.files.contains_key(rel_path) => {
+                let base_content = std::fs::read(dir.join(rel_path))?;
+                chunk_content(&base_content).into_iter().map(|(hash, _)| hash).collect()
+            }
+            _ => std::collections::BTreeSet::new(),
+        };
+
+        let mut chunk_hashes = Vec::new();
+        for (hash, bytes) in chunk_content(&new_content) {
+            chunk_hashes.push(hash.clone());
+            if base_chunk_hashes.contains(&hash) {
+                reused_chunk_count += 1;
+                reused_chunk_bytes += bytes.len() as u64;
+            } else {
+                new_chunk_store.entry(hash).or_insert(bytes);
+            }
+        }
+        recipes.insert(rel_path.clone(), ChunkRecipe { chunk_hashes });
+    }
+
+    let new_chunk_count = new_chunk_store.len() as u64;
+    let new_chunk_bytes: u64 = new_chunk_store.values().map(|b| b.len() as u64).sum();
+    for (hash, bytes) in &new_chunk_store {
+        std::fs::write(chunks_dir.join(hash), bytes)?;
+    }
+
+    // RG2: Delta reconstruction - applying the delta onto the base must
+    // reproduce exactly the full pack's pack_hash (see apply_delta).
+    let (recon_status, recon_detail) = match apply_delta(&base_manifest, &delta) {
+        Ok(reconstructed) if reconstructed.pack_hash == full_pack_hash => (
+            RGateStatus::Pass,
+            format!(
+                "reconstructed pack_hash matches full pack ({})",
+                &full_pack_hash[..16.min(full_pack_hash.len())]
+            ),
+        ),
+        Ok(reconstructed) => (
+            RGateStatus::Fail,
+            format!(
+                "reconstructed pack_hash {} does not match full pack_hash {}",
+                &reconstructed.pack_hash[..16.min(reconstructed.pack_hash.len())],
+                &full_pack_hash[..16.min(full_pack_hash.len())]
+            ),
+        ),
+        Err(e) => (RGateStatus::Fail, format!("delta application failed: {e}")),
+    };
+    let recon_ok = recon_status == RGateStatus::Pass;
+    gates.push(ReplicationGateResult {
+        gate: "RG2_DELTA_RECONSTRUCTION".to_string(),
+        status: recon_status,
+        detail: recon_detail,
+    });
+
+    if !recon_ok {
+        let receipt = ReplicationReceipt::new(
+            "replicate",
+            "R4_DELTA_REPLICATION",
+            &seed.fingerprint,
+            Some(&delta.base_pack_hash),
+            Some(&full_pack_hash),
+            gates,
+        );
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
+    // RG4: Signature (opt-in; see gate_signature)
+    let signature_gate = gate_signature(
+        "R4_DELTA_REPLICATION",
+        &seed.fingerprint,
+        Some(&delta.base_pack_hash),
+        Some(&full_pack_hash),
+        &gates,
+        policy,
+        signing_keys,
+    )?;
+    let signature_ok = signature_gate.status == RGateStatus::Pass;
+    gates.push(signature_gate);
+
+    if !signature_ok {
+        let receipt = ReplicationReceipt::new(
+            "replicate",
+            "R4_DELTA_REPLICATION",
+            &seed.fingerprint,
+            Some(&delta.base_pack_hash),
+            Some(&full_pack_hash),
+            gates,
+        );
+        return Err(ReplicationError::Failed {
+            reason: receipt.to_json().unwrap_or_default(),
+        });
+    }
+
+    // RG5: Receipt
+    gates.push(ReplicationGateResult {
+        gate: "RG5_RECEIPT".to_string(),
+        status: RGateStatus::Pass,
+        detail: format!(
+            "{} new chunks ({} bytes), {} reused ({} bytes)",
+            new_chunk_count, new_chunk_bytes, reused_chunk_count, reused_chunk_bytes
+        ),
+    });
+
+    let mut receipt = ReplicationReceipt::new(
+        "replicate",
+        "R4_DELTA_REPLICATION",
+        &seed.fingerprint,
+        Some(&delta.base_pack_hash),
+        Some(&full_pack_hash),
+        gates,
+    );
+    if let Some(keys) = signing_keys {
+        receipt = trust::sign_receipt(&receipt, keys, RoleName::Targets)?;
+    }
+
+    let chunk_delta = ChunkDeltaManifest {
+        delta,
+        recipes,
+        new_chunk_count,
+        new_chunk_bytes,
+        reused_chunk_count,
+        reused_chunk_bytes,
+    };
+    let chunk_delta_json = serde_json::to_string_pretty(&chunk_delta)?;
+    std::fs::write(output_dir.join("chunk_delta_manifest.json"), &chunk_delta_json)?;
+
+    let receipt_json = receipt.to_json()?;
+    std::fs::write(output_dir.join("replication_receipt.json"), &receipt_json)?;
+
+    Ok(receipt)
+}
+
+/// Reconstruct the full target pack directory (`manifest.json` + `data/`)
+/// from a chunk delta produced by [`replicate_delta`] plus the base pack it
+/// was computed against. For each changed path, re-chunks the base's copy
+/// of that same path (when present) to resolve the reused chunk hashes in
+/// its [`ChunkRecipe`], reads new chunk hashes from `chunk_delta_dir`'s
+/// `chunks/` directory, and concatenates them in order; unchanged paths are
+/// copied straight from the base pack. Fails closed if any chunk is missing
+/// or a reconstructed file's content doesn't hash to its manifest entry.
+pub fn materialize_chunk_delta_pack(
+    chunk_delta_dir: &Path,
+    base_pack_dir: &Path,
+    output_dir: &Path,
+) -> Result<DpackManifest, ReplicationError> {
+    let chunk_delta_str = std::fs::read_to_string(chunk_delta_dir.join("chunk_delta_manifest.json"))?;
+    let chunk_delta: ChunkDeltaManifest = serde_json::from_str(&chunk_delta_str)?;
+
+    let base_manifest = load_base_manifest(base_pack_dir)?;
+    let full_manifest = apply_delta(&base_manifest, &chunk_delta.delta)?;
+
+    let base_data_dir = base_pack_dir.join("data");
+    let new_chunks_dir = chunk_delta_dir.join("chunks");
+    let out_data_dir = output_dir.join("data");
+    std::fs::create_dir_all(&out_data_dir)?;
+
+    let mut base_chunk_cache: BTreeMap<String, BTreeMap<String, Vec<u8>>> = BTreeMap::new();
+
+    for (rel_path, entry) in &full_manifest.files {
+        let dst = out_data_dir.join(rel_path);
+        if let Some(parent) = dst.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let content = if let Some(recipe) = chunk_delta.recipes.get(rel_path) {
+            let mut content = Vec::new();
+            for hash in &recipe.chunk_hashes {
+                let new_chunk_path = new_chunks_dir.join(hash);
+                if new_chunk_path.is_file() {
+                    content.extend(std::fs::read(&new_chunk_path)?);
+                    continue;
+                }
+
+                if !base_chunk_cache.contains_key(rel_path) {
+                    let base_content = std::fs::read(base_data_dir.join(rel_path))?;
+                    let base_chunks: BTreeMap<String, Vec<u8>> =
+                        chunk_content(&base_content).into_iter().collect();
+                    base_chunk_cache.insert(rel_path.clone(), base_chunks);
+                }
+                let Some(bytes) = base_chunk_cache[rel_path].get(hash) else {
+                    return Err(ReplicationError::Failed {
+                        reason: format!("chunk {hash} for {rel_path} is absent from both the delta and the base"),
+                    });
+                };
+                content.extend(bytes.clone());
+            }
+            content
+        } else {
+            std::fs::read(base_data_dir.join(rel_path))?
+        };
+
+        if full_manifest.hash_scheme.digest(&content) != entry.sha256 {
+            return Err(ReplicationError::Failed {
+                reason: format!("hash mismatch reconstructing {rel_path}"),
+            });
+        }
+        std::fs::write(&dst, &content)?;
+    }
+
+    let manifest_json = serde_json::to_string_pretty(&full_manifest)?;
+    std::fs::write(output_dir.join("manifest.json"), &manifest_json)?;
+
+    Ok(full_manifest)
+}
+
+/// Parse `url`'s host, for comparing against the original request host on
+/// a redirect.
+fn url_host(url: &str) -> Result<String, ReplicationError> {
+    let parsed = Url::parse(url).map_err(|e| ReplicationError::Fetch {
+        url: url.to_string(),
+        reason: format!("invalid URL: {e}"),
+    })?;
+    parsed
+        .host_str()
+        .map(|h| h.to_string())
+        .ok_or_else(|| ReplicationError::Fetch {
+            url: url.to_string(),
+            reason: "URL has no host".to_string(),
+        })
+}
+
+/// Stream `url` (an `http://`/`https://` resource) into a temp file under
+/// `staging_dir` and return its path. Kept separate from the caller so the
+/// whole response is on disk - and nothing has touched `output_dir` - before
+/// any verification happens.
+///
+/// Redirects are followed manually (ureq's auto-redirect is disabled via
+/// `redirects(0)`) so each hop's target host can be checked against the
+/// original host and `allowed_redirect_hosts` before it's followed. The
+/// response body is capped at `max_bytes`; anything past that aborts the
+/// download rather than silently truncating it.
+fn fetch_to_temp(
+    url: &str,
+    staging_dir: &Path,
+    max_bytes: u64,
+    allowed_redirect_hosts: &[String],
+) -> Result<PathBuf, ReplicationError> {
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(ReplicationError::Fetch {
+            url: url.to_string(),
+            reason: "only http:// and https:// URLs are supported".to_string(),
+        });
+    }
+
+    let original_host = url_host(url)?;
+    let agent = ureq::AgentBuilder::new().redirects(0).build();
+
+    let mut current_url = url.to_string();
+    let mut hops = 0u32;
+    let response = loop {
+        match agent.get(&current_url).call() {
+            Ok(response) => break response,
+            Err(ureq::Error::Status(code, response)) if (300..400).contains(&code) => {
+                hops += 1;
+                if hops > MAX_REDIRECT_HOPS {
+                    return Err(ReplicationError::Fetch {
+                        url: url.to_string(),
+                        reason: format!("too many redirects (> {MAX_REDIRECT_HOPS})"),
+                    });
+                }
+                let location = response.header("Location").ok_or_else(|| ReplicationError::Fetch {
+                    url: current_url.clone(),
+                    reason: format!("redirect ({code}) without a Location header"),
+                })?;
+                let next_host = url_host(location)?;
+                if next_host != original_host
+                    && !allowed_redirect_hosts.iter().any(|h| h == &next_host)
+                {
+                    return Err(ReplicationError::Fetch {
+                        url: current_url.clone(),
+                        reason: format!(
+                            "redirect to host {next_host} is not in policy.allowed_redirect_hosts"
+                        ),
+                    });
+                }
+                current_url = location.to_string();
+            }
+            Err(e) => {
+                return Err(ReplicationError::Fetch {
+                    url: current_url.clone(),
+                    reason: e.to_string(),
+                })
+            }
+        }
+    };
+
+    let dest_path = staging_dir.join("fetched.bin");
+    let mut dest = std::fs::File::create(&dest_path)?;
+    let mut capped_reader = response.into_reader().take(max_bytes + 1);
+    let copied =
+        std::io::copy(&mut capped_reader, &mut dest).map_err(|e| ReplicationError::Fetch {
+            url: url.to_string(),
+            reason: e.to_string(),
+        })?;
+    if copied > max_bytes {
+        drop(dest);
+        std::fs::remove_file(&dest_path).ok();
+        return Err(ReplicationError::FetchTooLarge {
+            url: url.to_string(),
+            max_bytes,
+        });
+    }
+
+    Ok(dest_path)
+}
+
+fn is_archive_url(url: &str) -> bool {
+    url.ends_with(".zip") || url.ends_with(".tar.gz") || url.ends_with(".tgz")
+}
+
+/// Extract a downloaded zip/tar.gz/tgz archive into `dest_dir`.
+fn extract_archive(
+    url: &str,
+    archive_path: &Path,
+    dest_dir: &Path,
+) -> Result<(), ReplicationError> {
+    std::fs::create_dir_all(dest_dir)?;
+
+    if url.ends_with(".zip") {
+        return extract_zip_secure(archive_path, dest_dir);
+    }
+
+    if url.ends_with(".tar.gz") || url.ends_with(".tgz") {
+        return extract_tar_gz_secure(archive_path, dest_dir);
+    }
+
+    Err(ReplicationError::UnrecognizedArtifact(url.to_string()))
+}
+
+/// Stream-extract a gzipped tar at `archive_path` into `dest_dir`, one
+/// entry at a time.
+///
+/// Mirrors [`extract_zip_secure`]'s discipline: each entry's stored path
+/// is sanitized via [`sanitize_archive_entry_path`], rejecting `..`
+/// components and absolute paths so a crafted archive can't write outside
+/// `dest_dir` (tar-slip). Symlink and hardlink entries are rejected rather
+/// than followed or recreated, since `tar::Archive::unpack` would
+/// otherwise create them at an attacker-chosen link target verbatim.
+fn extract_tar_gz_secure(archive_path: &Path, dest_dir: &Path) -> Result<(), ReplicationError> {
+    let file = std::fs::File::open(archive_path)?;
+    let decoder = flate2::read::GzDecoder::new(file);
+    let mut archive = tar::Archive::new(decoder);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            return Err(ReplicationError::Fetch {
+                url: archive_path.display().to_string(),
+                reason: format!(
+                    "tar entry '{}' is a {}, which is not allowed",
+                    entry.path()?.display(),
+                    if entry_type.is_symlink() { "symlink" } else { "hardlink" }
+                ),
+            });
+        }
+
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let rel_path = sanitize_archive_entry_path(&name)?;
+        let dest_path = dest_dir.join(&rel_path);
+
+        if entry_type.is_dir() {
+            std::fs::create_dir_all(&dest_path)?;
+            continue;
+        }
+
+        if let Some(parent) = dest_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        entry.unpack(&dest_path)?;
+    }
+
+    Ok(())
+}
+
+/// Bit mask (in a zip entry's Unix mode) that identifies the entry's file
+/// type, and the value of that mask for a symlink - used to reject symlink
+/// entries outright rather than materialize them.
+const ZIP_UNIX_TYPE_MASK: u32 = 0o170000;
+const ZIP_UNIX_TYPE_SYMLINK: u32 = 0o120000;
+
+/// Stream-extract `zip_path` into `dest_dir`, one entry at a time.
+///
+/// Each entry's stored path is normalized (`\` -> `/`) and sanitized via
+/// [`sanitize_archive_entry_path`], rejecting `..` components and absolute
+/// paths so a crafted archive can't write outside `dest_dir` (zip-slip).
+/// Symlink entries (detected via the Unix mode bits zip stores for them)
+/// are rejected rather than followed or recreated. Regular file
+/// permissions are restored from those same mode bits when present.
+fn extract_zip_secure(zip_path: &Path, dest_dir: &Path) -> Result<(), ReplicationError> {
+    std::fs::create_dir_all(dest_dir)?;
+    let file = std::fs::File::open(zip_path)?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| ReplicationError::Fetch {
+        url: zip_path.display().to_string(),
+        reason: format!("invalid zip archive: {e}"),
+    })?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| ReplicationError::Fetch {
+            url: zip_path.display().to_string(),
+            reason: format!("invalid zip entry {i}: {e}"),
+        })?;
+
+        let mode = entry.unix_mode();
+        if let Some(mode) = mode {
+            if mode & ZIP_UNIX_TYPE_MASK == ZIP_UNIX_TYPE_SYMLINK {
+                return Err(ReplicationError::Fetch {
+                    url: zip_path.display().to_string(),
+                    reason: format!("zip entry '{}' is a symlink, which is not allowed", entry.name()),
+                });
+            }
+        }
+
+        let rel_path = sanitize_archive_entry_path(entry.name())?;
+        let dest_path = dest_dir.join(&rel_path);
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&dest_path)?;
+            continue;
+        }
+
+        if let Some(parent) = dest_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = std::fs::File::create(&dest_path)?;
+        std::io::copy(&mut entry, &mut out)?;
+
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&dest_path, std::fs::Permissions::from_mode(mode & 0o777))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Normalize a zip entry's stored path (which may use either separator
+/// regardless of the extracting platform) into a safe relative `PathBuf`,
+/// rejecting absolute paths and any `..` component that could escape the
+/// extraction root.
+fn sanitize_archive_entry_path(name: &str) -> Result<PathBuf, ReplicationError> {
+    if name.starts_with('/') || name.starts_with('\\') {
+        return Err(ReplicationError::Fetch {
+            url: name.to_string(),
+            reason: "zip entry has an absolute path".to_string(),
+        });
+    }
+
+    let normalized = name.replace('\\', "/");
+    let mut safe = PathBuf::new();
+    for component in normalized.split('/') {
+        if component.is_empty() || component == "." {
+            continue;
+        }
+        if component == ".." {
+            return Err(ReplicationError::Fetch {
+                url: name.to_string(),
+                reason: "zip entry path contains a `..` component".to_string(),
+            });
+        }
+        safe.push(component);
+    }
+    if safe.as_os_str().is_empty() {
+        return Err(ReplicationError::Fetch {
+            url: name.to_string(),
+            reason: "zip entry has an empty path".to_string(),
+        });
+    }
+    Ok(safe)
+}
+
+/// The MS-DOS-epoch timestamp (1980-01-01, the earliest the zip format
+/// supports) [`write_deterministic_zip`] stamps on every entry, so the
+/// archive hash depends only on content and paths - never on when it was
+/// produced.
+fn deterministic_zip_timestamp() -> zip::DateTime {
+    zip::DateTime::from_date_and_time(1980, 1, 1, 0, 0, 0)
+        .expect("1980-01-01 00:00:00 is a valid MS-DOS date/time")
+}
+
+/// Serialize `pack_dir`'s contents into a single deterministic `.zip` at
+/// `zip_path`: entries are visited in sorted path order (matching the
+/// `WalkDir::sort_by_file_name` convention `dpack_core::pack` already
+/// uses), stamped with [`deterministic_zip_timestamp`], and given fixed
+/// `0o755` (directory) / `0o644` (file) Unix mode bits rather than the
+/// packer's actual umask-derived permissions - trading fidelity to the
+/// source tree's exact permissions for a byte-for-byte reproducible
+/// archive. `store_only` selects `CompressionMethod::Stored` over the
+/// default `Deflated` (see `Policy::zip_store_only`).
+fn write_deterministic_zip(
+    pack_dir: &Path,
+    zip_path: &Path,
+    store_only: bool,
+) -> Result<(), ReplicationError> {
+    let file = std::fs::File::create(zip_path)?;
+    let mut writer = zip::ZipWriter::new(file);
+    let method = if store_only {
+        zip::CompressionMethod::Stored
+    } else {
+        zip::CompressionMethod::Deflated
+    };
+    let timestamp = deterministic_zip_timestamp();
+    let to_zip_error = |e: zip::result::ZipError| ReplicationError::Fetch {
+        url: zip_path.display().to_string(),
+        reason: format!("writing zip entry: {e}"),
+    };
+
+    for entry in WalkDir::new(pack_dir).sort_by_file_name() {
+        let entry = entry.map_err(|e| ReplicationError::Fetch {
+            url: pack_dir.display().to_string(),
+            reason: format!("walking pack dir: {e}"),
+        })?;
+        let rel_path = entry
+            .path()
+            .strip_prefix(pack_dir)
+            .expect("WalkDir yields paths nested under pack_dir");
+        if rel_path.as_os_str().is_empty() {
+            continue;
+        }
+        let name = rel_path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        if entry.file_type().is_dir() {
+            let options = zip::write::FileOptions::default()
+                .last_modified_time(timestamp)
+                .unix_permissions(0o755);
+            writer
+                .add_directory(format!("{name}/"), options)
+                .map_err(to_zip_error)?;
+        } else {
+            let options = zip::write::FileOptions::default()
+                .compression_method(method)
+                .last_modified_time(timestamp)
+                .unix_permissions(0o644);
+            writer.start_file(name, options).map_err(to_zip_error)?;
+            let mut src = std::fs::File::open(entry.path())?;
+            std::io::copy(&mut src, &mut writer)?;
+        }
+    }
+
+    writer.finish().map_err(to_zip_error)?;
+    Ok(())
+}
+
+/// Read a manifest from a pack directory.
+pub fn read_manifest(pack_dir: &Path) -> Result<DpackManifest, ReplicationError> {
+    let manifest_str = std::fs::read_to_string(pack_dir.join("manifest.json"))?;
+    let manifest: DpackManifest = serde_json::from_str(&manifest_str)?;
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn make_test_repo(dir: &Path) -> Seed {
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("README.md"), "# Test").unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        let seed_dir = dir.join("spec/seed");
+        std::fs::create_dir_all(&seed_dir).unwrap();
+        std::fs::write(seed_dir.join("denotum.seed.2i.yaml"), "test seed").unwrap();
+        Seed::load_from_workspace(dir).unwrap()
+    }
+
+    #[test]
+    fn test_replicate_local() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+
+        let receipt = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+        assert!(receipt.passed);
+        assert_eq!(receipt.mode, "R0_LOCAL_CLONE");
+
+        // Verify the target has the same files
+        assert!(target.path().join("README.md").exists());
+        assert!(target.path().join("src/main.rs").exists());
+
+        // Shape equivalence is verified internally by the RG2 gate, which
+        // ignores the bookkeeping files replicate_local stamps into
+        // target_dir (a raw shape comparison would otherwise see drift).
+        assert!(target.path().join("replication_receipt.json").exists());
+        assert!(target.path().join("manifest.json").exists());
+    }
+
+    #[test]
+    fn test_replicate_local_reuses_unchanged_files_on_second_call() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+
+        let first = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+        assert!(first.passed);
+
+        // No changes between calls: everything should come back "reused".
+        let second = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+        assert!(second.passed);
+        let rg3 = second
+            .gates
+            .iter()
+            .find(|g| g.gate == "RG3_CONTENT_EQUIVALENCE")
+            .unwrap();
+        assert_eq!(rg3.status, RGateStatus::Pass);
+        assert!(rg3.detail.contains("0 added, 0 changed"));
+
+        // Change one file and add another, then replicate again.
+        std::fs::write(repo.path().join("README.md"), "# Changed").unwrap();
+        std::fs::write(repo.path().join("new.txt"), "new file").unwrap();
+        let third = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+        assert!(third.passed);
+        assert!(target.path().join("new.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(target.path().join("README.md")).unwrap(),
+            "# Changed"
+        );
+        let rg3 = third
+            .gates
+            .iter()
+            .find(|g| g.gate == "RG3_CONTENT_EQUIVALENCE")
+            .unwrap();
+        assert!(rg3.detail.contains("1 added, 1 changed"));
+
+        // Remove a file from the repo and confirm it disappears from target.
+        std::fs::remove_file(repo.path().join("new.txt")).unwrap();
+        let fourth = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+        assert!(fourth.passed);
+        assert!(!target.path().join("new.txt").exists());
+        let rg3 = fourth
+            .gates
+            .iter()
+            .find(|g| g.gate == "RG3_CONTENT_EQUIVALENCE")
+            .unwrap();
+        assert!(rg3.detail.contains("1 removed"));
+    }
+
+    #[test]
+    fn test_replicate_rootball() {
+        let repo = TempDir::new().unwrap();
+        let rootball = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+
+        let receipt = replicate_rootball(repo.path(), rootball.path(), &seed, None, None, None).unwrap();
+        assert!(receipt.passed);
+        assert_eq!(receipt.mode, "R1_ROOTBALL_SEED");
+        assert!(rootball.path().join("manifest.json").exists());
+        assert!(rootball.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_replicate_rootball_zip_is_deterministic_and_extractable() {
+        let repo = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+
+        let zip_dir_a = TempDir::new().unwrap();
+        let zip_a = zip_dir_a.path().join("rootball.zip");
+        let receipt_a =
+            replicate_rootball_zip(repo.path(), &zip_a, &seed, None, None, None).unwrap();
+        assert!(receipt_a.passed);
+        assert_eq!(receipt_a.mode, "R1_ROOTBALL_SEED");
+
+        let zip_dir_b = TempDir::new().unwrap();
+        let zip_b = zip_dir_b.path().join("rootball.zip");
+        replicate_rootball_zip(repo.path(), &zip_b, &seed, None, None, None).unwrap();
+
+        let bytes_a = std::fs::read(&zip_a).unwrap();
+        let bytes_b = std::fs::read(&zip_b).unwrap();
+        assert_eq!(bytes_a, bytes_b, "rootball zip must be byte-for-byte reproducible");
+
+        let extracted = TempDir::new().unwrap();
+        extract_zip_secure(&zip_a, extracted.path()).unwrap();
+        assert!(extracted.path().join("manifest.json").exists());
+        assert!(extracted.path().join("data").exists());
+    }
+
+    #[test]
+    fn test_replicate_zip2repo_v1_accepts_actual_zip_file() {
+        let source_dir = TempDir::new().unwrap();
+        let seed = make_test_repo(source_dir.path());
+
+        let zip_dir = TempDir::new().unwrap();
+        let zip_path = zip_dir.path().join("source.zip");
+        {
+            let file = std::fs::File::create(&zip_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            let options = zip::write::FileOptions::default();
+            writer.start_file("README.md", options).unwrap();
+            writer
+                .write_all(&std::fs::read(source_dir.path().join("README.md")).unwrap())
+                .unwrap();
+            writer.start_file("src/main.rs", options).unwrap();
+            writer
+                .write_all(&std::fs::read(source_dir.path().join("src/main.rs")).unwrap())
+                .unwrap();
+            writer.finish().unwrap();
+        }
+
+        let out = TempDir::new().unwrap();
+        let receipt =
+            replicate_zip2repo_v1(&zip_path, out.path(), &seed, false, None, None, None).unwrap();
+        assert!(receipt.passed);
+        assert!(out.path().join("README.md").exists());
+        assert!(out.path().join("src/main.rs").exists());
+    }
+
+    #[test]
+    fn test_extract_zip_secure_rejects_path_traversal() {
+        let zip_dir = TempDir::new().unwrap();
+        let zip_path = zip_dir.path().join("evil.zip");
+        {
+            let file = std::fs::File::create(&zip_path).unwrap();
+            let mut writer = zip::ZipWriter::new(file);
+            writer
+                .start_file("../escaped.txt", zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(b"pwned").unwrap();
+            writer.finish().unwrap();
+        }
+
+        let dest = TempDir::new().unwrap();
+        let err = extract_zip_secure(&zip_path, dest.path()).unwrap_err();
+        assert!(matches!(err, ReplicationError::Fetch { .. }));
+        assert!(!dest.path().parent().unwrap().join("escaped.txt").exists());
+    }
+
+    #[test]
+    fn test_extract_tar_gz_secure_rejects_path_traversal() {
+        let tar_dir = TempDir::new().unwrap();
+        let tar_path = tar_dir.path().join("evil.tar.gz");
+        {
+            let file = std::fs::File::create(&tar_path).unwrap();
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            let mut builder = tar::Builder::new(encoder);
+            let mut header = tar::Header::new_gnu();
+            // Header::set_path (used by append_data) refuses `..`
+            // components, so the raw name bytes are written directly.
+            let name = b"../escaped.txt";
+            header.as_old_mut().name[..name.len()].copy_from_slice(name);
+            header.set_size(5);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, &b"pwned"[..]).unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dest = TempDir::new().unwrap();
+        let err = extract_archive("https://example.com/evil.tar.gz", &tar_path, dest.path())
+            .unwrap_err();
+        assert!(matches!(err, ReplicationError::Fetch { .. }));
+        assert!(!dest.path().parent().unwrap().join("escaped.txt").exists());
+    }
+
+    #[test]
+    fn test_extract_tar_gz_secure_rejects_symlink_entries() {
+        let tar_dir = TempDir::new().unwrap();
+        let tar_path = tar_dir.path().join("evil.tar.gz");
+        {
+            let file = std::fs::File::create(&tar_path).unwrap();
+            let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            let mut builder = tar::Builder::new(encoder);
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(tar::EntryType::Symlink);
+            header.set_size(0);
+            header.set_mode(0o777);
+            header.set_cksum();
+            builder
+                .append_link(&mut header, "escaped", "/etc/passwd")
+                .unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let dest = TempDir::new().unwrap();
+        let err = extract_archive("https://example.com/evil.tar.gz", &tar_path, dest.path())
+            .unwrap_err();
+        assert!(matches!(err, ReplicationError::Fetch { .. }));
+        assert!(!dest.path().join("escaped").exists());
+    }
+
+    #[test]
+    fn test_sanitize_archive_entry_path_rejects_dotdot_and_absolute() {
+        assert!(sanitize_archive_entry_path("a/../../etc/passwd").is_err());
+        assert!(sanitize_archive_entry_path("/etc/passwd").is_err());
+        assert!(sanitize_archive_entry_path(r"C:\windows\system32").is_ok());
+        assert_eq!(
+            sanitize_archive_entry_path("a/b/c.txt").unwrap(),
+            PathBuf::from("a/b/c.txt")
+        );
+    }
+
+    #[test]
+    fn test_replicate_zip2repo_v1() {
+        let source = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let seed = make_test_repo(source.path());
+
+        let receipt = replicate_zip2repo_v1(source.path(), out.path(), &seed, false, None, None, None).unwrap();
+        assert!(receipt.passed);
+        assert_eq!(receipt.mode, "R2_ZIP_TO_FRESH_REPO_V1");
+        assert!(out.path().join("README.md").exists());
+    }
+
+    #[test]
+    fn test_replicate_zip2repo_v1_with_git_init() {
+        let source = TempDir::new().unwrap();
+        let out = TempDir::new().unwrap();
+        let seed = make_test_repo(source.path());
+
+        let receipt = replicate_zip2repo_v1(source.path(), out.path(), &seed, true, None, None, None).unwrap();
+        assert!(receipt.passed);
+        assert!(out.path().join(".git/HEAD").exists());
+    }
+
+    #[test]
+    fn test_replicate_local_preserves_seed_binding() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+
+        let receipt = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+        assert_eq!(receipt.root_2i_seed_fingerprint, seed.fingerprint);
+    }
+
+    #[test]
+    fn test_replicate_local_content_equivalence() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+
+        let receipt = replicate_local(repo.path(), target.path(), &seed, None, None, None).unwrap();
+
+        // Check that source and target pack hashes match
+        assert!(receipt.source_pack_hash.is_some());
+        assert!(receipt.target_pack_hash.is_some());
         assert_eq!(receipt.source_pack_hash, receipt.target_pack_hash);
     }
+
+    #[test]
+    fn test_is_archive_url() {
+        assert!(is_archive_url("https://example.com/snapshot.zip"));
+        assert!(is_archive_url("https://example.com/snapshot.tar.gz"));
+        assert!(is_archive_url("https://example.com/snapshot.tgz"));
+        assert!(!is_archive_url("https://example.com/snapshot.cpack"));
+    }
+
+    #[test]
+    fn test_fetch_to_temp_rejects_non_http_scheme() {
+        let staging = TempDir::new().unwrap();
+        let err = fetch_to_temp("file:///etc/passwd", staging.path(), DEFAULT_MAX_FETCH_BYTES, &[])
+            .unwrap_err();
+        assert!(matches!(err, ReplicationError::Fetch { .. }));
+    }
+
+    #[test]
+    fn test_extract_archive_rejects_unrecognized_extension() {
+        let staging = TempDir::new().unwrap();
+        let bogus = staging.path().join("snapshot.rar");
+        std::fs::write(&bogus, b"not really an archive").unwrap();
+        let dest = staging.path().join("out");
+        let err = extract_archive("https://example.com/snapshot.rar", &bogus, &dest).unwrap_err();
+        assert!(matches!(err, ReplicationError::UnrecognizedArtifact(_)));
+    }
+
+    #[test]
+    fn test_replicate_remote_rejects_non_http_scheme() {
+        let staging = TempDir::new().unwrap();
+        let seed_dir = staging.path().join("seed_repo");
+        let seed = make_test_repo(&seed_dir);
+        let out = TempDir::new().unwrap();
+
+        // An ftp:// URL: fetch_to_temp fails before any network is involved
+        // because the scheme is rejected, which is the fail-closed behavior
+        // we want without standing up a real server.
+        let err = replicate_remote(
+            "ftp://example.com/snapshot.cpack",
+            out.path(),
+            &seed,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ReplicationError::Fetch { .. }));
+    }
+
+    #[test]
+    fn test_fetch_to_temp_rejects_malformed_url() {
+        let staging = TempDir::new().unwrap();
+        let err = fetch_to_temp("https:no-host", staging.path(), DEFAULT_MAX_FETCH_BYTES, &[])
+            .unwrap_err();
+        assert!(matches!(err, ReplicationError::Fetch { .. }));
+    }
+
+    #[test]
+    fn test_url_host_extracts_host() {
+        assert_eq!(
+            url_host("https://example.com/snapshot.cpack").unwrap(),
+            "example.com"
+        );
+    }
+
+    fn root_json_with_targets_key(dir: &Path, key: &SigningKey) -> PathBuf {
+        let mut roles = std::collections::BTreeMap::new();
+        roles.insert(
+            RoleName::Targets,
+            crate::trust::RoleDelegation {
+                keyids: vec![hex::encode(key.verifying_key().as_bytes())],
+                threshold: 1,
+            },
+        );
+        let root = crate::trust::RootDelegation { version: 1, roles };
+        let signed_root = SignedRoot {
+            root,
+            signatures: vec![],
+        };
+        let path = dir.join("root.json");
+        std::fs::write(&path, serde_json::to_string_pretty(&signed_root).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_replicate_local_signs_receipt_when_keys_given() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+
+        let receipt =
+            replicate_local(repo.path(), target.path(), &seed, None, Some(std::slice::from_ref(&key)), None)
+                .unwrap();
+        assert!(receipt.passed);
+        assert_eq!(receipt.signatures.len(), 1);
+    }
+
+    #[test]
+    fn test_replicate_local_verifies_signature_against_trusted_root() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+
+        let root_dir = TempDir::new().unwrap();
+        let root_path = root_json_with_targets_key(root_dir.path(), &key);
+        let policy = Policy {
+            trusted_root: Some(root_path),
+            ..Policy::default()
+        };
+
+        let receipt = replicate_local(
+            repo.path(),
+            target.path(),
+            &seed,
+            Some(&policy),
+            Some(std::slice::from_ref(&key)),
+            None,
+        )
+        .unwrap();
+        assert!(receipt.passed);
+        assert!(receipt
+            .gates
+            .iter()
+            .any(|g| g.gate == "RG4_SIGNATURE" && g.status == RGateStatus::Pass));
+    }
+
+    #[test]
+    fn test_replicate_local_fails_closed_on_untrusted_signer() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let trusted_key = SigningKey::generate(&mut rand_core::OsRng);
+        let wrong_key = SigningKey::generate(&mut rand_core::OsRng);
+
+        let root_dir = TempDir::new().unwrap();
+        let root_path = root_json_with_targets_key(root_dir.path(), &trusted_key);
+        let policy = Policy {
+            trusted_root: Some(root_path),
+            ..Policy::default()
+        };
+
+        let err = replicate_local(
+            repo.path(),
+            target.path(),
+            &seed,
+            Some(&policy),
+            Some(std::slice::from_ref(&wrong_key)),
+            None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, ReplicationError::Failed { .. }));
+    }
+
+    #[test]
+    fn test_replicate_local_fails_closed_when_trusted_root_set_without_keys() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+
+        let root_dir = TempDir::new().unwrap();
+        let root_path = root_json_with_targets_key(root_dir.path(), &key);
+        let policy = Policy {
+            trusted_root: Some(root_path),
+            ..Policy::default()
+        };
+
+        let err = replicate_local(repo.path(), target.path(), &seed, Some(&policy), None, None).unwrap_err();
+        assert!(matches!(err, ReplicationError::Failed { .. }));
+    }
+
+    #[test]
+    fn test_replicate_local_authorizes_with_trusted_capability_root() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let policy = Policy {
+            trusted_capability_roots: vec![capability::did_key_of(&root_key.verifying_key())],
+            ..Policy::default()
+        };
+        let cap = Capability::issue_root(
+            &root_key,
+            "did:key:anyone",
+            vec![capability::CapabilityClaim {
+                resource: format!("seed:{}", seed.fingerprint),
+                ability: Ability::ReplicateLocal,
+            }],
+            0,
+            i64::MAX,
+        );
+
+        let receipt = replicate_local(
+            repo.path(),
+            target.path(),
+            &seed,
+            Some(&policy),
+            None,
+            Some(&cap),
+        )
+        .unwrap();
+        assert!(receipt.passed);
+        assert!(receipt
+            .gates
+            .iter()
+            .any(|g| g.gate == "RG1_SEED_BINDING" && g.status == RGateStatus::Pass));
+    }
+
+    #[test]
+    fn test_replicate_local_fails_closed_without_capability_when_roots_configured() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let policy = Policy {
+            trusted_capability_roots: vec![capability::did_key_of(&root_key.verifying_key())],
+            ..Policy::default()
+        };
+
+        let err =
+            replicate_local(repo.path(), target.path(), &seed, Some(&policy), None, None)
+                .unwrap_err();
+        assert!(matches!(err, ReplicationError::Failed { .. }));
+    }
+
+    #[test]
+    fn test_replicate_local_fails_closed_on_untrusted_capability_root() {
+        let repo = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let other_root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let policy = Policy {
+            trusted_capability_roots: vec![capability::did_key_of(&other_root_key.verifying_key())],
+            ..Policy::default()
+        };
+        let cap = Capability::issue_root(
+            &root_key,
+            "did:key:anyone",
+            vec![capability::CapabilityClaim {
+                resource: format!("seed:{}", seed.fingerprint),
+                ability: Ability::ReplicateLocal,
+            }],
+            0,
+            i64::MAX,
+        );
+
+        let err = replicate_local(
+            repo.path(),
+            target.path(),
+            &seed,
+            Some(&policy),
+            None,
+            Some(&cap),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ReplicationError::Failed { .. }));
+    }
+
+    #[test]
+    fn test_replicate_delta_roundtrips_to_full_pack() {
+        let repo = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        let base_pack = TempDir::new().unwrap();
+        let pack_receipt = pack_repo(repo.path(), base_pack.path(), &seed, None, false).unwrap();
+        assert!(pack_receipt.passed);
+
+        // Change one file and add another after the base pack was taken.
+        std::fs::write(repo.path().join("src/main.rs"), "fn main() { println!(\"hi\"); }").unwrap();
+        std::fs::write(repo.path().join("src/lib.rs"), "pub fn helper() {}").unwrap();
+
+        let delta_dir = TempDir::new().unwrap();
+        let receipt = replicate_delta(repo.path(), base_pack.path(), delta_dir.path(), &seed, None, None, None).unwrap();
+        assert!(receipt.passed);
+        assert_eq!(receipt.mode, "R4_DELTA_REPLICATION");
+        assert!(delta_dir.path().join("chunk_delta_manifest.json").exists());
+
+        let reconstructed_dir = TempDir::new().unwrap();
+        let reconstructed =
+            materialize_chunk_delta_pack(delta_dir.path(), base_pack.path(), reconstructed_dir.path()).unwrap();
+        assert_eq!(reconstructed.pack_hash, receipt.target_pack_hash.unwrap());
+        assert_eq!(
+            std::fs::read(reconstructed_dir.path().join("data/src/main.rs")).unwrap(),
+            b"fn main() { println!(\"hi\"); }"
+        );
+        assert_eq!(
+            std::fs::read(reconstructed_dir.path().join("data/src/lib.rs")).unwrap(),
+            b"pub fn helper() {}"
+        );
+    }
+
+    #[test]
+    fn test_replicate_delta_reuses_chunks_unchanged_within_a_modified_file() {
+        let repo = TempDir::new().unwrap();
+        let seed = make_test_repo(repo.path());
+        // A large, repeated-pattern file so FastCDC settles on multiple chunks.
+        let original: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(repo.path().join("src/big.bin"), &original).unwrap();
+
+        let base_pack = TempDir::new().unwrap();
+        pack_repo(repo.path(), base_pack.path(), &seed, None, false).unwrap();
+
+        // Append a small amount of new content; most chunks should be unaffected.
+        let mut modified = original.clone();
+        modified.extend_from_slice(b"trailing change");
+        std::fs::write(repo.path().join("src/big.bin"), &modified).unwrap();
+
+        let delta_dir = TempDir::new().unwrap();
+        let receipt = replicate_delta(repo.path(), base_pack.path(), delta_dir.path(), &seed, None, None, None).unwrap();
+        assert!(receipt.passed);
+
+        let chunk_delta_str = std::fs::read_to_string(delta_dir.path().join("chunk_delta_manifest.json")).unwrap();
+        let chunk_delta: ChunkDeltaManifest = serde_json::from_str(&chunk_delta_str).unwrap();
+        assert!(
+            chunk_delta.reused_chunk_count > 0,
+            "expected at least one chunk to be reused from the base, got {chunk_delta:?}"
+        );
+    }
 }
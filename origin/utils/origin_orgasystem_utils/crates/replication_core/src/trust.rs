@@ -0,0 +1,405 @@
+//! TUF-style signing and trust for replication receipts.
+//!
+//! Three roles, each backed by one or more ed25519 keypairs:
+//!   - `root`: signs the key-delegation document ([`RootDelegation`]) that
+//!     lists which public keys are authorized for every role, and the
+//!     signature threshold required for each.
+//!   - `targets`: signs a whole [`ReplicationReceipt`] (see
+//!     [`sign_receipt`]), whose source/target `pack_hash` values it thereby
+//!     covers, attesting that the pack came from a trusted steward. No
+//!     per-file `sha256` map is signed.
+//!   - `snapshot`: a role a [`RootDelegation`] can list keys and a
+//!     threshold for; no function in this module signs or verifies with it.
+//!
+//! A signature block is `signatures: [{keyid, sig}]`, where `keyid` is the
+//! hex SHA-256 of the signer's public key and `sig` is the hex ed25519
+//! signature over the signed body: for a receipt, its
+//! [`ReplicationReceipt::canonical_bytes`] with its own `signatures` field
+//! cleared first; for a delegation document, the serde JSON of the
+//! [`RootDelegation`] itself.
+//! Verification is fail-closed throughout: an unknown keyid, an invalid
+//! signature, or an unmet threshold all reject.
+//!
+//! Key rotation: a new [`RootDelegation`] is only accepted if it is signed
+//! by a threshold of the *previous* root's `root`-role keys (see
+//! [`verify_root_rotation`]), so compromising the new keys alone is not
+//! enough to take over trust.
+
+use crate::gate::ReplicationReceipt;
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A role in the delegation model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RoleName {
+    Root,
+    Targets,
+    Snapshot,
+}
+
+impl RoleName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoleName::Root => "root",
+            RoleName::Targets => "targets",
+            RoleName::Snapshot => "snapshot",
+        }
+    }
+}
+
+/// A single signature over a signed body's canonical bytes (see the module
+/// doc for what those are for a receipt and for a delegation document).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Signature {
+    /// Hex SHA-256 of the signer's ed25519 public key.
+    pub keyid: String,
+    /// Hex ed25519 signature.
+    pub sig: String,
+}
+
+/// One role's authorized keys and the number of valid signatures required.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleDelegation {
+    /// Hex-encoded ed25519 public keys (each keyid is the hex SHA-256 of
+    /// the raw key bytes here, not of this hex string).
+    pub keyids: Vec<String>,
+    /// Number of distinct, valid signatures from `keyids` required.
+    pub threshold: usize,
+}
+
+/// The key-delegation document: which keys are authorized for each role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootDelegation {
+    pub version: u32,
+    pub roles: BTreeMap<RoleName, RoleDelegation>,
+}
+
+/// A [`RootDelegation`] plus the signatures authorizing it (by the
+/// previous root, for rotation, or self-issued for an initial root).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedRoot {
+    pub root: RootDelegation,
+    #[serde(default)]
+    pub signatures: Vec<Signature>,
+}
+
+impl SignedRoot {
+    /// Load a `root.json` (or equivalently-shaped file) from disk.
+    pub fn load(path: &Path) -> Result<Self, TrustError> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TrustError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("hex decode error: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("malformed ed25519 key or signature: {0}")]
+    Malformed(String),
+    #[error("role {role} has no delegation in this root")]
+    UnknownRole { role: String },
+    #[error("signature {keyid} is not an authorized key for role {role}")]
+    UnknownKeyId { keyid: String, role: String },
+    #[error("signature {keyid} failed to verify")]
+    InvalidSignature { keyid: String },
+    #[error("only {valid}/{threshold} valid signatures for role {role}")]
+    ThresholdNotMet {
+        role: String,
+        valid: usize,
+        threshold: usize,
+    },
+}
+
+fn keyid_for(verifying_key: &VerifyingKey) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(verifying_key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Canonical bytes signed for a receipt: [`ReplicationReceipt::canonical_bytes`]
+/// with `signatures` cleared first, so a signature only binds the receipt's
+/// content, not any other role's signatures over it.
+fn canonical_receipt_bytes(receipt: &ReplicationReceipt) -> Result<Vec<u8>, TrustError> {
+    let mut unsigned = receipt.clone();
+    unsigned.signatures.clear();
+    Ok(unsigned.canonical_bytes())
+}
+
+/// Canonical bytes signed for a root delegation document: just the `root`
+/// field, not the outer `signatures` block.
+fn canonical_root_bytes(root: &RootDelegation) -> Result<Vec<u8>, TrustError> {
+    Ok(serde_json::to_vec(root)?)
+}
+
+fn sign_bytes(body: &[u8], keys: &[SigningKey]) -> Vec<Signature> {
+    keys.iter()
+        .map(|key| Signature {
+            keyid: keyid_for(&key.verifying_key()),
+            sig: hex::encode(key.sign(body).to_bytes()),
+        })
+        .collect()
+}
+
+/// Sign `receipt` as `role`, returning a copy with `signatures` appended
+/// (existing signatures, e.g. from another role, are preserved).
+pub fn sign_receipt(
+    receipt: &ReplicationReceipt,
+    keys: &[SigningKey],
+    role: RoleName,
+) -> Result<ReplicationReceipt, TrustError> {
+    let _ = role; // the role is recorded by the caller choosing which keys to sign with
+    let body = canonical_receipt_bytes(receipt)?;
+    let mut signed = receipt.clone();
+    signed.signatures.extend(sign_bytes(&body, keys));
+    Ok(signed)
+}
+
+/// Verify that `receipt.signatures` meets `role`'s threshold in `root`,
+/// over the receipt's canonical bytes. Fails closed: an unknown keyid or
+/// an invalid signature is rejected outright rather than merely uncounted.
+pub fn verify_receipt(
+    receipt: &ReplicationReceipt,
+    root: &RootDelegation,
+    role: RoleName,
+) -> Result<(), TrustError> {
+    let body = canonical_receipt_bytes(receipt)?;
+    verify_threshold(&body, &receipt.signatures, root, role)
+}
+
+/// Verify that a new root delegation's signatures meet the *previous*
+/// root's `root`-role threshold, authorizing the rotation. Returns the new
+/// `root` on success.
+pub fn verify_root_rotation(
+    new_root: &SignedRoot,
+    previous_root: &RootDelegation,
+) -> Result<RootDelegation, TrustError> {
+    let body = canonical_root_bytes(&new_root.root)?;
+    verify_threshold(&body, &new_root.signatures, previous_root, RoleName::Root)?;
+    Ok(new_root.root.clone())
+}
+
+fn verify_threshold(
+    body: &[u8],
+    signatures: &[Signature],
+    root: &RootDelegation,
+    role: RoleName,
+) -> Result<(), TrustError> {
+    let delegation = root.roles.get(&role).ok_or_else(|| TrustError::UnknownRole {
+        role: role.as_str().to_string(),
+    })?;
+
+    if signatures.is_empty() {
+        return Err(TrustError::ThresholdNotMet {
+            role: role.as_str().to_string(),
+            valid: 0,
+            threshold: delegation.threshold,
+        });
+    }
+
+    let mut valid_keyids = std::collections::BTreeSet::new();
+    for signature in signatures {
+        if !delegation.keyids.iter().any(|k| keyid_of_hex_key(k) == signature.keyid) {
+            return Err(TrustError::UnknownKeyId {
+                keyid: signature.keyid.clone(),
+                role: role.as_str().to_string(),
+            });
+        }
+
+        let raw_key = delegation
+            .keyids
+            .iter()
+            .find(|k| keyid_of_hex_key(k) == signature.keyid)
+            .expect("just matched above");
+        let key_bytes: [u8; 32] = hex::decode(raw_key)?
+            .try_into()
+            .map_err(|_| TrustError::Malformed(format!("public key {raw_key} is not 32 bytes")))?;
+        let verifying_key = VerifyingKey::from_bytes(&key_bytes)
+            .map_err(|e| TrustError::Malformed(e.to_string()))?;
+
+        let sig_bytes: [u8; 64] = hex::decode(&signature.sig)?
+            .try_into()
+            .map_err(|_| TrustError::Malformed(format!("signature {} is not 64 bytes", signature.sig)))?;
+        let sig = ed25519_dalek::Signature::from_bytes(&sig_bytes);
+
+        verifying_key
+            .verify(body, &sig)
+            .map_err(|_| TrustError::InvalidSignature {
+                keyid: signature.keyid.clone(),
+            })?;
+
+        valid_keyids.insert(signature.keyid.clone());
+    }
+
+    if valid_keyids.len() < delegation.threshold {
+        return Err(TrustError::ThresholdNotMet {
+            role: role.as_str().to_string(),
+            valid: valid_keyids.len(),
+            threshold: delegation.threshold,
+        });
+    }
+
+    Ok(())
+}
+
+fn keyid_of_hex_key(hex_key: &str) -> String {
+    match hex::decode(hex_key) {
+        Ok(bytes) => {
+            let mut hasher = Sha256::new();
+            hasher.update(&bytes);
+            hex::encode(hasher.finalize())
+        }
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gate::{RGateStatus, ReplicationGateResult};
+
+    fn make_receipt() -> ReplicationReceipt {
+        let gates = vec![ReplicationGateResult {
+            gate: "RG0_POLICY".to_string(),
+            status: RGateStatus::Pass,
+            detail: "ok".to_string(),
+        }];
+        ReplicationReceipt::new(
+            "replicate",
+            "R0_LOCAL_CLONE",
+            "fp",
+            Some("src_hash"),
+            Some("tgt_hash"),
+            gates,
+        )
+    }
+
+    fn root_with_targets(keys: &[SigningKey], threshold: usize) -> RootDelegation {
+        let keyids = keys
+            .iter()
+            .map(|k| hex::encode(k.verifying_key().as_bytes()))
+            .collect();
+        let mut roles = BTreeMap::new();
+        roles.insert(
+            RoleName::Targets,
+            RoleDelegation { keyids, threshold },
+        );
+        RootDelegation { version: 1, roles }
+    }
+
+    #[test]
+    fn test_sign_and_verify_receipt_roundtrip() {
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let root = root_with_targets(std::slice::from_ref(&key), 1);
+
+        let receipt = make_receipt();
+        let signed = sign_receipt(&receipt, std::slice::from_ref(&key), RoleName::Targets).unwrap();
+        assert_eq!(signed.signatures.len(), 1);
+
+        verify_receipt(&signed, &root, RoleName::Targets).unwrap();
+    }
+
+    #[test]
+    fn test_verify_fails_closed_on_unmet_threshold() {
+        let key_a = SigningKey::generate(&mut rand_core::OsRng);
+        let key_b = SigningKey::generate(&mut rand_core::OsRng);
+        let root = root_with_targets(&[key_a.clone(), key_b.clone()], 2);
+
+        let receipt = make_receipt();
+        let signed = sign_receipt(&receipt, std::slice::from_ref(&key_a), RoleName::Targets).unwrap();
+
+        let err = verify_receipt(&signed, &root, RoleName::Targets).unwrap_err();
+        assert!(matches!(err, TrustError::ThresholdNotMet { .. }));
+    }
+
+    #[test]
+    fn test_verify_fails_closed_on_unknown_keyid() {
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let stranger = SigningKey::generate(&mut rand_core::OsRng);
+        let root = root_with_targets(std::slice::from_ref(&key), 1);
+
+        let receipt = make_receipt();
+        let signed = sign_receipt(&receipt, std::slice::from_ref(&stranger), RoleName::Targets).unwrap();
+
+        let err = verify_receipt(&signed, &root, RoleName::Targets).unwrap_err();
+        assert!(matches!(err, TrustError::UnknownKeyId { .. }));
+    }
+
+    #[test]
+    fn test_verify_fails_closed_on_tampered_receipt() {
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let root = root_with_targets(std::slice::from_ref(&key), 1);
+
+        let receipt = make_receipt();
+        let mut signed = sign_receipt(&receipt, std::slice::from_ref(&key), RoleName::Targets).unwrap();
+        signed.source_pack_hash = Some("tampered".to_string());
+
+        let err = verify_receipt(&signed, &root, RoleName::Targets).unwrap_err();
+        assert!(matches!(err, TrustError::InvalidSignature { .. }));
+    }
+
+    #[test]
+    fn test_root_rotation_requires_previous_root_threshold() {
+        let old_root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let mut old_roles = BTreeMap::new();
+        old_roles.insert(
+            RoleName::Root,
+            RoleDelegation {
+                keyids: vec![hex::encode(old_root_key.verifying_key().as_bytes())],
+                threshold: 1,
+            },
+        );
+        let old_root = RootDelegation {
+            version: 1,
+            roles: old_roles,
+        };
+
+        let new_targets_key = SigningKey::generate(&mut rand_core::OsRng);
+        let new_root = root_with_targets(std::slice::from_ref(&new_targets_key), 1);
+        let new_root_bytes = canonical_root_bytes(&new_root).unwrap();
+        let signed_new_root = SignedRoot {
+            root: new_root,
+            signatures: sign_bytes(&new_root_bytes, std::slice::from_ref(&old_root_key)),
+        };
+
+        let accepted = verify_root_rotation(&signed_new_root, &old_root).unwrap();
+        assert_eq!(accepted.version, 1);
+    }
+
+    #[test]
+    fn test_root_rotation_rejects_unauthorized_signer() {
+        let old_root_key = SigningKey::generate(&mut rand_core::OsRng);
+        let mut old_roles = BTreeMap::new();
+        old_roles.insert(
+            RoleName::Root,
+            RoleDelegation {
+                keyids: vec![hex::encode(old_root_key.verifying_key().as_bytes())],
+                threshold: 1,
+            },
+        );
+        let old_root = RootDelegation {
+            version: 1,
+            roles: old_roles,
+        };
+
+        let impostor_key = SigningKey::generate(&mut rand_core::OsRng);
+        let new_targets_key = SigningKey::generate(&mut rand_core::OsRng);
+        let new_root = root_with_targets(std::slice::from_ref(&new_targets_key), 1);
+        let new_root_bytes = canonical_root_bytes(&new_root).unwrap();
+        let signed_new_root = SignedRoot {
+            root: new_root,
+            signatures: sign_bytes(&new_root_bytes, std::slice::from_ref(&impostor_key)),
+        };
+
+        let err = verify_root_rotation(&signed_new_root, &old_root).unwrap_err();
+        assert!(matches!(err, TrustError::UnknownKeyId { .. }));
+    }
+}
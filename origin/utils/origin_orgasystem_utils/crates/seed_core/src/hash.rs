@@ -0,0 +1,127 @@
+//! Pluggable hash algorithm + encoding for pack/manifest content digests.
+//!
+//! A seed's own fingerprint is still computed and stored as SHA-256 hex (see
+//! [`crate::Seed`] and [`crate::compute_sha256`]), but verification now
+//! accepts a self-describing multihash alternative (see [`crate::HashAlgo`]
+//! and [`crate::compute_multihash`]) so a steward can pin a fingerprint to
+//! BLAKE3 without invalidating fingerprints already on file. This module is
+//! a separate, unrelated concern: content hashing that travels with the
+//! artifact it digests (pack manifests, `pack_hash`), where a steward may
+//! want BLAKE3's speed on a large repo while packs already hashed with
+//! SHA-256 stay verifiable.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+/// Hash function used to digest pack/manifest content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+/// Text encoding used to render a digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HashEncoding {
+    /// Lowercase hex.
+    Hex,
+    /// RFC 4648 base32, lowercase, no padding.
+    Base32,
+}
+
+/// A hash algorithm + encoding pair, carried alongside the digests it
+/// produces so they remain interpretable even after a steward's default
+/// changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashScheme {
+    pub algorithm: HashAlgorithm,
+    pub encoding: HashEncoding,
+}
+
+impl Default for HashScheme {
+    /// SHA-256 hex: the scheme every pack produced before this type existed
+    /// implicitly used, so old manifests without a `hash_scheme` field
+    /// (`#[serde(default)]`) stay verifiable.
+    fn default() -> Self {
+        Self {
+            algorithm: HashAlgorithm::Sha256,
+            encoding: HashEncoding::Hex,
+        }
+    }
+}
+
+impl HashScheme {
+    /// Digest `data`, rendered in this scheme's encoding.
+    pub fn digest(&self, data: &[u8]) -> String {
+        let raw: Vec<u8> = match self.algorithm {
+            HashAlgorithm::Sha256 => {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Sha512 => {
+                let mut hasher = Sha512::new();
+                hasher.update(data);
+                hasher.finalize().to_vec()
+            }
+            HashAlgorithm::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        };
+        self.encoding.encode(&raw)
+    }
+}
+
+impl HashEncoding {
+    fn encode(&self, raw: &[u8]) -> String {
+        match self {
+            HashEncoding::Hex => hex::encode(raw),
+            HashEncoding::Base32 => data_encoding::BASE32_NOPAD.encode(raw).to_lowercase(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex_matches_compute_sha256() {
+        let scheme = HashScheme::default();
+        assert_eq!(scheme.digest(b"hello world"), crate::compute_sha256(b"hello world"));
+    }
+
+    #[test]
+    fn test_digest_deterministic_across_algorithms() {
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Sha512, HashAlgorithm::Blake3] {
+            let scheme = HashScheme {
+                algorithm,
+                encoding: HashEncoding::Hex,
+            };
+            assert_eq!(scheme.digest(b"some content"), scheme.digest(b"some content"));
+        }
+    }
+
+    #[test]
+    fn test_base32_encoding_is_lowercase_and_unpadded() {
+        let scheme = HashScheme {
+            algorithm: HashAlgorithm::Blake3,
+            encoding: HashEncoding::Base32,
+        };
+        let digest = scheme.digest(b"some content");
+        assert_eq!(digest, digest.to_lowercase());
+        assert!(!digest.contains('='));
+    }
+
+    #[test]
+    fn test_different_algorithms_produce_different_digests() {
+        let sha256 = HashScheme {
+            algorithm: HashAlgorithm::Sha256,
+            encoding: HashEncoding::Hex,
+        };
+        let blake3 = HashScheme {
+            algorithm: HashAlgorithm::Blake3,
+            encoding: HashEncoding::Hex,
+        };
+        assert_ne!(sha256.digest(b"some content"), blake3.digest(b"some content"));
+    }
+}
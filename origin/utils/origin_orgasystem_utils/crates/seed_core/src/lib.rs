@@ -4,10 +4,15 @@
 //! (pack, manifest, receipt) must bind to the seed fingerprint. If the
 //! fingerprint is absent or mismatched, operations FAIL CLOSED.
 
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
+pub mod hash;
+
 /// Default relative path from the workspace root to the canonical seed file.
 pub const SEED_RELATIVE_PATH: &str = "spec/seed/denotum.seed.2i.yaml";
 
@@ -21,6 +26,90 @@ pub enum SeedError {
     FingerprintMismatch { expected: String, actual: String },
     #[error("seed fingerprint missing in artifact")]
     FingerprintMissing,
+    #[error("seed signature invalid: {reason}")]
+    SignatureInvalid { reason: String },
+    #[error("seed signature public key {public_key} is not in the trusted key set")]
+    UntrustedKey { public_key: String },
+    #[error("seed fingerprint uses unsupported hash algorithm code {code:?}")]
+    UnsupportedAlgo { code: String },
+}
+
+/// Hash algorithm a seed fingerprint's multihash prefix can name.
+///
+/// Distinct from [`hash::HashAlgorithm`]: that one selects the scheme used to
+/// digest pack/manifest *content*, while this one selects the algorithm a
+/// seed *fingerprint* itself was computed with, so a steward can migrate off
+/// SHA-256 without invalidating every fingerprint already on file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    Sha2_256,
+    Blake3,
+}
+
+impl HashAlgo {
+    /// Multihash-style algorithm code used as the fingerprint's prefix.
+    fn code(self) -> &'static str {
+        match self {
+            HashAlgo::Sha2_256 => "sha2-256",
+            HashAlgo::Blake3 => "blake3",
+        }
+    }
+
+    /// Parse a multihash algorithm code, `None` if it isn't recognized.
+    fn parse(code: &str) -> Option<Self> {
+        match code {
+            "sha2-256" => Some(HashAlgo::Sha2_256),
+            "blake3" => Some(HashAlgo::Blake3),
+            _ => None,
+        }
+    }
+}
+
+/// Digest `data` with `algo`, rendered as a self-describing multihash string
+/// `<algo-code>-<hex-digest>` (e.g. `sha2-256-<64 hex>`, `blake3-<64 hex>`).
+pub fn compute_multihash(algo: HashAlgo, data: &[u8]) -> String {
+    let raw: [u8; 32] = match algo {
+        HashAlgo::Sha2_256 => {
+            let mut hasher = Sha256::new();
+            hasher.update(data);
+            hasher.finalize().into()
+        }
+        HashAlgo::Blake3 => *blake3::hash(data).as_bytes(),
+    };
+    format!("{}-{}", algo.code(), hex::encode(raw))
+}
+
+/// Split a fingerprint string into its algorithm and hex digest. Bare
+/// 64-char hex (no algorithm prefix) is accepted for backward compatibility
+/// and treated as `sha2-256`, matching every fingerprint produced before
+/// this type existed.
+fn parse_fingerprint(fingerprint: &str) -> Result<(HashAlgo, &str), SeedError> {
+    match fingerprint.rsplit_once('-') {
+        Some((code, digest)) => HashAlgo::parse(code)
+            .map(|algo| (algo, digest))
+            .ok_or_else(|| SeedError::UnsupportedAlgo {
+                code: code.to_string(),
+            }),
+        None if fingerprint.len() == 64 && fingerprint.chars().all(|c| c.is_ascii_hexdigit()) => {
+            Ok((HashAlgo::Sha2_256, fingerprint))
+        }
+        None => Err(SeedError::UnsupportedAlgo {
+            code: fingerprint.to_string(),
+        }),
+    }
+}
+
+/// A detached ed25519 signature over a seed's fingerprint, attesting that
+/// the named steward key produced or endorses this exact seed - unlike the
+/// fingerprint alone, which anyone can recompute, only the holder of the
+/// matching private key can produce this. Hex-encoded throughout, the same
+/// convention `replication_core::trust::Signature` uses for its keyids/sigs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedSignature {
+    /// Hex-encoded ed25519 public key of the signing steward.
+    pub public_key: String,
+    /// Hex-encoded ed25519 signature over the seed's raw SHA-256 digest.
+    pub signature: String,
 }
 
 /// A loaded seed with its raw bytes and computed fingerprint.
@@ -57,9 +146,37 @@ impl Seed {
         Self::load(&path)
     }
 
+    /// Compute a seed's fingerprint by streaming the file in fixed-size
+    /// chunks rather than reading it fully into memory, for verify-and-
+    /// discard workflows that never need the seed's bytes afterward.
+    pub fn load_streaming(path: &Path) -> Result<SeedFingerprint, SeedError> {
+        if !path.exists() {
+            return Err(SeedError::NotFound {
+                path: path.to_path_buf(),
+            });
+        }
+        Ok(SeedFingerprint {
+            fingerprint: file_sha256(path)?,
+            source_path: path.to_path_buf(),
+        })
+    }
+
     /// Verify that a given fingerprint matches this seed's fingerprint.
+    ///
+    /// `expected` may be a bare legacy SHA-256 hex digest or a self-describing
+    /// multihash string (`<algo-code>-<hex-digest>`, see [`HashAlgo`]); either
+    /// way this recomputes the seed's digest under the named algorithm and
+    /// compares, so a fingerprint pinned to e.g. `blake3-...` verifies even
+    /// though `self.fingerprint` itself stays SHA-256.
     pub fn verify_fingerprint(&self, expected: &str) -> Result<(), SeedError> {
-        if self.fingerprint != expected {
+        let (algo, expected_digest) = parse_fingerprint(expected)?;
+        let actual = compute_multihash(algo, &self.bytes);
+        let actual_digest = actual
+            .strip_prefix(algo.code())
+            .and_then(|rest| rest.strip_prefix('-'))
+            .expect("compute_multihash always prefixes with its own algo code");
+
+        if actual_digest != expected_digest {
             return Err(SeedError::FingerprintMismatch {
                 expected: expected.to_string(),
                 actual: self.fingerprint.clone(),
@@ -76,6 +193,81 @@ impl Seed {
             Some(fp) => self.verify_fingerprint(fp),
         }
     }
+
+    /// Sign this seed's fingerprint (its raw 32-byte SHA-256 digest, not
+    /// the hex rendering) with `signing_key`, producing a detached
+    /// [`SeedSignature`] a steward can attach to any artifact that already
+    /// carries the fingerprint itself.
+    pub fn sign(&self, signing_key: &SigningKey) -> SeedSignature {
+        let digest = self.fingerprint_digest();
+        let signature: Signature = signing_key.sign(&digest);
+        SeedSignature {
+            public_key: hex::encode(signing_key.verifying_key().to_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    /// Verify `signature` over this seed's fingerprint against
+    /// `trusted_keys`. Fails closed: a malformed key or signature, a key
+    /// absent from `trusted_keys`, or a signature that doesn't verify are
+    /// all errors.
+    pub fn verify_signature(
+        &self,
+        signature: &SeedSignature,
+        trusted_keys: &[VerifyingKey],
+    ) -> Result<(), SeedError> {
+        let key_bytes: [u8; 32] = hex::decode(&signature.public_key)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| SeedError::SignatureInvalid {
+                reason: "malformed public key".to_string(),
+            })?;
+        let verifying_key = VerifyingKey::from_bytes(&key_bytes).map_err(|e| SeedError::SignatureInvalid {
+            reason: format!("malformed public key: {e}"),
+        })?;
+
+        if !trusted_keys.contains(&verifying_key) {
+            return Err(SeedError::UntrustedKey {
+                public_key: signature.public_key.clone(),
+            });
+        }
+
+        let sig_bytes: [u8; 64] = hex::decode(&signature.signature)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| SeedError::SignatureInvalid {
+                reason: "malformed signature".to_string(),
+            })?;
+        let sig = Signature::from_bytes(&sig_bytes);
+
+        verifying_key
+            .verify(&self.fingerprint_digest(), &sig)
+            .map_err(|_| SeedError::SignatureInvalid {
+                reason: "signature does not verify against this seed's fingerprint".to_string(),
+            })
+    }
+
+    /// Like [`Self::assert_binding`], but also requires `signature` to be a
+    /// valid steward signature from `trusted_keys` over this seed's
+    /// fingerprint - an artifact must carry both a matching fingerprint
+    /// and a trusted signature to pass.
+    pub fn assert_signed_binding(
+        &self,
+        fingerprint: Option<&str>,
+        signature: &SeedSignature,
+        trusted_keys: &[VerifyingKey],
+    ) -> Result<(), SeedError> {
+        self.assert_binding(fingerprint)?;
+        self.verify_signature(signature, trusted_keys)
+    }
+
+    /// Raw 32-byte SHA-256 digest behind `self.fingerprint`'s hex rendering.
+    fn fingerprint_digest(&self) -> [u8; 32] {
+        hex::decode(&self.fingerprint)
+            .expect("fingerprint is always a hex-encoded SHA-256 digest")
+            .try_into()
+            .expect("SHA-256 digest is always 32 bytes")
+    }
 }
 
 /// Compute SHA-256 hex digest of arbitrary bytes.
@@ -85,10 +277,97 @@ pub fn compute_sha256(data: &[u8]) -> String {
     hex::encode(hasher.finalize())
 }
 
-/// Compute SHA-256 hex digest of a file.
+/// Bytes read per chunk by [`file_sha256`] and [`StreamingHasher`] consumers,
+/// chosen to keep peak memory use well below a large CPACK payload's size.
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Compute SHA-256 hex digest of a file by reading it in fixed-size chunks,
+/// so hashing a large CPACK payload never requires holding the whole file
+/// in memory at once (unlike [`compute_sha256`], which takes an in-memory
+/// slice).
 pub fn file_sha256(path: &Path) -> Result<String, SeedError> {
-    let bytes = std::fs::read(path)?;
-    Ok(compute_sha256(&bytes))
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = StreamingHasher::new();
+    let mut buf = [0u8; STREAM_CHUNK_SIZE];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize_hex())
+}
+
+/// Incremental SHA-256 hasher. Lets a caller that already streams bytes
+/// through some other pipeline (e.g. a zstd encoder or decoder) fold each
+/// chunk into a digest as it flows, instead of buffering the whole payload
+/// purely to hash it afterward.
+pub struct StreamingHasher(Sha256);
+
+impl Default for StreamingHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamingHasher {
+    pub fn new() -> Self {
+        Self(Sha256::new())
+    }
+
+    /// Fold `chunk` into the running digest.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.0.update(chunk);
+    }
+
+    /// Consume the hasher and return the final digest as lowercase hex.
+    pub fn finalize_hex(self) -> String {
+        hex::encode(self.0.finalize())
+    }
+}
+
+/// A seed's SHA-256 fingerprint without the source bytes retained, for
+/// verify-and-discard workflows where holding the whole seed in memory
+/// isn't worth it once its fingerprint has been checked.
+#[derive(Debug, Clone)]
+pub struct SeedFingerprint {
+    /// SHA-256 hex fingerprint of the seed bytes.
+    pub fingerprint: String,
+    /// Path from which the seed was loaded.
+    pub source_path: PathBuf,
+}
+
+impl SeedFingerprint {
+    /// Verify `expected` against this fingerprint. Since the source bytes
+    /// weren't retained, only the SHA-256 digest this handle was streamed
+    /// with can be checked; a multihash naming any other algorithm is
+    /// rejected as unsupported rather than silently treated as a mismatch.
+    pub fn verify_fingerprint(&self, expected: &str) -> Result<(), SeedError> {
+        let (algo, expected_digest) = parse_fingerprint(expected)?;
+        if algo != HashAlgo::Sha2_256 {
+            return Err(SeedError::UnsupportedAlgo {
+                code: algo.code().to_string(),
+            });
+        }
+        if expected_digest != self.fingerprint {
+            return Err(SeedError::FingerprintMismatch {
+                expected: expected.to_string(),
+                actual: self.fingerprint.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Assert that a fingerprint string is non-empty and matches this
+    /// fingerprint, mirroring [`Seed::assert_binding`].
+    pub fn assert_binding(&self, fingerprint: Option<&str>) -> Result<(), SeedError> {
+        match fingerprint {
+            None => Err(SeedError::FingerprintMissing),
+            Some("") => Err(SeedError::FingerprintMissing),
+            Some(fp) => self.verify_fingerprint(fp),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -202,4 +481,180 @@ mod tests {
         let seed = Seed::load_from_workspace(dir.path()).unwrap();
         assert_eq!(seed.bytes, b"workspace seed");
     }
+
+    #[test]
+    fn test_sign_and_verify_signature_with_trusted_key() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let signature = seed.sign(&key);
+        seed.verify_signature(&signature, &[key.verifying_key()]).unwrap();
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_untrusted_key() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let other_key = SigningKey::generate(&mut rand_core::OsRng);
+        let signature = seed.sign(&key);
+
+        let err = seed.verify_signature(&signature, &[other_key.verifying_key()]).unwrap_err();
+        assert!(matches!(err, SeedError::UntrustedKey { .. }));
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_signature_over_a_different_seed() {
+        let mut tmp_a = NamedTempFile::new().unwrap();
+        tmp_a.write_all(b"seed a").unwrap();
+        tmp_a.flush().unwrap();
+        let seed_a = Seed::load(tmp_a.path()).unwrap();
+
+        let mut tmp_b = NamedTempFile::new().unwrap();
+        tmp_b.write_all(b"seed b").unwrap();
+        tmp_b.flush().unwrap();
+        let seed_b = Seed::load(tmp_b.path()).unwrap();
+
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let signature = seed_a.sign(&key);
+
+        let err = seed_b.verify_signature(&signature, &[key.verifying_key()]).unwrap_err();
+        assert!(matches!(err, SeedError::SignatureInvalid { .. }));
+    }
+
+    #[test]
+    fn test_assert_signed_binding_requires_both_fingerprint_and_signature() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+        let fp = seed.fingerprint.clone();
+
+        let key = SigningKey::generate(&mut rand_core::OsRng);
+        let stranger = SigningKey::generate(&mut rand_core::OsRng);
+        let signature = seed.sign(&key);
+
+        seed.assert_signed_binding(Some(&fp), &signature, &[key.verifying_key()])
+            .unwrap();
+
+        assert!(matches!(
+            seed.assert_signed_binding(None, &signature, &[key.verifying_key()]),
+            Err(SeedError::FingerprintMissing)
+        ));
+        assert!(matches!(
+            seed.assert_signed_binding(Some(&fp), &signature, &[stranger.verifying_key()]),
+            Err(SeedError::UntrustedKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_fingerprint_accepts_blake3_multihash() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+
+        let blake3_fp = compute_multihash(HashAlgo::Blake3, &seed.bytes);
+        seed.verify_fingerprint(&blake3_fp).unwrap();
+    }
+
+    #[test]
+    fn test_verify_fingerprint_accepts_sha2_256_multihash() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+
+        let prefixed_fp = compute_multihash(HashAlgo::Sha2_256, &seed.bytes);
+        assert_ne!(prefixed_fp, seed.fingerprint, "multihash form carries the algo prefix");
+        seed.verify_fingerprint(&prefixed_fp).unwrap();
+    }
+
+    #[test]
+    fn test_verify_fingerprint_rejects_unsupported_algo() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+
+        let err = seed.verify_fingerprint("md5-deadbeef").unwrap_err();
+        assert!(matches!(err, SeedError::UnsupportedAlgo { code } if code == "md5"));
+    }
+
+    #[test]
+    fn test_verify_fingerprint_rejects_blake3_digest_of_wrong_content() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+        let seed = Seed::load(tmp.path()).unwrap();
+
+        let wrong_fp = compute_multihash(HashAlgo::Blake3, b"different content");
+        let err = seed.verify_fingerprint(&wrong_fp).unwrap_err();
+        assert!(matches!(err, SeedError::FingerprintMismatch { .. }));
+    }
+
+    #[test]
+    fn test_streaming_hasher_matches_compute_sha256() {
+        let mut hasher = StreamingHasher::new();
+        hasher.update(b"hello ");
+        hasher.update(b"world");
+        assert_eq!(hasher.finalize_hex(), compute_sha256(b"hello world"));
+    }
+
+    #[test]
+    fn test_file_sha256_matches_in_memory_hash_across_multiple_chunks() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        let content = vec![0x5au8; STREAM_CHUNK_SIZE * 3 + 17];
+        tmp.write_all(&content).unwrap();
+        tmp.flush().unwrap();
+
+        assert_eq!(file_sha256(tmp.path()).unwrap(), compute_sha256(&content));
+    }
+
+    #[test]
+    fn test_load_streaming_computes_fingerprint_without_retaining_bytes() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+
+        let eager = Seed::load(tmp.path()).unwrap();
+        let streamed = Seed::load_streaming(tmp.path()).unwrap();
+        assert_eq!(streamed.fingerprint, eager.fingerprint);
+        streamed.verify_fingerprint(&eager.fingerprint).unwrap();
+    }
+
+    #[test]
+    fn test_load_streaming_not_found() {
+        let result = Seed::load_streaming(Path::new("/nonexistent/path/seed.yaml"));
+        assert!(matches!(result, Err(SeedError::NotFound { .. })));
+    }
+
+    #[test]
+    fn test_seed_fingerprint_assert_binding_missing() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+
+        let streamed = Seed::load_streaming(tmp.path()).unwrap();
+        assert!(streamed.assert_binding(None).is_err());
+        assert!(streamed.assert_binding(Some("")).is_err());
+    }
+
+    #[test]
+    fn test_seed_fingerprint_rejects_non_sha2_256_multihash() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        tmp.write_all(b"content").unwrap();
+        tmp.flush().unwrap();
+
+        let streamed = Seed::load_streaming(tmp.path()).unwrap();
+        let blake3_fp = compute_multihash(HashAlgo::Blake3, b"content");
+        let err = streamed.verify_fingerprint(&blake3_fp).unwrap_err();
+        assert!(matches!(err, SeedError::UnsupportedAlgo { .. }));
+    }
 }